@@ -0,0 +1,48 @@
+//! Measures how fast the backend draws dense lines, made of many separate line segments like the
+//! ones plotters draws for meshes and series, with and without batching them into paths.
+//!
+//! Run with `cargo bench --bench draw_line`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use egui::{CentralPanel, Context};
+use egui_plotter::EguiBackend;
+use plotters::prelude::*;
+
+const SEGMENTS: i32 = 50_000;
+
+/// Draw a zigzag of `SEGMENTS` line segments and tessellate it.
+fn draw_frame(ctx: &Context, batch_lines: bool) {
+    let output = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let mut backend = EguiBackend::new(ui).batch_lines(batch_lines);
+
+            for i in 0..SEGMENTS {
+                let from = (i / 64, 100 + i % 2 * 50);
+                let to = ((i + 1) / 64, 100 + (i + 1) % 2 * 50);
+
+                backend.draw_line(from, to, &BLUE).unwrap();
+            }
+        });
+    });
+
+    let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+
+    std::hint::black_box(primitives);
+}
+
+fn draw_line(c: &mut Criterion) {
+    let mut group = c.benchmark_group("draw_line");
+
+    for (name, batch_lines) in [("unbatched", false), ("batched", true)] {
+        let ctx = Context::default();
+
+        group.bench_function(BenchmarkId::new(name, SEGMENTS), |b| {
+            b.iter(|| draw_frame(&ctx, batch_lines))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, draw_line);
+criterion_main!(benches);
@@ -2,10 +2,8 @@
 //! [eframe](https://docs.rs/eframe/0.22.0/eframe/index.html#usage-native) and
 //! [plotters](https://github.com/plotters-rs/plotters/blob/master/plotters/examples/3d-plot.rs)
 
-use std::time::Duration;
-
 use egui::{self, CentralPanel, Visuals};
-use egui_plotter::EguiBackend;
+use egui_plotter::{AnimationDriver, EguiBackend};
 use plotters::prelude::*;
 const MOVE_SCALE: f32 = 0.01;
 const SCROLL_SCALE: f32 = 0.001;
@@ -26,6 +24,7 @@ struct ThreeD {
     chart_scale: f32,
     chart_pitch_vel: f32,
     chart_yaw_vel: f32,
+    driver: AnimationDriver,
 }
 
 impl ThreeD {
@@ -46,6 +45,7 @@ impl ThreeD {
             chart_scale: 0.9,
             chart_pitch_vel: 0.0,
             chart_yaw_vel: 0.0,
+            driver: AnimationDriver::new(100.0),
         }
     }
 }
@@ -84,7 +84,7 @@ impl eframe::App for ThreeD {
             let z_axis = (-3.0..3.0).step(0.1);
 
             let mut chart = ChartBuilder::on(&root)
-                .caption(format!("3D Plot Test"), (FontFamily::SansSerif, 20))
+                .caption("3D Plot Test", (FontFamily::SansSerif, 20))
                 .build_cartesian_3d(x_axis, -3.0..3.0, z_axis)
                 .unwrap();
 
@@ -138,7 +138,6 @@ impl eframe::App for ThreeD {
         });
 
         // Limit framerate to 100fps
-        std::thread::sleep(Duration::from_millis(10));
-        ctx.request_repaint();
+        self.driver.request_repaint(ctx);
     }
 }
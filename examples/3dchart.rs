@@ -2,10 +2,8 @@
 //! [eframe](https://docs.rs/eframe/0.22.0/eframe/index.html#usage-native) and
 //! [plotters](https://github.com/plotters-rs/plotters/blob/master/plotters/examples/3d-plot.rs)
 
-use std::time::Duration;
-
 use eframe::egui::{self, CentralPanel, Visuals};
-use egui_plotter::{Chart, MouseConfig};
+use egui_plotter::{AnimationDriver, Chart, MouseConfig};
 use plotters::prelude::*;
 
 fn main() {
@@ -20,6 +18,7 @@ fn main() {
 
 struct Chart3d {
     chart: Chart<()>,
+    driver: AnimationDriver,
 }
 
 impl Chart3d {
@@ -39,6 +38,7 @@ impl Chart3d {
             .mouse(MouseConfig::enabled())
             .pitch(0.7)
             .yaw(0.7)
+            .three_d(true)
             .builder_cb(Box::new(|area, transform, _d| {
                 // Build a chart like you would in any other plotter chart.
                 // The drawing area and projection transformations are provided
@@ -47,16 +47,11 @@ impl Chart3d {
                 let z_axis = (-3.0..3.0).step(0.1);
 
                 let mut chart = ChartBuilder::on(area)
-                    .caption(format!("3D Plot Test"), (FontFamily::SansSerif, 20))
+                    .caption("3D Plot Test", (FontFamily::SansSerif, 20))
                     .build_cartesian_3d(x_axis, -3.0..3.0, z_axis)
                     .unwrap();
 
-                chart.with_projection(|mut pb| {
-                    pb.yaw = transform.yaw;
-                    pb.pitch = transform.pitch;
-                    pb.scale = 0.7; // Set scale to 0.7 to avoid artifacts caused by plotter's renderer
-                    pb.into_matrix()
-                });
+                chart.with_projection(|pb| transform.projection(pb));
 
                 chart
                     .configure_axes()
@@ -98,7 +93,10 @@ impl Chart3d {
                     .unwrap();
             }));
 
-        Self { chart }
+        Self {
+            chart,
+            driver: AnimationDriver::new(100.0),
+        }
     }
 }
 
@@ -109,7 +107,6 @@ impl eframe::App for Chart3d {
         });
 
         // Limit framerate to 100fps
-        std::thread::sleep(Duration::from_millis(10));
-        ctx.request_repaint();
+        self.driver.request_repaint(ctx);
     }
 }
@@ -1,10 +1,13 @@
 //! Simple spiral example using the pre-defined xytime chart type
 
-use std::{f32::consts::PI, time::Duration};
+use std::f32::consts::PI;
 
 use eframe::egui::{self, CentralPanel, Visuals};
-use egui::{Key, Slider, TopBottomPanel};
-use egui_plotter::charts::XyTimeData;
+use egui::{Slider, TopBottomPanel};
+use egui_plotter::{
+    charts::{PlaybackKeys, XyTimeData},
+    AnimationDriver,
+};
 use plotters::style::{
     full_palette::{GREY_700, GREY_900, ORANGE_50, TEAL_400},
     ShapeStyle, WHITE,
@@ -25,6 +28,7 @@ fn main() {
 
 struct SprialExample {
     spiralchart: XyTimeData,
+    driver: AnimationDriver,
 }
 
 impl SprialExample {
@@ -78,7 +82,10 @@ impl SprialExample {
             })
             .text_color(ORANGE_50);
 
-        Self { spiralchart }
+        Self {
+            spiralchart,
+            driver: AnimationDriver::new(100.0),
+        }
     }
 }
 
@@ -124,16 +131,12 @@ impl eframe::App for SprialExample {
         });
 
         CentralPanel::default().show(ctx, |ui| {
+            // Space toggles playback, the arrow keys seek
+            self.spiralchart.handle_input(ui, PlaybackKeys::default());
             self.spiralchart.draw(ui);
         });
 
-        // If space bar is down, start playback
-        if ctx.input(|input| input.key_pressed(Key::Space)) {
-            self.spiralchart.toggle_playback();
-        }
-
         // Limit framerate to 100fps
-        std::thread::sleep(Duration::from_millis(10));
-        ctx.request_repaint();
+        self.driver.request_repaint(ctx);
     }
 }
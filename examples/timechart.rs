@@ -1,10 +1,11 @@
 //! Simple example using the pre-defined time chart type
 
-use std::time::Duration;
-
 use eframe::egui::{self, CentralPanel, Visuals};
-use egui::{Key, Slider, TopBottomPanel};
-use egui_plotter::charts::TimeData;
+use egui::{Slider, TopBottomPanel};
+use egui_plotter::{
+    charts::{PlaybackKeys, TimeData},
+    AnimationDriver,
+};
 
 const DISTANCE_M: [f32; 6] = [0.0, 2.0, 2.8, 3.4, 3.8, 4.0];
 const TIME_S: [f32; 6] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
@@ -21,6 +22,7 @@ fn main() {
 
 struct TimeDataExample {
     timechart: TimeData,
+    driver: AnimationDriver,
 }
 
 impl TimeDataExample {
@@ -43,7 +45,10 @@ impl TimeDataExample {
 
         let timechart = TimeData::new(&points, "meters", "Distance Over Time");
 
-        Self { timechart }
+        Self {
+            timechart,
+            driver: AnimationDriver::new(100.0),
+        }
     }
 }
 
@@ -64,22 +69,21 @@ impl eframe::App for TimeDataExample {
                     }
                 }
 
-                let start_time = self.timechart.start_time();
                 let current_time = self.timechart.current_time();
-                let mut set_time = current_time;
                 let end_time = self.timechart.end_time();
+                let progress = self.timechart.get_progress();
+                let mut set_progress = progress;
                 let mut speed = self.timechart.get_playback_speed();
 
-                let time_slider =
-                    Slider::new(&mut set_time, start_time..=end_time).show_value(false);
+                let progress_slider = Slider::new(&mut set_progress, 0.0..=1.0).show_value(false);
 
                 let speed_slider = Slider::new(&mut speed, 0.25..=4.0).logarithmic(true);
 
-                ui.add(time_slider);
+                ui.add(progress_slider);
                 ui.add(speed_slider);
 
-                if set_time != current_time {
-                    self.timechart.set_time(set_time);
+                if set_progress != progress {
+                    self.timechart.set_progress(set_progress);
                 }
 
                 self.timechart.set_playback_speed(speed);
@@ -89,16 +93,12 @@ impl eframe::App for TimeDataExample {
         });
 
         CentralPanel::default().show(ctx, |ui| {
+            // Space toggles playback, the arrow keys seek
+            self.timechart.handle_input(ui, PlaybackKeys::default());
             self.timechart.draw(ui);
         });
 
-        // If space bar is down, start playback
-        if ctx.input(|input| input.key_pressed(Key::Space)) {
-            self.timechart.toggle_playback();
-        }
-
         // Limit framerate to 100fps
-        std::thread::sleep(Duration::from_millis(10));
-        ctx.request_repaint();
+        self.driver.request_repaint(ctx);
     }
 }
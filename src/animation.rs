@@ -0,0 +1,150 @@
+//! Helpers used to drive animated charts without blocking the UI thread
+
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use egui::Context;
+
+/// Default framerate used by the AnimationDriver.
+pub const DEFAULT_FPS: f32 = 100.0;
+
+#[derive(Debug, Copy, Clone)]
+/// Schedules repaints at a capped framerate.
+///
+/// ## Usage
+/// Instead of sleeping the UI thread and then requesting a repaint, which blocks input handling,
+/// call `request_repaint` once at the end of your `update` function. The driver keeps track of
+/// when the next frame is due and asks egui to repaint at that time using
+/// `Context::request_repaint_after`. Frames triggered early by user input don't push the
+/// scheduled frame back.
+///
+/// ```ignore
+/// fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+///     CentralPanel::default().show(ctx, |ui| {
+///         self.chart.draw(ui);
+///     });
+///
+///     // Limit framerate to 100fps
+///     self.driver.request_repaint(ctx);
+/// }
+/// ```
+pub struct AnimationDriver {
+    frame_time: f64,
+    next_frame: Option<f64>,
+}
+
+impl Default for AnimationDriver {
+    fn default() -> Self {
+        Self::new(DEFAULT_FPS)
+    }
+}
+
+impl AnimationDriver {
+    #[inline]
+    /// Create a new AnimationDriver capped at the given framerate.
+    pub fn new(fps: f32) -> Self {
+        let mut driver = Self {
+            frame_time: 0.0,
+            next_frame: None,
+        };
+
+        driver.set_fps(fps);
+
+        driver
+    }
+
+    #[inline]
+    /// Set the framerate cap. Non-positive framerates are treated as uncapped.
+    pub fn set_fps(&mut self, fps: f32) {
+        self.frame_time = match fps > 0.0 {
+            true => 1.0 / fps as f64,
+            false => 0.0,
+        };
+    }
+
+    #[inline]
+    /// Set the framerate cap. Consumes self.
+    pub fn fps(mut self, fps: f32) -> Self {
+        self.set_fps(fps);
+
+        self
+    }
+
+    #[inline]
+    /// Return the framerate cap.
+    pub fn get_fps(&self) -> f32 {
+        match self.frame_time > 0.0 {
+            true => (1.0 / self.frame_time) as f32,
+            false => f32::INFINITY,
+        }
+    }
+
+    /// Schedule the next frame. Call once per frame.
+    pub fn request_repaint(&mut self, ctx: &Context) {
+        let now = ctx.input(|input| input.time);
+
+        let next_frame = match self.next_frame {
+            Some(next_frame) if next_frame > now => next_frame,
+            _ => now + self.frame_time,
+        };
+
+        self.next_frame = Some(next_frame);
+
+        ctx.request_repaint_after(Duration::from_secs_f64(next_frame - now));
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Shared handle charts report to while they're animating or being interacted with, so the app
+/// only keeps repainting while something on screen is changing.
+///
+/// ## Usage
+/// Give the same handle to each chart with `set_animation_handle`. Charts mark it active when
+/// drawn while playing, zooming smoothly, tracing out their series or being dragged, rotated,
+/// scrolled or box zoomed. Then call `needs_repaint` once at the end of your `update` function,
+/// which reports whether any chart was active since the last call and clears it for the next
+/// frame.
+///
+/// ```ignore
+/// fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+///     CentralPanel::default().show(ctx, |ui| {
+///         self.chart.draw(ui);
+///     });
+///
+///     // Only keep animating while a chart is changing
+///     if self.animation.needs_repaint() {
+///         self.driver.request_repaint(ctx);
+///     }
+/// }
+/// ```
+pub struct AnimationHandle(Rc<Cell<bool>>);
+
+impl AnimationHandle {
+    #[inline]
+    /// Create a new, idle handle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Mark the handle active, so the next call to `needs_repaint` returns true. Charts call
+    /// this themselves, but it can also be used for other animations in the app.
+    pub fn mark_active(&self) {
+        self.0.set(true)
+    }
+
+    #[inline]
+    /// Return true if anything was marked active since the last call, clearing the handle.
+    ///
+    /// ```
+    /// # use egui_plotter::AnimationHandle;
+    /// let animation = AnimationHandle::new();
+    ///
+    /// animation.clone().mark_active();
+    ///
+    /// assert!(animation.needs_repaint());
+    /// assert!(!animation.needs_repaint());
+    /// ```
+    pub fn needs_repaint(&self) -> bool {
+        self.0.replace(false)
+    }
+}
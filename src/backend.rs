@@ -1,13 +1,20 @@
 //! Plotter backend for egui
 
+use std::cell::{Cell, RefCell};
 use std::error::Error as ErrorTrait;
 use std::f32::consts::FRAC_PI_2;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::ops::{Add, AddAssign, MulAssign, Sub, SubAssign};
 
 use egui::{
-    epaint::{PathShape, TextShape},
-    Align, Align2, Color32, FontFamily as EguiFontFamily, FontId, Pos2, Rect, Stroke, Ui,
+    emath::Rot2,
+    epaint::{
+        shape_transform::adjust_colors, ClippedShape, PathShape, TessellationOptions, Tessellator,
+        TextShape,
+    },
+    text::LayoutJob,
+    Align, Align2, Color32, Context, FontFamily as EguiFontFamily, FontId, Galley, Id, Mesh,
+    Painter, Pos2, Rect, Shape, Stroke, TextFormat, Ui, Vec2,
 };
 use plotters_backend::{
     text_anchor::{HPos, Pos, VPos},
@@ -15,6 +22,75 @@ use plotters_backend::{
     FontFamily as PlottersFontFamily,
 };
 
+use crate::legend::LegendRecorder;
+use crate::series::SeriesRegistry;
+
+/// Smallest factor text is shrunk by when using OverflowMode::Shrink.
+const MIN_TEXT_SHRINK: f32 = 0.25;
+/// Space between text and the edge of its background, in points.
+const TEXT_BACKGROUND_PADDING: f32 = 2.0;
+/// Corner radius of text backgrounds, in points.
+const TEXT_BACKGROUND_ROUNDING: f32 = 2.0;
+/// Length of the dashes outlining selection rects, in points.
+const SELECTION_DASH_LENGTH: f32 = 4.0;
+/// Length of the gaps between the dashes outlining selection rects, in points.
+const SELECTION_GAP_LENGTH: f32 = 3.0;
+/// Size of superscripts and subscripts in math text, relative to the rest of the text.
+const MATH_SCRIPT_SCALE: f32 = 0.7;
+/// Distance subscripts in math text are lowered by, relative to the size of the text.
+const MATH_SUBSCRIPT_DROP: f32 = 0.25;
+/// Widest stroke, in points, that's snapped to the pixel grid when pixel snapping is enabled.
+const PIXEL_SNAP_MAX_WIDTH: f32 = 2.0;
+/// Segments that stray from horizontal or vertical by at most this fraction of their length are
+/// snapped, which only gridlines, axes and the like do.
+const PIXEL_SNAP_SLOPE: f32 = 0.01;
+/// Width of each character of fallback text, relative to the size of the text.
+const FALLBACK_GLYPH_WIDTH: f32 = 0.4;
+/// Height of each character of fallback text, relative to the size of the text.
+const FALLBACK_GLYPH_HEIGHT: f32 = 0.7;
+/// Distance between the starts of consecutive characters of fallback text, relative to the size
+/// of the text.
+const FALLBACK_GLYPH_ADVANCE: f32 = 0.55;
+/// Key of the flag stored in a context once missing fonts have been warned about.
+const MISSING_FONTS_WARNED_KEY: &str = "egui_plotter_missing_fonts_warned";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How text that doesn't fit inside the drawing area is handled.
+pub enum OverflowMode {
+    /// Cut the text off at the edge of the drawing area.
+    #[default]
+    Clip,
+    /// Truncate the text and end it with an ellipsis.
+    Ellipsis,
+    /// Shrink the font until the text fits.
+    Shrink,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Classes of shapes drawn by the backend, used to control feathering separately for each. Text
+/// isn't included as egui antialiases it through the font texture rather than by feathering.
+pub enum ShapeClass {
+    /// Lines, paths, single pixels and the outlines of rectangles, other than those counted as
+    /// grid. Includes line series.
+    Lines,
+    /// Lone horizontal and vertical line segments, which is how plotters draws mesh lines, axes
+    /// and tick marks. A series that's a single horizontal or vertical segment counts too.
+    Grid,
+    /// Filled rectangles and polygons. Includes backgrounds, bars and areas.
+    Fills,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Units the stroke width of lines and outlines is given in.
+pub enum StrokeUnits {
+    /// Widths are in points, so lines keep the same thickness on screen whatever the zoom.
+    #[default]
+    Pixels,
+    /// Widths are multiplied by the scale of the backend, so lines grow and shrink with the zoom
+    /// like the rest of the drawing. With different X and Y scales their geometric mean is used.
+    Data,
+}
+
 #[derive(Debug, Clone, Copy)]
 /// Error to be returned by the backend. Since egui doesn't return any errors
 /// on any painter operations, this is a stub type.
@@ -161,7 +237,8 @@ impl From<BackendColor> for EguiBackendColor {
     fn from(value: BackendColor) -> Self {
         let (r, g, b) = value.rgb;
 
-        let a = (value.alpha * 255.0) as u8;
+        // Round rather than truncate so that 50% alpha maps to 128, not 127
+        let a = (value.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
 
         Self { r, g, b, a }
     }
@@ -170,17 +247,126 @@ impl From<BackendColor> for EguiBackendColor {
 impl From<EguiBackendColor> for Color32 {
     #[inline]
     fn from(val: EguiBackendColor) -> Self {
-        Color32::from_rgba_unmultiplied(val.r, val.g, val.b, val.a)
+        // Premultiply in gamma space, which is what plotters' own backends blend in.
+        // Color32::from_rgba_unmultiplied premultiplies in linear space, which makes
+        // translucent colors brighter than plotters intends and oversaturates overlaps.
+        let premultiply = |channel: u8| ((channel as u32 * val.a as u32 + 127) / 255) as u8;
+
+        Color32::from_rgba_premultiplied(
+            premultiply(val.r),
+            premultiply(val.g),
+            premultiply(val.b),
+            val.a,
+        )
     }
 }
 
+/// Shapes of a selection rect in screen coordinates, a translucent fill outlined with dashes in the
+/// ui's selection colors.
+pub(crate) fn selection_shapes(ui: &Ui, rect: Rect) -> Vec<Shape> {
+    let visuals = &ui.visuals().selection;
+
+    let outline = [
+        rect.left_top(),
+        rect.right_top(),
+        rect.right_bottom(),
+        rect.left_bottom(),
+        rect.left_top(),
+    ];
+
+    let mut shapes = vec![Shape::rect_filled(
+        rect,
+        0.0,
+        visuals.bg_fill.gamma_multiply(0.25),
+    )];
+
+    shapes.extend(Shape::dashed_line(
+        &outline,
+        visuals.stroke,
+        SELECTION_DASH_LENGTH,
+        SELECTION_GAP_LENGTH,
+    ));
+
+    shapes
+}
+
+/// Convert a plotters color into an egui color, the same way the backend does when drawing.
+pub(crate) fn backend_color(color: BackendColor) -> Color32 {
+    EguiBackendColor::from(color).into()
+}
+
+/// Return the height halfway between the top and bottom of a set of points.
+fn middle_y(points: &[BackendCoord]) -> i32 {
+    let top = points.iter().map(|(_, y)| *y).min().unwrap_or(0);
+    let bottom = points.iter().map(|(_, y)| *y).max().unwrap_or(0);
+
+    (top + bottom) / 2
+}
+
 /// Plotter backend for egui; simply provide a reference to the ui element to
 /// use.
+///
+/// ## Transparency
+/// Colors are premultiplied the same way plotters' bitmap backend blends them, and every primitive
+/// plotters draws is emitted as a single egui shape, apart from connected lines which are batched
+/// into one path. Overlapping translucent fills therefore blend in the order they were drawn and
+/// match what plotters would render itself.
+///
+/// Text is colored the same way, so translucent text is drawn at the opacity it was given.
+///
+/// If egui has no fonts loaded, as in some headless setups, text is drawn with simple stroked
+/// glyphs instead so axis labels still show, and a warning is logged once.
 pub struct EguiBackend<'a> {
     ui: &'a Ui,
     x: i32,
     y: i32,
-    scale: f32,
+    scale: (f32, f32),
+    label_overflow: OverflowMode,
+    rtl: bool,
+    feather_lines: Option<bool>,
+    feather_grid: Option<bool>,
+    feather_fills: Option<bool>,
+    tessellators: RefCell<[Option<Tessellator>; 2]>,
+    pixel_snap_lines: bool,
+    path_simplify: Option<f32>,
+    capture: Option<&'a RefCell<Vec<ClippedShape>>>,
+    clip: Option<Rect>,
+    size: Option<Vec2>,
+    batch_lines: bool,
+    pending_line: Option<PendingLine>,
+    opacity: f32,
+    transparent: bool,
+    text_background: Option<Color32>,
+    math_text: bool,
+    letter_spacing: f32,
+    stroke_units: StrokeUnits,
+    output_scale: f32,
+    subpixel_offset: (f32, f32),
+    legend: Option<LegendRecorder>,
+    series: Option<SeriesRegistry>,
+    series_drawn: Cell<bool>,
+    layers: RefCell<Vec<(Layer, ClippedShape)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Layer a shape is painted in when ordering shapes by the z-order of their series.
+enum Layer {
+    /// Drawn before any series, such as the background and mesh.
+    Back,
+    /// Drawn for a series, at its z-order.
+    Series(i32),
+    /// Drawn after the series, such as labels and the legend.
+    Front,
+}
+
+#[derive(Debug, Clone)]
+/// Connected line segments of the same style, waiting to be painted as a single path.
+struct PendingLine {
+    points: Vec<Pos2>,
+    stroke: Stroke,
+    clip_rect: Rect,
+    /// Layer of the series the line belongs to.
+    layer: Layer,
 }
 
 impl<'a> EguiBackend<'a> {
@@ -191,7 +377,74 @@ impl<'a> EguiBackend<'a> {
             ui,
             x: 0,
             y: 0,
-            scale: 1.0,
+            scale: (1.0, 1.0),
+            label_overflow: OverflowMode::default(),
+            rtl: false,
+            feather_lines: None,
+            feather_grid: None,
+            feather_fills: None,
+            tessellators: RefCell::default(),
+            pixel_snap_lines: true,
+            path_simplify: None,
+            capture: None,
+            clip: None,
+            size: None,
+            batch_lines: true,
+            pending_line: None,
+            opacity: 1.0,
+            transparent: false,
+            text_background: None,
+            math_text: false,
+            letter_spacing: 0.0,
+            stroke_units: StrokeUnits::default(),
+            output_scale: 1.0,
+            subpixel_offset: (0.0, 0.0),
+            legend: None,
+            series: None,
+            series_drawn: Cell::new(false),
+            layers: RefCell::default(),
+        }
+    }
+
+    #[inline]
+    /// Return the rect the backend draws in, in screen coordinates. Useful for aligning your own
+    /// widgets with the plot.
+    pub fn drawing_rect(&self) -> Rect {
+        let rect = self.ui.max_rect();
+
+        match self.size {
+            Some(size) => Rect::from_min_size(rect.min, size),
+            None => rect,
+        }
+    }
+
+    #[inline]
+    /// Return the rect drawing is clipped to, in screen coordinates.
+    fn clip_rect(&self) -> Rect {
+        self.clip.unwrap_or_else(|| self.output_rect())
+    }
+
+    #[inline]
+    /// Return the rect the drawing covers once the output scale is applied, in screen coordinates.
+    fn output_rect(&self) -> Rect {
+        let rect = self.drawing_rect();
+
+        Rect::from_min_size(rect.min, rect.size() * self.output_scale)
+    }
+
+    /// Return the egui font matching a plotters text style, at the size it's drawn at.
+    fn font_id<TStyle: BackendTextStyle>(&self, style: &TStyle) -> FontId {
+        let family = match style.family() {
+            PlottersFontFamily::Serif | PlottersFontFamily::SansSerif => {
+                EguiFontFamily::Proportional
+            }
+            PlottersFontFamily::Monospace => EguiFontFamily::Monospace,
+            PlottersFontFamily::Name(string) => EguiFontFamily::Name(string.into()),
+        };
+
+        FontId {
+            size: style.size() as f32 * self.output_scale,
+            family,
         }
     }
 
@@ -200,10 +453,15 @@ impl<'a> EguiBackend<'a> {
     fn point_transform(&self, mut point: EguiBackendCoord, bounds: Rect) -> EguiBackendCoord {
         let center = EguiBackendCoord::from(bounds.center()) - EguiBackendCoord::from(bounds.min);
         point -= center;
-        point *= self.scale;
+        point.x *= self.scale.0;
+        point.y *= self.scale.1;
         point += center;
 
         point += EguiBackendCoord::from((self.x, self.y));
+        point.x += self.subpixel_offset.0;
+        point.y += self.subpixel_offset.1;
+        point.x *= self.output_scale;
+        point.y *= self.output_scale;
         point += EguiBackendCoord::from(bounds.min);
 
         point
@@ -222,10 +480,84 @@ impl<'a> EguiBackend<'a> {
         self
     }
 
+    #[inline]
+    /// Set an offset of less than a pixel added on top of the offset, such as to line gridlines up
+    /// with whole pixels.
+    pub fn set_subpixel_offset(&mut self, subpixel_offset: (f32, f32)) {
+        self.subpixel_offset = subpixel_offset
+    }
+
+    #[inline]
+    /// Set an offset of less than a pixel added on top of the offset. Consumes self.
+    pub fn subpixel_offset(mut self, subpixel_offset: (f32, f32)) -> Self {
+        self.set_subpixel_offset(subpixel_offset);
+
+        self
+    }
+
+    #[inline]
+    /// Set the recorder that reads series labels drawn with `LegendRecorder::record`, instead of
+    /// them being drawn. See [`LegendRecorder`].
+    pub fn set_legend_recorder(&mut self, legend: Option<LegendRecorder>) {
+        self.legend = legend
+    }
+
+    #[inline]
+    /// Set the recorder that reads series labels instead of them being drawn. Consumes self.
+    pub fn legend_recorder(mut self, legend: Option<LegendRecorder>) -> Self {
+        self.set_legend_recorder(legend);
+
+        self
+    }
+
+    #[inline]
+    /// Set the registry of the series being drawn. Shapes are then held back until the drawing
+    /// area is presented, and painted in the z-order of the series they were drawn for. See
+    /// [`SeriesRegistry`].
+    pub fn set_series_registry(&mut self, series: Option<SeriesRegistry>) {
+        self.series = series
+    }
+
+    #[inline]
+    /// Set the registry of the series being drawn. Consumes self.
+    pub fn series_registry(mut self, series: Option<SeriesRegistry>) -> Self {
+        self.set_series_registry(series);
+
+        self
+    }
+
+    /// Return the layer shapes drawn now are painted in: below every series until the first one
+    /// is drawn, at the z-order of the series being drawn, then above every series.
+    fn layer(&self) -> Layer {
+        match self.series.as_ref().and_then(|series| series.current_z()) {
+            Some(z) => {
+                self.series_drawn.set(true);
+
+                Layer::Series(z)
+            }
+            None if self.series_drawn.get() => Layer::Front,
+            None => Layer::Back,
+        }
+    }
+
+    /// Return the recorder if it's recording a legend, in which case shapes and text are given
+    /// to it rather than drawn.
+    fn recording_legend(&self) -> Option<&LegendRecorder> {
+        self.legend.as_ref().filter(|legend| legend.is_recording())
+    }
+
+    /// Give a shape drawn around the given height to the legend being recorded, if any. Returns
+    /// true if the shape was recorded, so it shouldn't be drawn.
+    fn record_legend(&self, y: i32, color: BackendColor) -> bool {
+        self.recording_legend()
+            .map(|legend| legend.shape(y, backend_color(color)))
+            .is_some()
+    }
+
     #[inline]
     /// Set the scale of the backend.
     pub fn set_scale(&mut self, scale: f32) {
-        self.scale = scale
+        self.scale = (scale, scale)
     }
 
     #[inline]
@@ -235,190 +567,1832 @@ impl<'a> EguiBackend<'a> {
 
         self
     }
-}
 
-impl<'a> DrawingBackend for EguiBackend<'a> {
-    type ErrorType = std::io::Error;
+    #[inline]
+    /// Set the scale of the backend separately along X and Y, stretching the drawing.
+    pub fn set_axis_scale(&mut self, x: f32, y: f32) {
+        self.scale = (x, y)
+    }
 
-    fn get_size(&self) -> (u32, u32) {
-        let bounds = self.ui.max_rect();
-        (bounds.width() as u32, bounds.height() as u32)
+    #[inline]
+    /// Set the scale of the backend separately along X and Y. Consume self.
+    pub fn axis_scale(mut self, x: f32, y: f32) -> Self {
+        self.set_axis_scale(x, y);
+
+        self
     }
 
-    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        Ok(())
+    #[inline]
+    /// Set the rect drawing is clipped to, in screen coordinates. Drawing is clipped to the
+    /// drawing rect if None, which is the default. Always clipped to the ui's clip rect too.
+    pub fn set_clip(&mut self, clip: Option<Rect>) {
+        self.clip = clip
     }
 
-    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        Ok(())
+    #[inline]
+    /// Set the rect drawing is clipped to, in screen coordinates. Consumes self.
+    pub fn clip(mut self, clip: Rect) -> Self {
+        self.set_clip(Some(clip));
+
+        self
     }
 
-    fn draw_pixel(
-        &mut self,
-        point: (i32, i32),
-        color: BackendColor,
-    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+    #[inline]
+    /// Set a fixed size for the drawing area, starting at the top left of the ui, instead of
+    /// filling all the space the ui has available. Uses the ui's size if None, which is the
+    /// default.
+    pub fn set_size(&mut self, size: Option<Vec2>) {
+        self.size = size
+    }
 
-        let p0 = self.point_transform(EguiBackendCoord::from(point), bounds);
+    #[inline]
+    /// Set a fixed size for the drawing area. Consumes self.
+    pub fn size(mut self, size: Vec2) -> Self {
+        self.set_size(Some(size));
 
-        let p1 = p0 + 1.0;
+        self
+    }
 
-        let color: Color32 = EguiBackendColor::from(color).into();
+    #[inline]
+    /// Set how text that would overflow the drawing area is handled.
+    pub fn set_label_overflow(&mut self, label_overflow: OverflowMode) {
+        self.label_overflow = label_overflow
+    }
 
-        let stroke = Stroke::new(1.0, color);
+    #[inline]
+    /// Set how text that would overflow the drawing area is handled. Consumes self.
+    pub fn label_overflow(mut self, label_overflow: OverflowMode) -> Self {
+        self.set_label_overflow(label_overflow);
 
-        painter.line_segment([p0.into(), p1.into()], stroke);
+        self
+    }
 
-        Ok(())
+    #[inline]
+    /// Enable/disable right-to-left text. When enabled, text is reordered so right-to-left
+    /// scripts such as Hebrew and Arabic read correctly while numbers and left-to-right words
+    /// embedded in them keep their order, and the horizontal anchor of all text is flipped so it
+    /// starts from the right. Note that egui does not shape text, so Arabic letters are shown in
+    /// their isolated forms.
+    pub fn set_rtl(&mut self, rtl: bool) {
+        self.rtl = rtl
     }
 
-    fn draw_line<S: BackendStyle>(
-        &mut self,
-        from: (i32, i32),
-        to: (i32, i32),
-        style: &S,
-    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+    #[inline]
+    /// Enable/disable right-to-left text. Consumes self.
+    pub fn rtl(mut self, rtl: bool) -> Self {
+        self.set_rtl(rtl);
 
-        let p0 = self.point_transform(EguiBackendCoord::from(from), bounds);
-        let p1 = self.point_transform(EguiBackendCoord::from(to), bounds);
+        self
+    }
 
-        let color: Color32 = EguiBackendColor::from(style.color()).into();
+    #[inline]
+    /// Enable/disable feathering for a class of shapes, whatever egui's tessellation options say.
+    /// Shapes whose feathering differs from those options are tessellated by the backend, so
+    /// gridlines can be kept crisp while line series stay smooth, or adjacent fills such as bars
+    /// kept from showing seams. Pass None to follow egui's options again, which is the default.
+    pub fn set_feathering_for(&mut self, class: ShapeClass, feathering: Option<bool>) {
+        match class {
+            ShapeClass::Lines => self.feather_lines = feathering,
+            ShapeClass::Grid => self.feather_grid = feathering,
+            ShapeClass::Fills => self.feather_fills = feathering,
+        }
+    }
 
-        let stroke = Stroke::new(style.stroke_width() as f32, color);
+    #[inline]
+    /// Enable/disable feathering for a class of shapes. Consumes self.
+    pub fn feathering_for(mut self, class: ShapeClass, feathering: bool) -> Self {
+        self.set_feathering_for(class, Some(feathering));
 
-        painter.line_segment([p0.into(), p1.into()], stroke);
+        self
+    }
 
-        Ok(())
+    #[inline]
+    /// Enable/disable snapping thin horizontal and vertical lines, such as gridlines, onto the
+    /// pixel grid. Without feathering, a 1 pixel line falling between two pixels can vanish
+    /// entirely, while a snapped line always covers whole pixels. Only single segments up to 2
+    /// points wide are snapped, so the lines of plotted data are left as they are. Enabled by
+    /// default.
+    pub fn set_pixel_snap_lines(&mut self, pixel_snap_lines: bool) {
+        self.pixel_snap_lines = pixel_snap_lines
     }
 
-    fn draw_text<TStyle: BackendTextStyle>(
-        &mut self,
-        text: &str,
-        style: &TStyle,
-        pos: (i32, i32),
-    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+    #[inline]
+    /// Enable/disable snapping thin horizontal and vertical lines onto the pixel grid. Consumes
+    /// self.
+    pub fn pixel_snap_lines(mut self, pixel_snap_lines: bool) -> Self {
+        self.set_pixel_snap_lines(pixel_snap_lines);
 
-        let pos = self.point_transform(EguiBackendCoord::from(pos), bounds);
+        self
+    }
 
-        let font_size = style.size() as f32;
-        let font_family = match style.family() {
-            PlottersFontFamily::Serif | PlottersFontFamily::SansSerif => {
-                EguiFontFamily::Proportional
-            }
-            PlottersFontFamily::Monospace => EguiFontFamily::Monospace,
-            PlottersFontFamily::Name(string) => EguiFontFamily::Name(string.into()),
-        };
+    /// Move a thin horizontal or vertical segment onto the pixel grid, centering strokes an odd
+    /// number of pixels wide on pixel centers and the rest on pixel edges.
+    fn snap_to_pixels(&self, segment: &mut [Pos2; 2], stroke_width: f32) {
+        if !self.pixel_snap_lines || stroke_width > PIXEL_SNAP_MAX_WIDTH {
+            return;
+        }
 
-        let font = FontId {
-            size: font_size,
-            family: font_family,
+        let pixels_per_point = self.ui.ctx().pixels_per_point();
+        let offset = match (stroke_width * pixels_per_point).round() as i32 % 2 {
+            1 => 0.5,
+            _ => 0.0,
         };
+        let snap =
+            |value: f32| ((value * pixels_per_point - offset).round() + offset) / pixels_per_point;
 
-        let color: Color32 = EguiBackendColor::from(style.color()).into();
+        let [p0, p1] = *segment;
+        let (dx, dy) = ((p1.x - p0.x).abs(), (p1.y - p0.y).abs());
 
-        let rotations = style.transform() as usize;
-        let angle = rotations as f32 * FRAC_PI_2;
+        if dx <= dy * PIXEL_SNAP_SLOPE {
+            let x = snap(p0.x);
 
-        let Pos { h_pos, v_pos } = style.anchor();
+            (segment[0].x, segment[1].x) = (x, x);
+        } else if dy <= dx * PIXEL_SNAP_SLOPE {
+            let y = snap(p0.y);
 
-        // !TODO! Find a slightly more eligant rotation function.
-        let mut anchor = Align2([
-            match h_pos {
-                HPos::Left => Align::LEFT,
-                HPos::Right => Align::RIGHT,
-                HPos::Center => Align::Center,
-            },
-            match v_pos {
-                VPos::Top => Align::TOP,
-                VPos::Center => Align::Center,
-                VPos::Bottom => Align::BOTTOM,
-            },
-        ]);
-        fn rotate(anchor: &mut Align2) {
-            *anchor = match anchor {
-                &mut Align2::LEFT_TOP => Align2::RIGHT_TOP,
-                &mut Align2::RIGHT_TOP => Align2::RIGHT_BOTTOM,
-                &mut Align2::RIGHT_BOTTOM => Align2::LEFT_BOTTOM,
-                &mut Align2::LEFT_BOTTOM => Align2::LEFT_TOP,
-                &mut Align2::LEFT_CENTER => Align2::CENTER_TOP,
-                &mut Align2::CENTER_TOP => Align2::RIGHT_CENTER,
-                &mut Align2::RIGHT_CENTER => Align2::CENTER_BOTTOM,
-                &mut Align2::CENTER_BOTTOM => Align2::LEFT_CENTER,
-                &mut Align2::CENTER_CENTER => Align2::CENTER_CENTER,
-            }
-        }
-        for _ in 0..rotations {
-            rotate(&mut anchor)
-        }
-        let galley = painter.layout_no_wrap(text.to_string(), font, color);
-        let rect = anchor.anchor_rect(Rect::from_min_size(pos.into(), galley.size()));
-        if !galley.is_empty() {
-            painter.add(TextShape {
-                angle,
-                ..TextShape::new(rect.min, galley, Color32::PLACEHOLDER)
-            });
+            (segment[0].y, segment[1].y) = (y, y);
         }
+    }
 
-        Ok(())
+    #[inline]
+    /// Simplify paths before drawing them, dropping points that move the line by less than the
+    /// given tolerance in pixels. End points and any feature larger than the tolerance are always
+    /// kept. Greatly reduces the cost of drawing long, dense lines. Disabled by default.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Context};
+    /// # use egui_plotter::EguiBackend;
+    /// # use plotters::prelude::*;
+    /// let ctx = Context::default();
+    ///
+    /// let output = ctx.run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| {
+    ///         let root = EguiBackend::new(ui)
+    ///             .path_simplify(Some(1.5))
+    ///             .into_drawing_area();
+    ///
+    ///         // 100k points along a line with a pixel of noise
+    ///         let noisy = (0..100_000).map(|i| (i / 100, 100 + (i * 7919 % 3) / 2));
+    ///
+    ///         root.draw(&PathElement::new(noisy.collect::<Vec<_>>(), BLACK))
+    ///             .unwrap();
+    ///     });
+    /// });
+    ///
+    /// let points: usize = output
+    ///     .shapes
+    ///     .iter()
+    ///     .filter_map(|clipped| match &clipped.shape {
+    ///         Shape::Path(path) => Some(path.points.len()),
+    ///         _ => None,
+    ///     })
+    ///     .sum();
+    ///
+    /// assert!(points < 1_000);
+    /// ```
+    pub fn set_path_simplify(&mut self, tolerance: Option<f32>) {
+        self.path_simplify = tolerance
     }
 
-    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
-        &mut self,
-        path: I,
-        style: &S,
-    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+    #[inline]
+    /// Simplify paths before drawing them. Consumes self.
+    pub fn path_simplify(mut self, tolerance: Option<f32>) -> Self {
+        self.set_path_simplify(tolerance);
 
-        let points: Vec<Pos2> = path
-            .into_iter()
-            .map(|point| {
-                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
+        self
+    }
 
-                point.into()
-            })
-            .collect();
+    #[inline]
+    /// Enable/disable batching of lines. When enabled, consecutive lines of the same style where
+    /// each starts at the end of the last, such as the segments of a polyline, are painted as a
+    /// single path rather than one shape per segment, which is much cheaper to lay out and
+    /// tessellate. Batched lines are painted when something else is drawn, when the drawing area
+    /// is presented, or when the backend is dropped. Enabled by default.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Context};
+    /// # use egui_plotter::EguiBackend;
+    /// # use plotters::prelude::*;
+    /// let ctx = Context::default();
+    ///
+    /// let output = ctx.run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| {
+    ///         let mut backend = EguiBackend::new(ui);
+    ///
+    ///         for i in 0..1_000 {
+    ///             backend.draw_line((i, i % 7), (i + 1, (i + 1) % 7), &BLACK).unwrap();
+    ///         }
+    ///     });
+    /// });
+    ///
+    /// let paths = output
+    ///     .shapes
+    ///     .iter()
+    ///     .filter(|clipped| matches!(clipped.shape, Shape::Path(_)))
+    ///     .count();
+    ///
+    /// assert_eq!(paths, 1);
+    /// ```
+    pub fn set_batch_lines(&mut self, batch_lines: bool) {
+        self.batch_lines = batch_lines
+    }
 
-        let color: Color32 = EguiBackendColor::from(style.color()).into();
+    #[inline]
+    /// Enable/disable batching of lines. Consumes self.
+    pub fn batch_lines(mut self, batch_lines: bool) -> Self {
+        self.set_batch_lines(batch_lines);
 
-        let stroke = Stroke::new(style.stroke_width() as f32, color);
+        self
+    }
 
-        let shape = PathShape::line(points, stroke);
+    #[inline]
+    /// Set the opacity of everything drawn, from 0.0 to 1.0, which multiplies the alpha of every
+    /// color. Nothing is drawn at 0.0. Defaults to 1.0.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Context};
+    /// # use egui_plotter::EguiBackend;
+    /// # use plotters::prelude::*;
+    /// let ctx = Context::default();
+    ///
+    /// let output = ctx.run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| {
+    ///         let root = EguiBackend::new(ui).opacity(0.5).into_drawing_area();
+    ///
+    ///         root.draw(&Rectangle::new([(0, 0), (20, 20)], RED.filled()))
+    ///             .unwrap();
+    ///     });
+    /// });
+    ///
+    /// let fill = output.shapes.iter().find_map(|clipped| match &clipped.shape {
+    ///     Shape::Rect(rect) if rect.fill.g() == 0 && rect.fill.r() > 0 => Some(rect.fill),
+    ///     _ => None,
+    /// });
+    ///
+    /// assert_eq!(fill.map(|fill| fill.a()), Some(128));
+    /// ```
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0)
+    }
 
-        painter.add(shape);
-        Ok(())
+    #[inline]
+    /// Set the opacity of everything drawn. Consumes self.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.set_opacity(opacity);
+
+        self
     }
 
-    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
-        &mut self,
-        vert: I,
-        style: &S,
-    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let bounds = self.ui.max_rect();
-        let painter = self.ui.painter().with_clip_rect(bounds);
+    #[inline]
+    /// Enable/disable a transparent background. When enabled, filled rects covering the whole
+    /// drawing area, such as those drawn by `DrawingArea::fill`, are skipped so only the series,
+    /// grid and text are drawn over whatever is behind the chart. Colors are premultiplied, so
+    /// the edges of shapes blend cleanly without an opaque background. Disabled by default.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Context};
+    /// # use egui_plotter::EguiBackend;
+    /// # use plotters::prelude::*;
+    /// let ctx = Context::default();
+    ///
+    /// let output = ctx.run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| {
+    ///         let root = EguiBackend::new(ui).transparent(true).into_drawing_area();
+    ///
+    ///         root.fill(&WHITE).unwrap();
+    ///         root.draw(&Rectangle::new([(10, 10), (30, 30)], RED.filled()))
+    ///             .unwrap();
+    ///     });
+    /// });
+    ///
+    /// let fills: Vec<_> = output
+    ///     .shapes
+    ///     .iter()
+    ///     .filter_map(|clipped| match &clipped.shape {
+    ///         Shape::Rect(rect) if rect.fill.g() == 0 => Some(rect.fill),
+    ///         Shape::Rect(rect) if rect.fill == egui::Color32::WHITE => Some(rect.fill),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    ///
+    /// // Only the red rectangle is drawn
+    /// assert_eq!(fills, [egui::Color32::RED]);
+    /// ```
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent
+    }
 
-        let points: Vec<Pos2> = vert
-            .into_iter()
-            .map(|point| {
-                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
+    #[inline]
+    /// Enable/disable a transparent background. Consumes self.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.set_transparent(transparent);
 
-                point.into()
-            })
-            .collect();
+        self
+    }
+
+    #[inline]
+    /// Set a color to fill a rounded rect with behind all text, keeping labels and annotations
+    /// readable over busy data. The rect fits the text with a little padding and follows its
+    /// anchor and rotation. Pass None to draw text without a background, which is the default.
+    pub fn set_text_background(&mut self, text_background: Option<Color32>) {
+        self.text_background = text_background
+    }
+
+    #[inline]
+    /// Set a color to fill a rounded rect with behind all text. Consumes self.
+    pub fn text_background(mut self, text_background: Color32) -> Self {
+        self.set_text_background(Some(text_background));
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable math text, where `^{...}` raises text into a superscript and `_{...}`
+    /// lowers it into a subscript, as in `"m/s^{2}"` or `"x_{1}"`. The braces can be left out
+    /// for a single character, as in `"x^2"`. Ignored for right-to-left text. Disabled by
+    /// default.
+    pub fn set_math_text(&mut self, math_text: bool) {
+        self.math_text = math_text
+    }
 
-        let color: Color32 = EguiBackendColor::from(style.color()).into();
+    #[inline]
+    /// Enable/disable math text. Consumes self.
+    pub fn math_text(mut self, math_text: bool) -> Self {
+        self.set_math_text(math_text);
+
+        self
+    }
+
+    #[inline]
+    /// Set the extra space added between the letters of text, in points. Negative values tighten
+    /// text instead. Defaults to 0.
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32) {
+        self.letter_spacing = letter_spacing
+    }
+
+    #[inline]
+    /// Set the extra space added between the letters of text, in points. Consumes self.
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.set_letter_spacing(letter_spacing);
+
+        self
+    }
+
+    #[inline]
+    /// Set the units stroke widths are given in. Defaults to `StrokeUnits::Pixels`.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Context};
+    /// # use egui_plotter::{EguiBackend, StrokeUnits};
+    /// # use plotters::prelude::*;
+    /// let ctx = Context::default();
+    ///
+    /// let output = ctx.run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| {
+    ///         let root = EguiBackend::new(ui)
+    ///             .scale(3.0)
+    ///             .stroke_units(StrokeUnits::Data)
+    ///             .into_drawing_area();
+    ///
+    ///         root.draw(&PathElement::new([(0, 0), (10, 10)], RED.stroke_width(2)))
+    ///             .unwrap();
+    ///     });
+    /// });
+    ///
+    /// let width = output
+    ///     .shapes
+    ///     .iter()
+    ///     .find_map(|clipped| match &clipped.shape {
+    ///         Shape::Path(path) => Some(path.stroke.width),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(width, 6.0);
+    /// ```
+    pub fn set_stroke_units(&mut self, stroke_units: StrokeUnits) {
+        self.stroke_units = stroke_units
+    }
+
+    #[inline]
+    /// Set the units stroke widths are given in. Consumes self.
+    pub fn stroke_units(mut self, stroke_units: StrokeUnits) -> Self {
+        self.set_stroke_units(stroke_units);
+
+        self
+    }
+
+    #[inline]
+    /// Set the factor everything drawn is enlarged by, including text and stroke widths, while
+    /// plotters still lays the chart out at the size of the drawing rect. Used to render exports
+    /// at a higher resolution than the screen, with text laid out at the larger size rather than
+    /// upscaled. The output then covers the drawing rect enlarged from its top left corner.
+    /// Defaults to 1.
+    pub fn set_output_scale(&mut self, output_scale: f32) {
+        self.output_scale = output_scale
+    }
+
+    #[inline]
+    /// Set the factor everything drawn is enlarged by. Consumes self.
+    pub fn output_scale(mut self, output_scale: f32) -> Self {
+        self.set_output_scale(output_scale);
+
+        self
+    }
+
+    #[inline]
+    /// Convert a plotters stroke width into the width drawn on screen, in points.
+    fn stroke_width(&self, stroke_width: u32) -> f32 {
+        let stroke_width = stroke_width as f32 * self.output_scale;
+
+        match self.stroke_units {
+            StrokeUnits::Pixels => stroke_width,
+            StrokeUnits::Data => stroke_width * (self.scale.0 * self.scale.1).abs().sqrt(),
+        }
+    }
+
+    #[inline]
+    /// Convert a plotters color into an egui color, applying the opacity of the backend.
+    fn color(&self, color: BackendColor) -> Color32 {
+        EguiBackendColor::from(BackendColor {
+            alpha: color.alpha * self.opacity as f64,
+            rgb: color.rgb,
+        })
+        .into()
+    }
+
+    /// Draw a rect for highlighting a selection, such as while dragging out a region to zoom to,
+    /// between two corners in backend coordinates. It's drawn in the ui's selection colors
+    /// rather than a plotters style, as a translucent fill outlined with dashes that stay the same
+    /// length however far the backend is scaled.
+    pub fn draw_selection_rect(&mut self, from: BackendCoord, to: BackendCoord) {
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let p0 = self.point_transform(EguiBackendCoord::from(from), bounds);
+        let p1 = self.point_transform(EguiBackendCoord::from(to), bounds);
+
+        let mut shape = Shape::Vec(selection_shapes(
+            self.ui,
+            Rect::from_two_pos(p0.into(), p1.into()),
+        ));
+
+        adjust_colors(&mut shape, &|color| {
+            *color = color.gamma_multiply(self.opacity)
+        });
+
+        self.add(&painter, shape);
+    }
+
+    #[inline]
+    /// Collect drawn shapes into the given list instead of painting them, for rendering offscreen.
+    pub(crate) fn set_capture(&mut self, capture: Option<&'a RefCell<Vec<ClippedShape>>>) {
+        self.capture = capture
+    }
+
+    /// Add a shape to the painter, in the layer of the series being drawn.
+    fn add(&self, painter: &Painter, shape: impl Into<Shape>) {
+        self.add_at(painter, shape, self.layer())
+    }
+
+    /// Add a shape to the painter in the given layer. With a series registry the shape is held
+    /// back to be painted in order once the drawing area is presented.
+    fn add_at(&self, painter: &Painter, shape: impl Into<Shape>, layer: Layer) {
+        if self.opacity <= 0.0 {
+            return;
+        }
+
+        let shape = ClippedShape {
+            clip_rect: painter.clip_rect(),
+            shape: shape.into(),
+        };
+
+        match self.series {
+            Some(_) => self.layers.borrow_mut().push((layer, shape)),
+            None => self.emit(shape),
+        }
+    }
+
+    /// Paint a shape, or add it to the capture list if capturing.
+    fn emit(&self, shape: ClippedShape) {
+        match self.capture {
+            Some(capture) => capture.borrow_mut().push(shape),
+            None => {
+                self.ui
+                    .painter()
+                    .with_clip_rect(shape.clip_rect)
+                    .add(shape.shape);
+            }
+        }
+    }
+
+    /// Paint the shapes held back by the series registry, from the back layer to the front.
+    /// Shapes in the same layer keep the order they were drawn in.
+    fn flush_layers(&mut self) {
+        let mut layers = self.layers.take();
+
+        layers.sort_by_key(|(layer, _)| *layer);
+
+        for (_, shape) in layers {
+            self.emit(shape);
+        }
+    }
+
+    /// Paint any lines waiting to be batched.
+    fn flush_lines(&mut self) {
+        let Some(line) = self.pending_line.take() else {
+            return;
+        };
+
+        let painter = self.ui.painter().with_clip_rect(line.clip_rect);
+
+        // Only lone segments are snapped, as snapping the segments of a longer line would pull
+        // their shared points apart
+        let (shape, class) = match line.points[..] {
+            [p0, p1] => {
+                let mut segment = [p0, p1];
+                self.snap_to_pixels(&mut segment, line.stroke.width);
+
+                (
+                    Shape::line_segment(segment, line.stroke),
+                    line_class(&segment),
+                )
+            }
+            _ => (
+                PathShape::line(line.points, line.stroke).into(),
+                ShapeClass::Lines,
+            ),
+        };
+
+        self.paint_at(&painter, shape, class, line.layer);
+    }
+
+    /// Return true if the edges of shapes of the given class are feathered.
+    fn feathers(&self, class: ShapeClass) -> bool {
+        let feathering = match class {
+            ShapeClass::Lines => self.feather_lines,
+            ShapeClass::Grid => self.feather_grid,
+            ShapeClass::Fills => self.feather_fills,
+        };
+
+        feathering.unwrap_or_else(|| self.ui.ctx().tessellation_options(|o| o.feathering))
+    }
+
+    /// Add a shape to the painter, tessellating it here if its class is feathered differently
+    /// from egui's tessellation options.
+    fn paint(&self, painter: &Painter, shape: impl Into<Shape>, class: ShapeClass) {
+        self.paint_at(painter, shape, class, self.layer())
+    }
+
+    /// Add a shape to the painter in the given layer, tessellating it here if needed.
+    fn paint_at(
+        &self,
+        painter: &Painter,
+        shape: impl Into<Shape>,
+        class: ShapeClass,
+        layer: Layer,
+    ) {
+        let ctx = self.ui.ctx();
+        let options = ctx.tessellation_options(|options| *options);
+        let feathering = self.feathers(class);
+
+        if feathering == options.feathering {
+            self.add_at(painter, shape, layer);
+
+            return;
+        }
+
+        let mut mesh = Mesh::default();
+
+        // A tessellator is kept for each setting, as building one takes the fonts lock
+        let mut tessellators = self.tessellators.borrow_mut();
+        let tessellator = tessellators[feathering as usize].get_or_insert_with(|| {
+            Tessellator::new(
+                ctx.pixels_per_point(),
+                TessellationOptions {
+                    feathering,
+                    ..options
+                },
+                ctx.fonts(|fonts| fonts.font_image_size()),
+                Vec::new(),
+            )
+        });
+
+        tessellator.tessellate_shape(shape.into(), &mut mesh);
+
+        self.add_at(painter, mesh, layer);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Direction of a character, used to reorder right-to-left text.
+enum TextDirection {
+    Ltr,
+    Rtl,
+    Neutral,
+}
+
+impl From<char> for TextDirection {
+    fn from(c: char) -> Self {
+        match c as u32 {
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and their extensions
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => Self::Rtl,
+            _ if c.is_alphanumeric() => Self::Ltr,
+            _ => Self::Neutral,
+        }
+    }
+}
+
+/// Cross product of the edges from a to b and from a to c, positive if they turn clockwise on
+/// screen.
+fn cross(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+    (b - a).x * (c - a).y - (b - a).y * (c - a).x
+}
+
+/// Return true if the polygon only ever turns one way, so it can be filled as a fan.
+fn is_convex(points: &[Pos2]) -> bool {
+    let len = points.len();
+    let mut sign = 0.0;
+
+    for i in 0..len {
+        let turn = cross(points[i], points[(i + 1) % len], points[(i + 2) % len]);
+
+        if turn != 0.0 {
+            if sign * turn < 0.0 {
+                return false;
+            }
+
+            sign = turn.signum();
+        }
+    }
+
+    true
+}
+
+/// Split a polygon into its two chains from the leftmost to the rightmost point, returning None
+/// unless both only ever move right, as the outline of the area under a line does.
+fn monotone_chains(points: &[Pos2]) -> Option<[Vec<Pos2>; 2]> {
+    let len = points.len();
+
+    let by_x = |a: &&Pos2, b: &&Pos2| a.x.total_cmp(&b.x);
+    let left = points.iter().enumerate().min_by(|a, b| by_x(&a.1, &b.1))?.0;
+    let right = points.iter().enumerate().max_by(|a, b| by_x(&a.1, &b.1))?.0;
+
+    let chain = |step: usize| {
+        let mut chain = vec![points[left]];
+        let mut i = left;
+
+        while i != right {
+            i = (i + step) % len;
+
+            if points[i].x < chain.last()?.x {
+                return None;
+            }
+
+            chain.push(points[i]);
+        }
+
+        Some(chain)
+    };
+
+    Some([chain(1)?, chain(len - 1)?])
+}
+
+/// Y of a chain at x, approaching from the left if `from_left`, otherwise from the right, so
+/// vertical steps in the chain are kept.
+fn chain_y(chain: &[Pos2], x: f32, from_left: bool) -> f32 {
+    let end = match from_left {
+        true => chain.partition_point(|point| point.x < x),
+        false => chain.partition_point(|point| point.x <= x),
+    }
+    .clamp(1, chain.len() - 1);
+
+    let (a, b) = (chain[end - 1], chain[end]);
+
+    match b.x > a.x {
+        true => a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x),
+        false => match from_left {
+            true => a.y,
+            false => b.y,
+        },
+    }
+}
+
+/// Fill an x-monotone polygon by slicing it into vertical strips between each point, which takes
+/// linear time rather than the quadratic time of ear clipping. Returns false if the polygon isn't
+/// x-monotone.
+fn fill_monotone(mesh: &mut Mesh, points: &[Pos2], color: Color32) -> bool {
+    let Some([upper, lower]) = monotone_chains(points) else {
+        return false;
+    };
+
+    let mut xs: Vec<f32> = points.iter().map(|point| point.x).collect();
+
+    xs.sort_by(f32::total_cmp);
+    xs.dedup();
+
+    for strip in xs.windows(2) {
+        let (start, end) = (strip[0], strip[1]);
+        let index = mesh.vertices.len() as u32;
+
+        mesh.colored_vertex(Pos2::new(start, chain_y(&upper, start, false)), color);
+        mesh.colored_vertex(Pos2::new(end, chain_y(&upper, end, true)), color);
+        mesh.colored_vertex(Pos2::new(end, chain_y(&lower, end, true)), color);
+        mesh.colored_vertex(Pos2::new(start, chain_y(&lower, start, false)), color);
+
+        mesh.add_triangle(index, index + 1, index + 2);
+        mesh.add_triangle(index, index + 2, index + 3);
+    }
+
+    true
+}
+
+/// Return 1.0 or -1.0 depending on the direction the polygon winds in, or 0.0 if it has no area.
+fn winding(points: &[Pos2]) -> f32 {
+    let len = points.len();
+
+    // The sign of twice the signed area
+    (0..len)
+        .map(|i| cross(Pos2::ZERO, points[i], points[(i + 1) % len]))
+        .sum::<f32>()
+        .signum()
+}
+
+/// Triangulate a simple polygon, convex or not, by ear clipping. Returns the indices of the
+/// triangles' corners. Self-intersecting polygons are filled as best as possible.
+fn triangulate(points: &[Pos2]) -> Vec<u32> {
+    let len = points.len();
+    let winding = winding(points);
+
+    if len < 3 || winding == 0.0 || !winding.is_finite() {
+        return Vec::new();
+    }
+
+    let turn = |a: usize, b: usize, c: usize| cross(points[a], points[b], points[c]) * winding;
+
+    // The corners left form a ring, linked to the corners either side
+    let mut prev: Vec<usize> = (0..len).map(|i| (i + len - 1) % len).collect();
+    let mut next: Vec<usize> = (0..len).map(|i| (i + 1) % len).collect();
+
+    // Only corners turning the other way can be inside an ear, and clipping ears only ever
+    // straightens the corners either side, so this list only shrinks
+    let mut reflex: Vec<usize> = (0..len)
+        .filter(|&i| turn(prev[i], i, next[i]) <= 0.0)
+        .collect();
+
+    let mut indices = Vec::with_capacity((len - 2) * 3);
+
+    let mut remaining = len;
+    let mut current = 0;
+    let mut attempts = 0;
+
+    while remaining > 3 && attempts < remaining {
+        let (before, after) = (prev[current], next[current]);
+
+        let corner = turn(before, current, after);
+
+        // Points in a straight line or doubling back add no area, so they're dropped
+        let is_ear = corner == 0.0
+            || corner > 0.0
+                && !reflex.iter().any(|&other| {
+                    ![before, current, after].contains(&other)
+                        && turn(before, current, other) > 0.0
+                        && turn(current, after, other) > 0.0
+                        && turn(after, before, other) > 0.0
+                });
+
+        if !is_ear {
+            current = after;
+            attempts += 1;
+
+            continue;
+        }
+
+        if corner != 0.0 {
+            indices.extend([before, current, after].map(|j| j as u32));
+        }
+
+        next[before] = after;
+        prev[after] = before;
+        remaining -= 1;
+
+        reflex.retain(|&i| i != current && turn(prev[i], i, next[i]) <= 0.0);
+
+        // The corner before may have just become an ear
+        current = before;
+        attempts = 0;
+    }
+
+    // Fan out whatever is left, which is a single triangle unless the polygon intersects itself
+    let first = current;
+    let mut corner = next[first];
+
+    for _ in 0..remaining.saturating_sub(2) {
+        indices.extend([first, corner, next[corner]].map(|j| j as u32));
+        corner = next[corner];
+    }
+
+    indices
+}
+
+/// Return the class of a line through the given points, which is grid for a lone horizontal or
+/// vertical segment.
+fn line_class(points: &[Pos2]) -> ShapeClass {
+    match points {
+        [p0, p1] if p0.x == p1.x || p0.y == p1.y => ShapeClass::Grid,
+        _ => ShapeClass::Lines,
+    }
+}
+
+/// Fade the edges of a filled polygon out over the given width, the way egui feathers the
+/// polygons it fills itself, so fills triangulated here are as smooth as convex ones.
+fn feather_outline(mesh: &mut Mesh, points: &[Pos2], color: Color32, width: f32) {
+    // Repeated points have no edge between them to find the direction of
+    let mut outline: Vec<Pos2> = points.to_vec();
+
+    outline.dedup();
+
+    if outline.len() > 1 && outline.first() == outline.last() {
+        outline.pop();
+    }
+
+    let len = outline.len();
+    let winding = winding(&outline);
+
+    if len < 3 || winding == 0.0 || !winding.is_finite() {
+        return;
+    }
+
+    // Outward normal of the edge starting at each point
+    let normals: Vec<Vec2> = (0..len)
+        .map(|i| {
+            let direction = (outline[(i + 1) % len] - outline[i]).normalized();
+
+            Vec2::new(direction.y, -direction.x) * winding
+        })
+        .collect();
+
+    let index = mesh.vertices.len() as u32;
+
+    for i in 0..len {
+        // Miter the corners, limiting how far sharp ones reach
+        let normal = (normals[(i + len - 1) % len] + normals[i]) / 2.0;
+        let normal = normal / normal.length_sq().max(0.25);
+
+        mesh.colored_vertex(outline[i], color);
+        mesh.colored_vertex(outline[i] + normal * width, Color32::TRANSPARENT);
+    }
+
+    for i in 0..len as u32 {
+        let j = (i + 1) % len as u32;
+        let [inner, outer] = [index + i * 2, index + i * 2 + 1];
+        let [next_inner, next_outer] = [index + j * 2, index + j * 2 + 1];
+
+        mesh.add_triangle(inner, outer, next_outer);
+        mesh.add_triangle(inner, next_outer, next_inner);
+    }
+}
+
+/// Simplify a line using the Douglas-Peucker algorithm, keeping the end points and every point
+/// further than `tolerance` from the simplified line.
+fn simplify_path(original: &[Pos2], tolerance: f32) -> Vec<Pos2> {
+    // Drop points within the tolerance of the previous point first, which is cheap and removes
+    // most of the points of a dense line before the more expensive pass
+    let mut points: Vec<Pos2> = original.iter().fold(Vec::new(), |mut kept, point| {
+        match kept.last() {
+            Some(last) if last.distance(*point) <= tolerance => {}
+            _ => kept.push(*point),
+        }
+
+        kept
+    });
+
+    // Always keep the last point
+    if let (Some(kept), Some(last)) = (points.last_mut(), original.last()) {
+        *kept = *last;
+    }
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut keep = vec![false; points.len()];
+    let mut stack = vec![(0, points.len() - 1)];
+
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // Iterate rather than recurse so long lines can't overflow the stack
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (points[start], points[end]);
+        let ab = b - a;
+        let length = ab.length();
+
+        let distance = |p: Pos2| match length > f32::EPSILON {
+            true => (ab.x * (p.y - a.y) - ab.y * (p.x - a.x)).abs() / length,
+            false => p.distance(a),
+        };
+
+        // Break ties towards the middle, so noisy lines split evenly rather than one point at a
+        // time
+        let middle = (start + end) / 2;
+
+        let furthest = (start + 1..end).map(|i| (i, distance(points[i]))).max_by(
+            |(a_index, a), (b_index, b)| {
+                a.total_cmp(b)
+                    .then(b_index.abs_diff(middle).cmp(&a_index.abs_diff(middle)))
+            },
+        );
+
+        if let Some((i, distance)) = furthest {
+            if distance > tolerance {
+                keep[i] = true;
+
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+/// Mirror brackets, which flip in right-to-left text.
+fn mirror_char(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        _ => c,
+    }
+}
+
+/// Reorder right-to-left text into the left-to-right visual order egui lays text out in.
+///
+/// This is a small subset of the unicode bidirectional algorithm that covers labels: neutral
+/// characters take the direction of their surroundings if both sides agree, or right-to-left
+/// otherwise. Runs are then drawn in reverse order, with right-to-left runs reversed as well.
+fn visual_order(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut directions: Vec<TextDirection> = chars.iter().map(|c| (*c).into()).collect();
+
+    for i in 0..directions.len() {
+        if directions[i] != TextDirection::Neutral {
+            continue;
+        }
+
+        let strong = |direction: &&TextDirection| **direction != TextDirection::Neutral;
+
+        let before = directions[..i].iter().rev().find(strong);
+        let after = directions[i + 1..].iter().find(strong);
+
+        directions[i] = match (before, after) {
+            (Some(TextDirection::Ltr), Some(TextDirection::Ltr)) => TextDirection::Ltr,
+            _ => TextDirection::Rtl,
+        };
+    }
+
+    let mut runs: Vec<(TextDirection, Vec<char>)> = Vec::new();
+
+    for (c, direction) in chars.into_iter().zip(directions) {
+        match runs.last_mut() {
+            Some((run_direction, run)) if *run_direction == direction => run.push(c),
+            _ => runs.push((direction, vec![c])),
+        }
+    }
+
+    runs.into_iter()
+        .rev()
+        .flat_map(|(direction, run)| match direction {
+            TextDirection::Rtl => run.into_iter().rev().map(mirror_char).collect::<Vec<_>>(),
+            _ => run,
+        })
+        .collect()
+}
+
+/// Lay out math text, raising `^{...}` into superscripts and marking `_{...}` as subscripts.
+/// Without braces only the next character is raised or marked, and an unclosed brace runs to the
+/// end of the text. Returns the job along with whether each of its sections is a subscript, which
+/// are laid out on the baseline for `split_subscripts` to lower.
+fn math_layout(text: &str, font: FontId, color: Color32) -> (LayoutJob, Vec<bool>) {
+    let mut job = LayoutJob::default();
+    let mut subscripts = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut plain = String::new();
+
+    let script_format = |valign| TextFormat {
+        font_id: FontId {
+            size: font.size * MATH_SCRIPT_SCALE,
+            ..font.clone()
+        },
+        color,
+        valign,
+        ..Default::default()
+    };
+    let plain_format = TextFormat::simple(font.clone(), color);
+
+    while let Some(c) = chars.next() {
+        let valign = match c {
+            '^' => Align::TOP,
+            '_' => Align::BOTTOM,
+            c => {
+                plain.push(c);
+
+                continue;
+            }
+        };
+
+        let script: String = match chars.next_if_eq(&'{') {
+            Some(_) => chars.by_ref().take_while(|c| *c != '}').collect(),
+            None => chars.next().into_iter().collect(),
+        };
+
+        job.append(&std::mem::take(&mut plain), 0.0, plain_format.clone());
+        job.append(&script, 0.0, script_format(valign));
+        subscripts.extend([false, c == '_']);
+    }
+
+    job.append(&plain, 0.0, plain_format);
+    subscripts.push(false);
+
+    (job, subscripts)
+}
+
+/// Move the subscripts of math text into a job of their own, hiding them in the original, so
+/// they can be drawn lowered below the rest of the text as egui can only raise text. Both jobs
+/// lay out the same glyphs, so the subscripts keep their place in the line. Returns None if there
+/// are no subscripts.
+fn split_subscripts(job: &mut LayoutJob, subscripts: &[bool]) -> Option<LayoutJob> {
+    if !subscripts.contains(&true) {
+        return None;
+    }
+
+    let mut lowered = job.clone();
+
+    for ((section, lowered), subscript) in job
+        .sections
+        .iter_mut()
+        .zip(&mut lowered.sections)
+        .zip(subscripts)
+    {
+        match subscript {
+            true => section.format.color = Color32::TRANSPARENT,
+            false => lowered.format.color = Color32::TRANSPARENT,
+        }
+    }
+
+    Some(lowered)
+}
+
+/// Return the strokes of a character of fallback text, as segments within a cell from (0, 0) at
+/// the top left to (1, 1) at the bottom right. Digits and the symbols used in numbers are drawn
+/// like a seven segment display, and any other visible character as an outlined box.
+fn fallback_glyph(c: char) -> Vec<[(f32, f32); 2]> {
+    const A: [(f32, f32); 2] = [(0.0, 0.0), (1.0, 0.0)];
+    const B: [(f32, f32); 2] = [(1.0, 0.0), (1.0, 0.5)];
+    const C: [(f32, f32); 2] = [(1.0, 0.5), (1.0, 1.0)];
+    const D: [(f32, f32); 2] = [(0.0, 1.0), (1.0, 1.0)];
+    const E: [(f32, f32); 2] = [(0.0, 0.5), (0.0, 1.0)];
+    const F: [(f32, f32); 2] = [(0.0, 0.0), (0.0, 0.5)];
+    const G: [(f32, f32); 2] = [(0.0, 0.5), (1.0, 0.5)];
+
+    let dot = |y: f32| [(0.4, y), (0.6, y)];
+
+    match c {
+        '0' => vec![A, B, C, D, E, F],
+        '1' => vec![B, C],
+        '2' => vec![A, B, G, E, D],
+        '3' => vec![A, B, G, C, D],
+        '4' => vec![F, G, B, C],
+        '5' => vec![A, F, G, C, D],
+        '6' => vec![A, F, G, E, D, C],
+        '7' => vec![A, B, C],
+        '8' => vec![A, B, C, D, E, F, G],
+        '9' => vec![A, B, C, D, F, G],
+        'e' | 'E' => vec![A, F, G, E, D],
+        '-' | '−' => vec![G],
+        '+' => vec![G, [(0.5, 0.25), (0.5, 0.75)]],
+        '.' => vec![dot(1.0)],
+        ',' => vec![[(0.55, 0.9), (0.45, 1.1)]],
+        ':' => vec![dot(0.3), dot(1.0)],
+        '%' => vec![
+            [(1.0, 0.0), (0.0, 1.0)],
+            [(0.0, 0.1), (0.2, 0.1)],
+            [(0.8, 0.9), (1.0, 0.9)],
+        ],
+        c if c.is_whitespace() => Vec::new(),
+        _ => vec![A, B, C, D, E, F],
+    }
+}
+
+/// Return true if a galley laid out from visible text has nothing to draw, as happens when egui
+/// has no fonts loaded.
+fn is_missing_glyphs(galley: &Galley) -> bool {
+    galley.job.text.chars().any(|c| !c.is_whitespace())
+        && galley.rows.iter().all(|row| row.visuals.mesh.is_empty())
+}
+
+/// Log a warning that text is being drawn without fonts, only the first time for each context.
+fn warn_missing_fonts(ctx: &Context) {
+    let warned = ctx.data_mut(|data| {
+        std::mem::replace(
+            data.get_temp_mut_or_default::<bool>(Id::new(MISSING_FONTS_WARNED_KEY)),
+            true,
+        )
+    });
+
+    if !warned {
+        log::warn!(
+            "egui-plotter: egui has no fonts to draw text with, so chart text is drawn with \
+             simple fallback glyphs instead. Load fonts into the context with `Context::set_fonts`"
+        );
+    }
+}
+
+/// Return the bounding box of text laid out in `rect` and rotated around its top left corner.
+fn text_bounds(rect: Rect, angle: f32) -> Rect {
+    let rotation = Rot2::from_angle(angle);
+
+    let corners = [
+        rect.left_top(),
+        rect.right_top(),
+        rect.left_bottom(),
+        rect.right_bottom(),
+    ];
+
+    Rect::from_points(&corners.map(|corner| rect.min + rotation * (corner - rect.min)))
+}
+
+/// Return the factor text has to be scaled by, around its anchor, to fit inside the bounds.
+fn text_fit(anchor: Pos2, text: Rect, bounds: Rect) -> f32 {
+    let axis_fit = |anchor: f32, min: f32, max: f32, bound_min: f32, bound_max: f32| {
+        let mut fit = f32::INFINITY;
+
+        if max > bound_max {
+            fit = fit.min((bound_max - anchor) / (max - anchor));
+        }
+
+        if min < bound_min {
+            fit = fit.min((anchor - bound_min) / (anchor - min));
+        }
+
+        fit
+    };
+
+    let x_fit = axis_fit(anchor.x, text.min.x, text.max.x, bounds.min.x, bounds.max.x);
+    let y_fit = axis_fit(anchor.y, text.min.y, text.max.y, bounds.min.y, bounds.max.y);
+
+    x_fit.min(y_fit)
+}
+
+impl Drop for EguiBackend<'_> {
+    fn drop(&mut self) {
+        self.flush_lines();
+        self.flush_layers();
+    }
+}
+
+impl<'a> DrawingBackend for EguiBackend<'a> {
+    type ErrorType = std::io::Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        let bounds = self.drawing_rect();
+        (bounds.width() as u32, bounds.height() as u32)
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        self.flush_layers();
+
+        Ok(())
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: (i32, i32),
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.record_legend(point.1, color) {
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let p0 = self.point_transform(EguiBackendCoord::from(point), bounds);
+
+        let p1 = p0 + self.output_scale;
+
+        let color = self.color(color);
+
+        let stroke = Stroke::new(self.output_scale, color);
+
+        self.paint(
+            &painter,
+            Shape::line_segment([p0.into(), p1.into()], stroke),
+            ShapeClass::Lines,
+        );
+
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: (i32, i32),
+        to: (i32, i32),
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.record_legend((from.1 + to.1) / 2, style.color()) {
+            return Ok(());
+        }
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let p0 = self.point_transform(EguiBackendCoord::from(from), bounds);
+        let p1 = self.point_transform(EguiBackendCoord::from(to), bounds);
+
+        let color = self.color(style.color());
+
+        let stroke = Stroke::new(self.stroke_width(style.stroke_width()), color);
+
+        let (p0, p1): (Pos2, Pos2) = (p0.into(), p1.into());
+
+        if !self.batch_lines {
+            let mut segment = [p0, p1];
+            self.snap_to_pixels(&mut segment, stroke.width);
+            let [p0, p1] = segment;
+
+            self.paint(
+                &painter,
+                Shape::line_segment([p0, p1], stroke),
+                line_class(&[p0, p1]),
+            );
+
+            return Ok(());
+        }
+
+        let clip_rect = painter.clip_rect();
+        let layer = self.layer();
+
+        // Extend the pending path if this line carries on from it
+        if let Some(line) = &mut self.pending_line {
+            if line.stroke == stroke
+                && line.clip_rect == clip_rect
+                && line.layer == layer
+                && line.points.last() == Some(&p0)
+            {
+                line.points.push(p1);
+
+                return Ok(());
+            }
+        }
+
+        self.flush_lines();
+
+        self.pending_line = Some(PendingLine {
+            points: vec![p0, p1],
+            stroke,
+            clip_rect,
+            layer,
+        });
+
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.record_legend(center.1, style.color()) {
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let center = self.point_transform(EguiBackendCoord::from(center), bounds);
+        // Radii scale with the drawing like positions do, keeping markers where they belong
+        let radius = radius as f32 * (self.scale.0 * self.scale.1).abs().sqrt() * self.output_scale;
+
+        let color = self.color(style.color());
+
+        match fill {
+            true => self.paint(
+                &painter,
+                Shape::circle_filled(center.into(), radius, color),
+                ShapeClass::Fills,
+            ),
+            false => self.paint(
+                &painter,
+                Shape::circle_stroke(
+                    center.into(),
+                    radius,
+                    Stroke::new(self.stroke_width(style.stroke_width()), color),
+                ),
+                ShapeClass::Lines,
+            ),
+        };
+
+        Ok(())
+    }
+
+    fn blit_bitmap(
+        &mut self,
+        pos: BackendCoord,
+        (width, height): (u32, u32),
+        src: &[u8],
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let pixel = |x: u32, y: u32| {
+            let index = (y * width + x) as usize * 3;
+
+            src.get(index..index + 3).map(|rgb| {
+                self.color(BackendColor {
+                    alpha: 1.0,
+                    rgb: (rgb[0], rgb[1], rgb[2]),
+                })
+            })
+        };
+
+        // Draw the bitmap as one mesh of colored quads, one for each run of same colored pixels
+        // in a row, so it's a single shape that the software rasterizer can draw too
+        let mut mesh = Mesh::default();
+
+        for y in 0..height {
+            let mut x = 0;
+
+            while x < width {
+                let Some(color) = pixel(x, y) else {
+                    break;
+                };
+
+                let start = x;
+
+                while x < width && pixel(x, y) == Some(color) {
+                    x += 1;
+                }
+
+                let min = (pos.0 + start as i32, pos.1 + y as i32);
+                let max = (pos.0 + x as i32, pos.1 + y as i32 + 1);
+
+                let min = self.point_transform(EguiBackendCoord::from(min), bounds);
+                let max = self.point_transform(EguiBackendCoord::from(max), bounds);
+
+                mesh.add_colored_rect(Rect::from_two_pos(min.into(), max.into()), color);
+            }
+        }
+
+        if !mesh.is_empty() {
+            self.add(&painter, mesh);
+        }
+
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: (i32, i32),
+        bottom_right: (i32, i32),
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if self.record_legend((upper_left.1 + bottom_right.1) / 2, style.color()) {
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        // Skip background fills when the background is transparent
+        if self.transparent && fill {
+            let (width, height) = self.get_size();
+
+            if upper_left.0 <= 0
+                && upper_left.1 <= 0
+                && bottom_right.0 >= width as i32
+                && bottom_right.1 >= height as i32
+            {
+                return Ok(());
+            }
+        }
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let p0 = self.point_transform(EguiBackendCoord::from(upper_left), bounds);
+        let p1 = self.point_transform(EguiBackendCoord::from(bottom_right), bounds);
+
+        let rect = Rect::from_two_pos(p0.into(), p1.into());
+
+        let color = self.color(style.color());
+
+        // Draw the rect as one shape rather than letting plotters fill it line by line, as
+        // overlapping lines would blend translucent colors unevenly
+        match fill {
+            true => self.paint(
+                &painter,
+                Shape::rect_filled(rect, 0.0, color),
+                ShapeClass::Fills,
+            ),
+            false => self.paint(
+                &painter,
+                Shape::rect_stroke(
+                    rect,
+                    0.0,
+                    Stroke::new(self.stroke_width(style.stroke_width()), color),
+                ),
+                ShapeClass::Lines,
+            ),
+        };
+
+        Ok(())
+    }
+
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let font = self.font_id(style);
+        let size = font.size;
+        let chars = text.chars().count() as f32;
+
+        let galley = self
+            .ui
+            .ctx()
+            .fonts(|fonts| fonts.layout_no_wrap(text.to_string(), font, Color32::PLACEHOLDER));
+
+        let (width, height) = match is_missing_glyphs(&galley) {
+            true => (chars * size * FALLBACK_GLYPH_ADVANCE, size),
+            false => (galley.size().x, galley.size().y),
+        };
+        let width = width + chars * self.letter_spacing * self.output_scale;
+
+        // Text isn't zoomed with the chart, so it covers less of the chart's coordinates the
+        // further it's zoomed in
+        let to_backend = |length: f32, scale: f32| match scale.abs() > 0.0 {
+            true => (length / (self.output_scale * scale.abs())).ceil() as u32,
+            false => 0,
+        };
+
+        Ok((
+            to_backend(width, self.scale.0),
+            to_backend(height, self.scale.1),
+        ))
+    }
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: (i32, i32),
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if let Some(legend) = self.recording_legend() {
+            legend.text(text, pos.1);
+
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let pos = self.point_transform(EguiBackendCoord::from(pos), bounds);
+
+        let font = self.font_id(style);
+
+        let color = self.color(style.color());
+
+        let rotations = style.transform() as usize;
+        let angle = rotations as f32 * FRAC_PI_2;
+
+        let Pos { mut h_pos, v_pos } = style.anchor();
+
+        if self.rtl {
+            h_pos = match h_pos {
+                HPos::Left => HPos::Right,
+                HPos::Right => HPos::Left,
+                HPos::Center => HPos::Center,
+            };
+        }
+
+        // !TODO! Find a slightly more eligant rotation function.
+        let mut anchor = Align2([
+            match h_pos {
+                HPos::Left => Align::LEFT,
+                HPos::Right => Align::RIGHT,
+                HPos::Center => Align::Center,
+            },
+            match v_pos {
+                VPos::Top => Align::TOP,
+                VPos::Center => Align::Center,
+                VPos::Bottom => Align::BOTTOM,
+            },
+        ]);
+        fn rotate(anchor: &mut Align2) {
+            *anchor = match *anchor {
+                Align2::LEFT_TOP => Align2::RIGHT_TOP,
+                Align2::RIGHT_TOP => Align2::RIGHT_BOTTOM,
+                Align2::RIGHT_BOTTOM => Align2::LEFT_BOTTOM,
+                Align2::LEFT_BOTTOM => Align2::LEFT_TOP,
+                Align2::LEFT_CENTER => Align2::CENTER_TOP,
+                Align2::CENTER_TOP => Align2::RIGHT_CENTER,
+                Align2::RIGHT_CENTER => Align2::CENTER_BOTTOM,
+                Align2::CENTER_BOTTOM => Align2::LEFT_CENTER,
+                Align2::CENTER_CENTER => Align2::CENTER_CENTER,
+            }
+        }
+        for _ in 0..rotations {
+            rotate(&mut anchor)
+        }
+        let rtl = self.rtl;
+        let math_text = self.math_text && !rtl;
+        let letter_spacing = self.letter_spacing * self.output_scale;
+        // Text is reordered at layout so truncation below works on the logical order
+        let layout = |text: String, font: FontId| {
+            let drop = font.size * MATH_SUBSCRIPT_DROP;
+            let (mut job, subscripts) = match (math_text, rtl) {
+                (true, _) => math_layout(&text, font, color),
+                (false, true) => (
+                    LayoutJob::simple(visual_order(&text), font, color, f32::INFINITY),
+                    Vec::new(),
+                ),
+                (false, false) => (
+                    LayoutJob::simple(text, font, color, f32::INFINITY),
+                    Vec::new(),
+                ),
+            };
+
+            for section in &mut job.sections {
+                section.format.extra_letter_spacing = letter_spacing;
+            }
+
+            let lowered = split_subscripts(&mut job, &subscripts);
+
+            let (galley, lowered) = painter.ctx().fonts(|fonts| {
+                (
+                    fonts.layout_job(job),
+                    lowered.map(|job| (fonts.layout_job(job), drop)),
+                )
+            });
+
+            // Lowered subscripts hang below the rest of the text
+            let size =
+                galley.size() + Vec2::new(0.0, lowered.as_ref().map_or(0.0, |(_, drop)| *drop));
+            let rect = anchor.anchor_rect(Rect::from_min_size(pos.into(), size));
+
+            (galley, lowered, rect)
+        };
+
+        let (mut galley, mut lowered, mut rect) = layout(text.to_string(), font.clone());
+
+        // Only handle overflow if the anchor is inside the bounds, otherwise the text can never
+        // fit and is simply clipped
+        let fit = text_fit(pos.into(), text_bounds(rect, angle), self.output_rect());
+
+        if fit > 0.0 && fit < 1.0 {
+            match self.label_overflow {
+                OverflowMode::Clip => {}
+                OverflowMode::Shrink => {
+                    let font = FontId {
+                        size: font.size * fit.max(MIN_TEXT_SHRINK),
+                        ..font
+                    };
+
+                    (galley, lowered, rect) = layout(text.to_string(), font);
+                }
+                OverflowMode::Ellipsis => {
+                    let max_width = galley.size().x * fit;
+                    let mut chars: Vec<char> = text.chars().collect();
+
+                    while !chars.is_empty() && galley.size().x > max_width {
+                        chars.pop();
+
+                        let truncated: String = chars.iter().chain(['…'].iter()).collect();
+
+                        (galley, lowered, rect) = layout(truncated, font.clone());
+                    }
+                }
+            }
+        }
+
+        // Draw the text with stroked glyphs rather than dropping it if there are no fonts to lay it
+        // out with
+        if is_missing_glyphs(&galley) {
+            warn_missing_fonts(painter.ctx());
+
+            let chars: Vec<char> = galley.job.text.chars().collect();
+            let size = font.size;
+            let rect = anchor.anchor_rect(Rect::from_min_size(
+                pos.into(),
+                Vec2::new(
+                    chars.len() as f32 * size * FALLBACK_GLYPH_ADVANCE,
+                    size * FALLBACK_GLYPH_HEIGHT,
+                ),
+            ));
+            let rotation = Rot2::from_angle(angle);
+            let stroke = Stroke::new((size / 10.0).max(1.0), color);
+
+            for (index, c) in chars.into_iter().enumerate() {
+                let cell = Vec2::new(index as f32 * size * FALLBACK_GLYPH_ADVANCE, 0.0);
+
+                for [from, to] in fallback_glyph(c) {
+                    let [from, to] = [from, to].map(|(x, y)| {
+                        let offset = cell
+                            + Vec2::new(
+                                x * size * FALLBACK_GLYPH_WIDTH,
+                                y * size * FALLBACK_GLYPH_HEIGHT,
+                            );
+
+                        rect.min + rotation * offset
+                    });
+
+                    self.paint(
+                        &painter,
+                        Shape::line_segment([from, to], stroke),
+                        ShapeClass::Lines,
+                    );
+                }
+            }
+
+            return Ok(());
+        }
+
+        if !galley.is_empty() {
+            // Text is only ever rotated in quarter turns, so its bounds fit it exactly
+            if let Some(background) = self.text_background {
+                self.paint(
+                    &painter,
+                    Shape::rect_filled(
+                        text_bounds(rect, angle)
+                            .expand(TEXT_BACKGROUND_PADDING * self.output_scale),
+                        TEXT_BACKGROUND_ROUNDING * self.output_scale,
+                        background.gamma_multiply(self.opacity),
+                    ),
+                    ShapeClass::Fills,
+                );
+            }
+
+            self.add(
+                &painter,
+                TextShape {
+                    angle,
+                    // The galley is already colored, but any placeholder left in it has to
+                    // resolve to the same color rather than egui's debug color
+                    ..TextShape::new(rect.min, galley, color)
+                },
+            );
+
+            if let Some((lowered, drop)) = lowered {
+                let pos = rect.min + Rot2::from_angle(angle) * Vec2::new(0.0, drop);
+
+                self.add(
+                    &painter,
+                    TextShape {
+                        angle,
+                        ..TextShape::new(pos, lowered, color)
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let path: Vec<BackendCoord> = path.into_iter().collect();
+
+        if self.record_legend(middle_y(&path), style.color()) {
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let points: Vec<Pos2> = path
+            .into_iter()
+            .map(|point| {
+                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
+
+                point.into()
+            })
+            .collect();
+
+        let color = self.color(style.color());
+
+        let stroke = Stroke::new(self.stroke_width(style.stroke_width()), color);
+
+        let mut points = match self.path_simplify {
+            Some(tolerance) => simplify_path(&points, tolerance),
+            None => points,
+        };
+
+        if let [p0, p1] = points[..] {
+            let mut segment = [p0, p1];
+            self.snap_to_pixels(&mut segment, stroke.width);
+
+            points = segment.to_vec();
+        }
+
+        let class = line_class(&points);
+        let shape = PathShape::line(points, stroke);
+
+        self.paint(&painter, shape, class);
+
+        Ok(())
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let vert: Vec<BackendCoord> = vert.into_iter().collect();
+
+        if self.record_legend(middle_y(&vert), style.color()) {
+            return Ok(());
+        }
+
+        self.flush_lines();
+
+        let bounds = self.drawing_rect();
+        let painter = self.ui.painter().with_clip_rect(self.clip_rect());
+
+        let points: Vec<Pos2> = vert
+            .into_iter()
+            .map(|point| {
+                let point = self.point_transform(EguiBackendCoord::from(point), bounds);
+
+                point.into()
+            })
+            .collect();
+
+        let color = self.color(style.color());
+
+        // Egui only fills convex polygons, so triangulate any others, such as areas under lines
+        if !is_convex(&points) {
+            let mut mesh = Mesh::default();
+
+            if !fill_monotone(&mut mesh, &points, color) {
+                for point in &points {
+                    mesh.colored_vertex(*point, color);
+                }
+
+                mesh.indices = triangulate(&points);
+            }
+
+            // Egui only feathers the shapes it tessellates itself, so the mesh is feathered here
+            if self.feathers(ShapeClass::Fills) {
+                let ctx = self.ui.ctx();
+                let size = ctx.tessellation_options(|options| options.feathering_size_in_pixels);
+                let width = size / ctx.pixels_per_point();
+
+                feather_outline(&mut mesh, &points, color, width);
+            }
+
+            self.add(&painter, mesh);
+
+            return Ok(());
+        }
 
         let stroke = Stroke::NONE;
 
         let shape = PathShape::convex_polygon(points, color, stroke);
 
-        painter.add(shape);
+        self.paint(&painter, shape, ShapeClass::Fills);
 
         Ok(())
     }
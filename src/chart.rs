@@ -1,36 +1,132 @@
 //! Structs used to simplify the process of making interactive charts
 
-use egui::{PointerState, Ui};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    hash::Hash,
+    ops::Range,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use egui::{
+    epaint::{ClippedShape, PaintCallback},
+    ColorImage, Context, Event, Id, InputState, LayerId, Modifiers, PointerState, Pos2, Rect,
+    Response, Sense, TextureHandle, TextureOptions, Ui, Vec2, ViewportCommand, Widget,
+};
 use plotters::{
-    coord::Shift,
+    coord::{
+        ranged3d::{ProjectionMatrix, ProjectionMatrixBuilder},
+        Shift,
+    },
+    element::{DashedPathElement, PathElement, Rectangle},
     prelude::{DrawingArea, IntoDrawingArea},
+    style::ShapeStyle,
 };
 
-use crate::EguiBackend;
+use crate::{
+    backend::selection_shapes, legend::draw_legend, raster::render_shapes, AnimationHandle,
+    CoordMapping, EguiBackend, LegendEntry, LegendRecorder, LegendStyle, MappingRecorder,
+    SeriesRegistry, StrokeUnits,
+};
 
 /// Default pitch and yaw scale for mouse rotations.
 pub const DEFAULT_MOVE_SCALE: f32 = 0.01;
 /// Default zoom scale for scroll wheel zooming.
 pub const DEFAULT_SCROLL_SCALE: f32 = 0.001;
+/// Default distance, in points, the mouse can be from a series for it to be hovered.
+pub const DEFAULT_HOVER_RADIUS: f32 = 8.0;
+/// Default scale of 3d projections at a chart scale of 1.
+///
+/// At a scale of 1 plotters fits the unrotated 3d box to the drawing area, so once it's rotated
+/// its corners and axis labels reach past the edges. Plotters' renderer doesn't clip 3d
+/// coordinates, which leaves stray lines and cut off labels along the edges. Shrinking the
+/// projection keeps the box inside the drawing area at any pitch and yaw.
+pub const DEFAULT_3D_SCALE: f64 = 0.7;
+/// Length of the dashes of overlay grids, in pixels.
+const OVERLAY_GRID_DASH: u32 = 4;
+/// Length of the gaps between the dashes of overlay grids, in pixels.
+const OVERLAY_GRID_GAP: u32 = 4;
+/// Most lines an overlay grid draws along each axis, so a tiny spacing can't stall drawing.
+const MAX_OVERLAY_GRID_LINES: usize = 1000;
+/// Size, in points, a chart widget takes along an axis without a limit on the space available.
+const DEFAULT_WIDGET_SIZE: f32 = 300.0;
+/// Selections smaller than this, in points, are treated as clicks and don't zoom.
+const MIN_SELECTION_SIZE: f32 = 4.0;
+/// Time constant, in seconds, of the easing toward the target scale when zooming smoothly.
+const SMOOTH_ZOOM_TIME: f32 = 0.08;
+/// Smooth zooming snaps to the target scale once within this fraction of it.
+const SMOOTH_ZOOM_EPSILON: f64 = 0.001;
+/// Default size of the magnifier's inset, in points.
+const DEFAULT_MAGNIFIER_SIZE: f32 = 150.0;
+/// Default zoom of the magnifier's inset relative to the chart.
+const DEFAULT_MAGNIFIER_ZOOM: f32 = 4.0;
+/// Distance of the magnifier's inset from the pointer, in points.
+const MAGNIFIER_OFFSET: f32 = 16.0;
+/// Key of the flag stored in a context once feathering has been warned about.
+const FEATHERING_WARNED_KEY: &str = "egui_plotter_feathering_warned";
+/// Tolerance in pixels lines in the minimap are simplified to, as it's too small to show detail.
+const MINIMAP_SIMPLIFY: f32 = 0.5;
 
-#[derive(Debug, Copy, Clone)]
+/// Number given to the next chart created, to give each chart its own id.
+static NEXT_CHART_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Mirror a range so that it is drawn from end to start.
+///
+/// Passing a mirrored Y range to `build_cartesian_2d` will place the origin at the top of the
+/// chart, with Y increasing downwards, which is useful for image-like or matrix data. When doing
+/// so you'll probably also want to use `top_x_label_area_size` instead of `x_label_area_size` so
+/// the X axis labels sit next to the origin.
+///
+/// Mirroring only changes how plotters maps your data, the offset and scale in [`Transform`] are
+/// applied afterwards in screen space. Dragging and zooming a mirrored chart therefore still moves
+/// the plot along with the mouse.
+///
+/// ```ignore
+/// let mut chart = ChartBuilder::on(area)
+///     .top_x_label_area_size(30)
+///     .y_label_area_size(30)
+///     .build_cartesian_2d(0f32..10f32, mirror_range(0f32..10f32))
+///     .unwrap();
+/// ```
+pub fn mirror_range<T>(range: Range<T>) -> Range<T> {
+    range.end..range.start
+}
+
+/// Callback used to populate a chart. Is provided the drawing area, the chart's transform and the
+/// chart's data.
+type BuilderCallback<Data> =
+    Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>;
+
+/// Callback used to fill a chart's context menu. Is provided the menu's ui, the chart and the
+/// data coordinates the menu was opened at, if a mapping was recorded.
+type ContextMenuCallback<Data> = Box<dyn FnMut(&mut Ui, &mut Chart<Data>, Option<(f64, f64)>)>;
+
+/// Callback used to hand a chart's shapes to a custom renderer. Is provided the rect of the chart
+/// and the shapes it drew, and returns the renderer's callback to put in an egui `PaintCallback`.
+type PaintCallbackBuilder = Box<dyn FnMut(Rect, Vec<ClippedShape>) -> Arc<dyn Any + Send + Sync>>;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 /// Transformations to be applied to your chart. Is modified by user input(if the mouse is enabled) and
 /// used by Chart::draw() and your builder callback.
 ///
 /// Chart::draw() applies the scale and the x/y offset to your plot, so unless
 /// you want to create some effects on your own you don't need to worry about them.
 ///
-/// If you are creating a 3d plot however you will have to manually apply the pitch and
-/// yaw to your chart with the following code:
+/// If you are creating a 3d plot however you will have to apply the pitch and yaw to your chart
+/// with the following code:
 ///
 /// ```ignore
-/// chart.with_projection(|mut pb| {
-///     pb.yaw = transform.yaw;
-///     pb.pitch = transform.pitch;
-///     pb.scale = 0.7; // Set scale to 0.7 to avoid artifacts caused by plotter's renderer
-///     pb.into_matrix()
-/// });
+/// chart.with_projection(|pb| transform.projection(pb));
 ///```
+///
+/// The projection only applies the scale when `three_d` is set, which `Chart::set_3d` does. Other
+/// charts have the scale applied to the whole drawing by the backend instead, so the projection
+/// leaves it out rather than zooming twice.
 pub struct Transform {
     /// Pitch of your graph in 3d
     pub pitch: f64,
@@ -42,6 +138,135 @@ pub struct Transform {
     pub x: i32,
     /// Y offset of your graph. Applied in Chart::draw()
     pub y: i32,
+    /// Scale of 3d projections at a scale of 1, applied by `Transform::projection`. See
+    /// [`DEFAULT_3D_SCALE`] for why it's below 1.
+    pub projection_scale: f64,
+    /// Fraction of each series to draw, from 0.0 to 1.0. Rises from 0.0 to 1.0 while the
+    /// animation set with `Chart::animate_draw` plays, and is 1.0 otherwise. Not applied
+    /// automatically, see `Transform::revealed`.
+    pub reveal: f64,
+    /// Whether the scale is applied by `Transform::projection` rather than to the whole drawing.
+    /// Set by `Chart::set_3d`.
+    pub three_d: bool,
+}
+
+impl Transform {
+    /// Apply the pitch, yaw and scale to a 3d chart's projection. Pass to plotters'
+    /// `with_projection` in your builder callback. The projection scale is always applied, so
+    /// there's no need to shrink the projection yourself, but the scale is only applied if
+    /// `three_d` is set with `Chart::set_3d`. Otherwise the chart already scales the drawing.
+    ///
+    /// ```
+    /// # use egui_plotter::Transform;
+    /// # use plotters::coord::ranged3d::ProjectionMatrixBuilder;
+    /// let zoomed = Transform {
+    ///     scale: 2.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let matrix = |transform: Transform| *transform.projection(ProjectionMatrixBuilder::new()).as_ref();
+    ///
+    /// // Without 3d the backend zooms the drawing, so the projection doesn't zoom again
+    /// assert_eq!(matrix(zoomed), matrix(Transform::default()));
+    ///
+    /// let zoomed = Transform {
+    ///     three_d: true,
+    ///     ..zoomed
+    /// };
+    ///
+    /// assert_ne!(matrix(zoomed), matrix(Transform::default()));
+    /// ```
+    pub fn projection(&self, mut pb: ProjectionMatrixBuilder) -> ProjectionMatrix {
+        pb.yaw = self.yaw;
+        pb.pitch = self.pitch;
+        pb.scale = match self.three_d {
+            true => self.projection_scale * self.scale,
+            false => self.projection_scale,
+        };
+
+        pb.into_matrix()
+    }
+
+    /// Return the part of a series to draw given the reveal fraction, so the series is traced
+    /// out while the chart's draw animation plays. Use it on your series in the builder callback.
+    ///
+    /// ```
+    /// # use egui_plotter::Transform;
+    /// let points = [(0.0, 1.0), (1.0, 2.0), (2.0, 4.0), (3.0, 8.0)];
+    ///
+    /// let transform = Transform {
+    ///     reveal: 0.5,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(transform.revealed(&points), &points[..2]);
+    /// ```
+    pub fn revealed<'a, T>(&self, series: &'a [T]) -> &'a [T] {
+        let len = (series.len() as f64 * self.reveal.clamp(0.0, 1.0)).ceil() as usize;
+
+        &series[..len]
+    }
+
+    /// Apply the offset and scale to a pixel position the backend was given, returning the
+    /// position within bounds it's drawn at.
+    fn transform(&self, pixel: (f64, f64), bounds: Rect, scale: Vec2) -> Pos2 {
+        let offset = Vec2::new(self.x as f32, self.y as f32);
+        let center = bounds.size() / 2.0;
+
+        let (x, y) = pixel;
+        let pixel = Vec2::new(x as f32, y as f32);
+
+        bounds.min + (pixel - center) * scale + center + offset
+    }
+
+    /// Undo the offset and scale applied to a position within bounds, returning the pixel
+    /// position the backend was given.
+    fn untransform(&self, pos: Pos2, bounds: Rect, scale: Vec2) -> (f64, f64) {
+        let offset = Vec2::new(self.x as f32, self.y as f32);
+        let center = bounds.size() / 2.0;
+
+        let pixel = (pos - bounds.min - offset - center) / scale + center;
+
+        (pixel.x as f64, pixel.y as f64)
+    }
+
+    /// Adjust the scale and offset so the given selection, in screen coordinates, fills the
+    /// bounds the chart is drawn in. The scale is uniform so the selection is fitted to whichever
+    /// axis is the tightest. Locked axes, given as `(x, y)`, are left as they are.
+    fn fit_selection(&mut self, selection: Rect, bounds: Rect, locked: (bool, bool)) {
+        if selection.width() < MIN_SELECTION_SIZE || selection.height() < MIN_SELECTION_SIZE {
+            return;
+        }
+
+        let zoom = match locked {
+            (false, false) => {
+                (bounds.width() / selection.width()).min(bounds.height() / selection.height())
+            }
+            (false, true) => bounds.width() / selection.width(),
+            (true, false) => bounds.height() / selection.height(),
+            (true, true) => return,
+        };
+
+        if !zoom.is_finite() || zoom <= 0.0 {
+            return;
+        }
+
+        // Undo the offset and scale to get the selection center relative to the bounds center
+        let offset = Vec2::new(self.x as f32, self.y as f32);
+        let center = (selection.center() - bounds.center() - offset) / self.scale as f32;
+
+        self.scale *= zoom as f64;
+
+        let offset = -center * self.scale as f32;
+
+        if !locked.0 {
+            self.x = offset.x.round() as i32;
+        }
+
+        if !locked.1 {
+            self.y = offset.y.round() as i32;
+        }
+    }
 }
 
 impl Default for Transform {
@@ -52,7 +277,236 @@ impl Default for Transform {
             scale: 1.0,
             x: 0,
             y: 0,
+            projection_scale: DEFAULT_3D_SCALE,
+            reveal: 1.0,
+            three_d: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Shared handle used to link the X offset and scale of several charts, so panning or zooming one
+/// pans or zooms them all. As the scale applies to both axes, linked charts zoom together on Y
+/// too, unless their Y axis is locked with `MouseConfig::set_lock_axis`. Add charts to a group
+/// with `Chart::set_sync_group` or `Chart::link_x_with`.
+///
+/// Since mouse input is shared by every chart, charts in a group only respond to dragging,
+/// rotating and zooming while hovered.
+pub struct SyncGroup(Rc<Cell<Option<(i32, f64)>>>);
+
+impl SyncGroup {
+    #[inline]
+    /// Create a new, empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Exchange the X offset and scale with the group. Changes made to the transform since the
+    /// last exchange are shared with the group, otherwise the group's values are adopted.
+    fn sync(&self, transform: &mut Transform, last: &mut Option<(i32, f64)>) {
+        let current = (transform.x, transform.scale);
+
+        match (*last, self.0.get()) {
+            (Some(last), Some(shared)) if last == current => {
+                (transform.x, transform.scale) = shared
+            }
+            (None, Some(shared)) => (transform.x, transform.scale) = shared,
+            _ => self.0.set(Some(current)),
+        }
+
+        *last = Some((transform.x, transform.scale));
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Axis of a chart.
+pub enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Spacing of the lines of an overlay grid, see `Chart::add_overlay_grid`.
+pub enum GridSpacing {
+    /// Lines at every multiple of the given X and Y intervals, in data units.
+    Data(f64, f64),
+    /// Lines every given number of pixels along X and Y, counted from the origin of the
+    /// plotting area. The pixels are scaled along with the rest of the chart when zooming.
+    Pixels(f64, f64),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Inset showing the region of a chart around the pointer zoomed in, see `Chart::set_magnifier`.
+pub struct MagnifierConfig {
+    /// Size of the inset, in points.
+    pub size: Vec2,
+    /// How much further the inset is zoomed in than the chart.
+    pub zoom: f32,
+}
+
+impl Default for MagnifierConfig {
+    fn default() -> Self {
+        Self {
+            size: Vec2::splat(DEFAULT_MAGNIFIER_SIZE),
+            zoom: DEFAULT_MAGNIFIER_ZOOM,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Reference line, band or grid drawn over a chart.
+enum Reference {
+    Line(Axis, f64, ShapeStyle),
+    Band(Axis, Range<f64>, ShapeStyle),
+    Grid(GridSpacing, ShapeStyle),
+}
+
+impl Reference {
+    /// Draw the reference across the plotting area, skipping it if it's outside the data range.
+    fn draw(&self, area: &DrawingArea<EguiBackend, Shift>, mapping: &CoordMapping) {
+        let (x_range, y_range) = (mapping.x_range(), mapping.y_range());
+
+        let clamp = |value: f64, range: &Range<f64>| {
+            value.clamp(range.start.min(range.end), range.start.max(range.end))
+        };
+
+        let (axis, range, style) = match self {
+            Self::Grid(spacing, style) => return draw_overlay_grid(area, mapping, spacing, *style),
+            Self::Line(axis, value, style) => (axis, *value..*value, *style),
+            Self::Band(axis, range, style) => (
+                axis,
+                range.clone(),
+                ShapeStyle {
+                    filled: true,
+                    ..*style
+                },
+            ),
+        };
+
+        let axis_range = match axis {
+            Axis::X => &x_range,
+            Axis::Y => &y_range,
+        };
+
+        let (start, end) = (clamp(range.start, axis_range), clamp(range.end, axis_range));
+
+        // Lines outside of the data range are hidden, bands are clipped to it
+        let visible = match self {
+            Self::Line(..) => start == range.start,
+            _ => start != end,
+        };
+
+        if !visible {
+            return;
         }
+
+        let (from, to) = match axis {
+            Axis::X => ((start, y_range.start), (end, y_range.end)),
+            Axis::Y => ((x_range.start, start), (x_range.end, end)),
+        };
+
+        let pixel = |data: (f64, f64)| {
+            let (x, y) = mapping.data_to_pixel(data);
+
+            (x.round() as i32, y.round() as i32)
+        };
+
+        let (from, to) = (pixel(from), pixel(to));
+
+        let _ = match self {
+            Self::Line(..) => area.draw(&PathElement::new(
+                match axis {
+                    Axis::X => vec![(from.0, from.1), (from.0, to.1)],
+                    Axis::Y => vec![(from.0, from.1), (to.0, from.1)],
+                },
+                style,
+            )),
+            _ => area.draw(&Rectangle::new([from, to], style)),
+        };
+    }
+}
+
+/// Draw dashed lines across the plotting area at the given spacing.
+fn draw_overlay_grid(
+    area: &DrawingArea<EguiBackend, Shift>,
+    mapping: &CoordMapping,
+    spacing: &GridSpacing,
+    style: ShapeStyle,
+) {
+    let (x_range, y_range) = (mapping.x_range(), mapping.y_range());
+
+    let (left, bottom) = mapping.data_to_pixel((x_range.start, y_range.start));
+    let (right, top) = mapping.data_to_pixel((x_range.end, y_range.end));
+
+    let (columns, rows): (Vec<f64>, Vec<f64>) = match *spacing {
+        GridSpacing::Data(x_step, y_step) => (
+            grid_values(&x_range, 0.0, x_step)
+                .map(|x| mapping.data_to_pixel((x, y_range.start)).0)
+                .collect(),
+            grid_values(&y_range, 0.0, y_step)
+                .map(|y| mapping.data_to_pixel((x_range.start, y)).1)
+                .collect(),
+        ),
+        GridSpacing::Pixels(x_step, y_step) => (
+            grid_values(&(left..right), left, x_step).collect(),
+            grid_values(&(bottom..top), bottom, y_step).collect(),
+        ),
+    };
+
+    let lines = columns
+        .into_iter()
+        .map(|x| [(x, top), (x, bottom)])
+        .chain(rows.into_iter().map(|y| [(left, y), (right, y)]));
+
+    for line in lines {
+        let line = line.map(|(x, y)| (x.round() as i32, y.round() as i32));
+
+        let _ = area.draw(&DashedPathElement::new(
+            line,
+            OVERLAY_GRID_DASH,
+            OVERLAY_GRID_GAP,
+            style,
+        ));
+    }
+}
+
+/// Return the values within a range, in either direction, that are a whole number of steps from
+/// the origin. Returns nothing if the step isn't positive and finite.
+fn grid_values(range: &Range<f64>, origin: f64, step: f64) -> impl Iterator<Item = f64> {
+    let (min, max) = (range.start.min(range.end), range.start.max(range.end));
+
+    let first = ((min - origin) / step).ceil();
+    let last = ((max - origin) / step).floor();
+
+    let count = match step > 0.0 && step.is_finite() {
+        true => ((last - first + 1.0).max(0.0) as usize).min(MAX_OVERLAY_GRID_LINES),
+        false => 0,
+    };
+
+    (0..count).map(move |i| origin + (first + i as f64) * step)
+}
+
+/// Log a warning if feathering is enabled in the context's tessellation options, only the first
+/// time it's found for each context.
+fn warn_feathering(ctx: &Context) {
+    if !ctx.tessellation_options(|options| options.feathering) {
+        return;
+    }
+
+    let warned = ctx.data_mut(|data| {
+        std::mem::replace(
+            data.get_temp_mut_or_default::<bool>(Id::new(FEATHERING_WARNED_KEY)),
+            true,
+        )
+    });
+
+    if !warned {
+        log::warn!(
+            "egui-plotter: feathering is enabled in the egui context's tessellation options, \
+             which causes artifacts in charts. Disable it with \
+             `ctx.tessellation_options_mut(|options| options.feathering = false)`, or silence \
+             this warning with `Chart::warn_feathering(false)`"
+        );
     }
 }
 
@@ -75,6 +529,19 @@ impl MouseButton {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// What scrolling over a chart does.
+pub enum ScrollAction {
+    /// Scrolling zooms in and out.
+    #[default]
+    Zoom,
+    /// Scrolling pans the chart, as two-finger scrolling on a touchpad usually does.
+    Pan,
+    /// Scrolling pans the chart, and scrolling with Ctrl (Cmd on Mac) held zooms, like in a web
+    /// browser. Pinch gestures zoom too.
+    PanWithZoomModifier,
+}
+
 #[derive(Debug, Copy, Clone)]
 /// Used to configure how the mouse interacts with the chart.
 ///
@@ -82,12 +549,28 @@ impl MouseButton {
 /// MouseConfig allows you to change the ways the user interacts with your chart in the following
 /// ways:
 ///  * `drag`, `rotate`, & `zoom` - Enables dragging, rotating, and zooming in on your plots with
-///  mouse controls.
+///    mouse controls.
 ///  * `pitch_scale` & `yaw_scale` - Modifies how quickly the pitch and yaw are rotated when rotating with the
-///  mouse.
+///    mouse.
 ///  * `zoom_scale` - Modifies how quickly you zoom in/out.
 ///  * `drag_bind` - Mouse button bound to dragging your plot.
 ///  * `rotate_bind` - Mouse button bound to rotating your plot.
+///  * `drag_modifiers` & `rotate_modifiers` - Modifier keys that must be held, along with the
+///    bound mouse button, to drag or rotate your plot. If both actions are bound to the same
+///    button, holding the modifiers of one action disables the action without modifiers. For
+///    example, binding both to the primary button and setting `rotate_modifiers` to
+///    `Modifiers::CTRL` pans on left-drag and rotates on Ctrl+left-drag.
+///  * `box_zoom` & `box_zoom_bind` - Enables zooming by dragging a box with the bound mouse button,
+///    which is fitted to the chart on release. Disabled by default, and bound to the secondary
+///    button.
+///  * `scroll_action` - Whether scrolling zooms or pans, see [`ScrollAction`]. Zooms by default.
+///  * `smooth_zoom` - Eases the scale toward the zoom level scrolled to over a few frames, rather
+///    than jumping to it on each scroll. Disabled by default.
+///  * `snap_to_grid` - Nudges the chart by less than a pixel as it's panned and zoomed, so the
+///    major gridlines land on whole pixels and stay sharp. Needs a recorded mapping, see
+///    `Chart::mapping_recorder`. Disabled by default.
+///  * `lock_axis` - Locks an axis so dragging doesn't pan along it and zooming doesn't stretch it,
+///    leaving the other axis to be panned and zoomed alone. Neither axis is locked by default.
 pub struct MouseConfig {
     drag: bool,
     rotate: bool,
@@ -97,6 +580,15 @@ pub struct MouseConfig {
     zoom_scale: f32,
     drag_bind: MouseButton,
     rotate_bind: MouseButton,
+    drag_modifiers: Option<Modifiers>,
+    rotate_modifiers: Option<Modifiers>,
+    box_zoom: bool,
+    box_zoom_bind: MouseButton,
+    scroll_action: ScrollAction,
+    smooth_zoom: bool,
+    snap_to_grid: bool,
+    lock_x: bool,
+    lock_y: bool,
 }
 
 impl Default for MouseConfig {
@@ -110,6 +602,15 @@ impl Default for MouseConfig {
             zoom_scale: DEFAULT_SCROLL_SCALE,
             drag_bind: MouseButton::Middle,
             rotate_bind: MouseButton::Primary,
+            drag_modifiers: None,
+            rotate_modifiers: None,
+            box_zoom: false,
+            box_zoom_bind: MouseButton::Secondary,
+            scroll_action: ScrollAction::Zoom,
+            smooth_zoom: false,
+            snap_to_grid: false,
+            lock_x: false,
+            lock_y: false,
         }
     }
 }
@@ -127,6 +628,15 @@ impl MouseConfig {
             zoom_scale: DEFAULT_SCROLL_SCALE,
             drag_bind: MouseButton::Middle,
             rotate_bind: MouseButton::Primary,
+            drag_modifiers: None,
+            rotate_modifiers: None,
+            box_zoom: false,
+            box_zoom_bind: MouseButton::Secondary,
+            scroll_action: ScrollAction::Zoom,
+            smooth_zoom: false,
+            snap_to_grid: false,
+            lock_x: false,
+            lock_y: false,
         }
     }
 
@@ -201,6 +711,229 @@ impl MouseConfig {
 
         self
     }
+
+    #[inline]
+    /// Change the yaw scale.
+    pub fn set_yaw_scale(&mut self, scale: f32) {
+        self.yaw_scale = scale
+    }
+
+    #[inline]
+    /// Change the yaw scale. Consumes self.
+    pub fn yaw_scale(mut self, scale: f32) -> Self {
+        self.set_yaw_scale(scale);
+
+        self
+    }
+
+    #[inline]
+    /// Change the zoom scale, how much the chart is zoomed per point scrolled.
+    pub fn set_zoom_scale(&mut self, scale: f32) {
+        self.zoom_scale = scale
+    }
+
+    #[inline]
+    /// Change the zoom scale. Consumes self.
+    pub fn zoom_scale(mut self, scale: f32) -> Self {
+        self.set_zoom_scale(scale);
+
+        self
+    }
+
+    #[inline]
+    /// Change the mouse button bound to dragging.
+    pub fn set_drag_bind(&mut self, bind: MouseButton) {
+        self.drag_bind = bind
+    }
+
+    #[inline]
+    /// Change the mouse button bound to dragging. Consumes self.
+    pub fn drag_bind(mut self, bind: MouseButton) -> Self {
+        self.set_drag_bind(bind);
+
+        self
+    }
+
+    #[inline]
+    /// Change the mouse button bound to rotating.
+    pub fn set_rotate_bind(&mut self, bind: MouseButton) {
+        self.rotate_bind = bind
+    }
+
+    #[inline]
+    /// Change the mouse button bound to rotating. Consumes self.
+    pub fn rotate_bind(mut self, bind: MouseButton) -> Self {
+        self.set_rotate_bind(bind);
+
+        self
+    }
+
+    #[inline]
+    /// Set the modifiers that must be held to drag, or None to not require any.
+    pub fn set_drag_modifiers(&mut self, modifiers: Option<Modifiers>) {
+        self.drag_modifiers = modifiers
+    }
+
+    #[inline]
+    /// Set the modifiers that must be held to drag, or None to not require any. Consumes self.
+    pub fn drag_modifiers(mut self, modifiers: Option<Modifiers>) -> Self {
+        self.set_drag_modifiers(modifiers);
+
+        self
+    }
+
+    #[inline]
+    /// Set the modifiers that must be held to rotate, or None to not require any.
+    pub fn set_rotate_modifiers(&mut self, modifiers: Option<Modifiers>) {
+        self.rotate_modifiers = modifiers
+    }
+
+    #[inline]
+    /// Set the modifiers that must be held to rotate, or None to not require any. Consumes self.
+    pub fn rotate_modifiers(mut self, modifiers: Option<Modifiers>) -> Self {
+        self.set_rotate_modifiers(modifiers);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable box zoom, where dragging a box with the box zoom bind zooms into it.
+    pub fn set_box_zoom(&mut self, box_zoom: bool) {
+        self.box_zoom = box_zoom
+    }
+
+    #[inline]
+    /// Enable/disable box zoom. Consumes self.
+    pub fn box_zoom(mut self, box_zoom: bool) -> Self {
+        self.set_box_zoom(box_zoom);
+
+        self
+    }
+
+    #[inline]
+    /// Set what scrolling over the chart does. Zooms by default.
+    pub fn set_scroll_action(&mut self, scroll_action: ScrollAction) {
+        self.scroll_action = scroll_action
+    }
+
+    #[inline]
+    /// Set what scrolling over the chart does. Consumes self.
+    pub fn scroll_action(mut self, scroll_action: ScrollAction) -> Self {
+        self.set_scroll_action(scroll_action);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable easing the scale toward the zoom level scrolled to, rather than jumping to
+    /// it on each scroll.
+    pub fn set_smooth_zoom(&mut self, smooth_zoom: bool) {
+        self.smooth_zoom = smooth_zoom
+    }
+
+    #[inline]
+    /// Enable/disable easing the scale toward the zoom level scrolled to. Consumes self.
+    pub fn smooth_zoom(mut self, smooth_zoom: bool) -> Self {
+        self.set_smooth_zoom(smooth_zoom);
+
+        self
+    }
+
+    #[inline]
+    /// Change the mouse button bound to box zoom.
+    pub fn set_box_zoom_bind(&mut self, bind: MouseButton) {
+        self.box_zoom_bind = bind
+    }
+
+    #[inline]
+    /// Change the mouse button bound to box zoom. Consumes self.
+    pub fn box_zoom_bind(mut self, bind: MouseButton) -> Self {
+        self.set_box_zoom_bind(bind);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable nudging the chart by less than a pixel so the major gridlines land on whole
+    /// pixels, keeping them sharp while panning and zooming. Only applies to 2d charts with a
+    /// recorded mapping.
+    pub fn set_snap_to_grid(&mut self, snap_to_grid: bool) {
+        self.snap_to_grid = snap_to_grid
+    }
+
+    #[inline]
+    /// Enable/disable nudging the major gridlines onto whole pixels. Consumes self.
+    pub fn snap_to_grid(mut self, snap_to_grid: bool) -> Self {
+        self.set_snap_to_grid(snap_to_grid);
+
+        self
+    }
+
+    #[inline]
+    /// Lock/unlock an axis, so dragging doesn't pan along it and zooming doesn't stretch it.
+    /// Locking Y gives oscilloscope-style views with a fixed amplitude scale.
+    pub fn set_lock_axis(&mut self, axis: Axis, lock: bool) {
+        match axis {
+            Axis::X => self.lock_x = lock,
+            Axis::Y => self.lock_y = lock,
+        }
+    }
+
+    #[inline]
+    /// Lock/unlock an axis from panning and zooming. Consumes self.
+    pub fn lock_axis(mut self, axis: Axis, lock: bool) -> Self {
+        self.set_lock_axis(axis, lock);
+
+        self
+    }
+
+    /// Return the scale a chart is drawn at on screen along X and Y. Locked axes stay at a scale
+    /// of 1, as do 3d charts, whose projection is scaled instead.
+    fn screen_scale(&self, scale: f64, three_d: bool) -> Vec2 {
+        let axis_scale = |locked: bool| match locked || three_d {
+            true => 1.0,
+            false => scale as f32,
+        };
+
+        Vec2::new(axis_scale(self.lock_x), axis_scale(self.lock_y))
+    }
+
+    /// Return which of dragging and rotating are currently active, in that order.
+    fn active_actions(&self, input: &InputState) -> (bool, bool) {
+        let pointer = &input.pointer;
+
+        let held = |modifiers: Option<Modifiers>| match modifiers {
+            Some(modifiers) => input.modifiers.matches_logically(modifiers),
+            None => true,
+        };
+
+        let drag = self.drag && self.drag_bind.is_down(pointer) && held(self.drag_modifiers);
+        let rotate =
+            self.rotate && self.rotate_bind.is_down(pointer) && held(self.rotate_modifiers);
+
+        // If both actions are triggered by the same button, the one that requires modifiers is
+        // more specific and takes priority
+        match (drag && rotate, self.drag_modifiers, self.rotate_modifiers) {
+            (true, Some(_), None) => (true, false),
+            (true, None, Some(_)) => (false, true),
+            _ => (drag, rotate),
+        }
+    }
+
+    /// Return whether scrolling pans and whether it zooms, given the current input.
+    fn scroll_actions(&self, input: &InputState) -> (bool, bool) {
+        let (pan, zoom) = match self.scroll_action {
+            ScrollAction::Zoom => (false, true),
+            ScrollAction::Pan => (true, false),
+            ScrollAction::PanWithZoomModifier => {
+                let zoom = input.modifiers.command;
+
+                (!zoom, zoom)
+            }
+        };
+
+        (pan && self.drag, zoom && self.zoom)
+    }
 }
 
 /// Allows users to drag, rotate, and zoom in/out on your plots.
@@ -210,20 +943,69 @@ impl MouseConfig {
 /// being powerful enough for your application. You can manipulate the
 /// following properties of a chart to get the effects you want:
 ///  * `builder_cb` - Callback used to populate the chart. Is provided a DrawingArea and the
-///  chart's `data`.
+///    chart's `data`.
 ///  * `mouse` - Mouse configuration. Configure how you wish the mouse to affect/manipulate the
-///  chart.
+///    chart.
 ///  * `data` - A Box of data of any type to be stored with the chart. Provided so that you can modify data
-///  without having to specify a new callback during runtime. For example, `examples/parachart.rs`
-///  uses it to store the range so it can be changed during runtime.
+///    without having to specify a new callback during runtime. For example, `examples/parachart.rs`
+///    uses it to store the range so it can be changed during runtime.
+///
+/// The ranges given to `build_cartesian_2d` in the callback must have a width, as plotters
+/// divides by it when mapping coordinates to pixels. When fitting ranges to data where every point
+/// may share a value, use `charts::fit_ranges`, which widens such ranges. The prebuilt charts do
+/// this themselves, so a chart of identical points is drawn flat rather than failing.
+///
+/// Anything drawn in the callback goes through the chart's backend, including custom plotters
+/// elements, bitmaps and circles, so it's panned, zoomed and layered in the order it's drawn
+/// along with the rest of the chart. Use [`draw_at`](crate::draw_at) to draw an element given in
+/// pixels, such as a marker or label, at a point in the chart's coordinates.
 ///
 ///  ## Examples
 ///  See `examples/3dchart.rs` and `examples/parachart.rs` for examples of usage.
 pub struct Chart<Data> {
     transform: Transform,
     mouse: MouseConfig,
-    builder_cb: Option<Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>>,
+    builder_cb: Option<BuilderCallback<Data>>,
     data: Data,
+    drawing_rect: Option<Rect>,
+    screenshot_requested: bool,
+    screenshot_rect: Option<Rect>,
+    screenshot: Option<ColorImage>,
+    box_zoom_start: Option<Pos2>,
+    target_scale: Option<f64>,
+    animate_draw: Option<Duration>,
+    draw_elapsed: Option<f64>,
+    mapping: MappingRecorder,
+    sync: Option<SyncGroup>,
+    last_sync: Option<(i32, f64)>,
+    legend: Vec<LegendEntry>,
+    legend_recorder: LegendRecorder,
+    legend_style: LegendStyle,
+    references: Vec<Reference>,
+    context_menu: Option<ContextMenuCallback<Data>>,
+    paint_callback: Option<PaintCallbackBuilder>,
+    context_pos: Option<(f64, f64)>,
+    texture: Option<TextureHandle>,
+    texture_state: Option<(Transform, [usize; 2], u64)>,
+    /// Incremented whenever the chart changes, so drawings of it kept between frames can tell
+    /// when they're out of date.
+    revision: u64,
+    minimap: Option<Minimap>,
+    id: Id,
+    hover_radius: f32,
+    opacity: f32,
+    transparent: bool,
+    math_text: bool,
+    letter_spacing: f32,
+    stroke_units: StrokeUnits,
+    animation: Option<AnimationHandle>,
+    series: SeriesRegistry,
+    auto_legend: bool,
+    interacting: bool,
+    square: bool,
+    min_size: Vec2,
+    warn_feathering: bool,
+    magnifier: Option<MagnifierConfig>,
 }
 
 impl<Data> Chart<Data> {
@@ -234,6 +1016,46 @@ impl<Data> Chart<Data> {
             mouse: MouseConfig::default(),
             builder_cb: None,
             data,
+            drawing_rect: None,
+            screenshot_requested: false,
+            screenshot_rect: None,
+            screenshot: None,
+            box_zoom_start: None,
+            target_scale: None,
+            animate_draw: None,
+            draw_elapsed: None,
+            mapping: MappingRecorder::new(),
+            sync: None,
+            last_sync: None,
+            legend: Vec::new(),
+            legend_recorder: LegendRecorder::new(),
+            legend_style: LegendStyle::default(),
+            references: Vec::new(),
+            context_menu: None,
+            paint_callback: None,
+            context_pos: None,
+            texture: None,
+            texture_state: None,
+            revision: 0,
+            minimap: None,
+            id: Id::new((
+                "egui_plotter_chart",
+                NEXT_CHART_ID.fetch_add(1, Ordering::Relaxed),
+            )),
+            hover_radius: DEFAULT_HOVER_RADIUS,
+            opacity: 1.0,
+            transparent: false,
+            math_text: false,
+            letter_spacing: 0.0,
+            stroke_units: StrokeUnits::default(),
+            animation: None,
+            series: SeriesRegistry::new(),
+            auto_legend: false,
+            interacting: false,
+            square: false,
+            min_size: Vec2::ZERO,
+            warn_feathering: true,
+            magnifier: None,
         }
     }
 
@@ -252,90 +1074,1199 @@ impl<Data> Chart<Data> {
     }
 
     #[inline]
-    /// Set the builder callback.
-    pub fn set_builder_cb(
-        &mut self,
-        builder_cb: Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>,
-    ) {
-        self.builder_cb = Some(builder_cb)
+    /// Lock or unlock an axis of the mouse controls, keeping the rest of them as they are.
+    pub(crate) fn set_mouse_lock_axis(&mut self, axis: Axis, lock: bool) {
+        self.mouse.set_lock_axis(axis, lock)
     }
 
     #[inline]
-    /// Set the builder callback. Consumes self.
-    pub fn builder_cb(
-        mut self,
-        builder_cb: Box<dyn FnMut(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data)>,
-    ) -> Self {
-        self.set_builder_cb(builder_cb);
-
-        self
+    /// Enable/disable 3d. When enabled the scale isn't applied to the drawing, and is instead
+    /// applied by `Transform::projection`, so zooming scales the plot without scaling the
+    /// caption and labels. Sets `three_d` on the chart's transform.
+    pub fn set_3d(&mut self, three_d: bool) {
+        self.transform.three_d = three_d
     }
 
     #[inline]
-    /// Set the pitch of the chart.
-    pub fn set_pitch(&mut self, pitch: f64) {
-        self.transform.pitch = pitch
+    /// Enable/disable 3d. Consumes self.
+    pub fn three_d(mut self, three_d: bool) -> Self {
+        self.set_3d(three_d);
+
+        self
     }
 
     #[inline]
-    /// Set the pitch of the chart. Consumes self.
-    pub fn pitch(mut self, pitch: f64) -> Self {
-        self.set_pitch(pitch);
-
-        self
+    /// Add the chart to a group of charts that pan and zoom together, or remove it from its
+    /// group with None. See [`SyncGroup`].
+    pub fn set_sync_group(&mut self, sync: Option<SyncGroup>) {
+        self.sync = sync;
+        self.last_sync = None;
     }
 
     #[inline]
-    /// Set the yaw of the chart.
-    pub fn set_yaw(&mut self, yaw: f64) {
-        self.transform.yaw = yaw
+    /// Set the source of the id the chart's interactions, such as clicking the minimap, are
+    /// registered with in egui. Each chart gets its own id when it's created, so this is only
+    /// needed for charts that are created again every frame, whose id must stay the same.
+    pub fn set_id_source(&mut self, id_source: impl Hash) {
+        self.id = Id::new(id_source)
     }
 
     #[inline]
-    /// Set the yaw of the chart. Consumes self.
-    pub fn yaw(mut self, yaw: f64) -> Self {
-        self.set_yaw(yaw);
+    /// Set the source of the id the chart's interactions are registered with. Consumes self.
+    pub fn id_source(mut self, id_source: impl Hash) -> Self {
+        self.set_id_source(id_source);
 
         self
     }
 
     #[inline]
-    /// Set the scale of the chart.
-    pub fn set_scale(&mut self, scale: f64) {
-        self.transform.scale = scale
+    /// Get the id the chart's interactions are registered with in egui.
+    pub fn id(&self) -> Id {
+        self.id
     }
 
     #[inline]
-    /// Set the scale of the chart. Consumes self.
-    pub fn scale(mut self, scale: f64) -> Self {
-        self.set_scale(scale);
+    /// Report to an animation handle whenever the chart is drawn while animating or being
+    /// interacted with, or stop reporting with None. See [`AnimationHandle`].
+    pub fn set_animation_handle(&mut self, animation: Option<AnimationHandle>) {
+        self.animation = animation
+    }
+
+    #[inline]
+    /// Report to an animation handle while animating or being interacted with. Consumes self.
+    pub fn animation_handle(mut self, animation: Option<AnimationHandle>) -> Self {
+        self.set_animation_handle(animation);
 
         self
     }
 
     #[inline]
-    /// Get the data of the chart as a reference.
-    pub fn get_data(&self) -> &Data {
-        &self.data
+    /// Mark the chart's animation handle active, if it has one.
+    pub(crate) fn mark_active(&self) {
+        if let Some(animation) = &self.animation {
+            animation.mark_active();
+        }
+    }
+
+    #[inline]
+    /// Return true if the chart was being dragged, rotated, scrolled or box zoomed during the
+    /// last call to `draw`.
+    pub fn is_interacting(&self) -> bool {
+        self.interacting
+    }
+
+    #[inline]
+    /// Return true if the chart is still easing toward a zoom level or tracing out its series,
+    /// and so will change in the next frame without any input.
+    pub fn is_animating(&self) -> bool {
+        self.target_scale.is_some() || self.transform.reveal < 1.0
+    }
+
+    /// Step the chart's animations forward by the given number of seconds, as if that much time
+    /// had passed: tracing out the series set with `animate_draw`, and easing toward the zoom
+    /// level being zoomed to. `draw` does this with egui's time since the last frame, so calling
+    /// it yourself lets you drive the animations deterministically, such as in tests run with
+    /// `RawInput::predicted_dt` set to 0.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use egui_plotter::Chart;
+    /// let mut chart = Chart::new(()).animate_draw(Duration::from_secs(2));
+    ///
+    /// chart.advance(0.0);
+    /// chart.advance(0.5);
+    ///
+    /// assert_eq!(chart.get_transform().reveal, 0.25);
+    /// assert!(chart.is_animating());
+    /// ```
+    pub fn advance(&mut self, dt: f32) {
+        let dt = dt.max(0.0);
+
+        // The draw animation plays from the first frame the chart is shown
+        if let Some(duration) = self.animate_draw {
+            let elapsed = self.draw_elapsed.map_or(0.0, |elapsed| elapsed + dt as f64);
+
+            self.draw_elapsed = Some(elapsed);
+            self.transform.reveal = match duration.as_secs_f64() {
+                secs if secs > 0.0 => (elapsed / secs).min(1.0),
+                _ => 1.0,
+            };
+        }
+
+        // Ease the scale toward the scale being zoomed to, independent of the frame rate
+        if let Some(target) = self.target_scale {
+            let transform = &mut self.transform;
+            let t = 1.0 - (-dt / SMOOTH_ZOOM_TIME).exp();
+
+            transform.scale += (target - transform.scale) * t as f64;
+
+            if (target - transform.scale).abs()
+                <= target.max(SMOOTH_ZOOM_EPSILON) * SMOOTH_ZOOM_EPSILON
+            {
+                transform.scale = target;
+                self.target_scale = None;
+            }
+        }
+    }
+
+    #[inline]
+    /// Add the chart to a group of charts that pan and zoom together. Consumes self.
+    pub fn sync_group(mut self, sync: Option<SyncGroup>) -> Self {
+        self.set_sync_group(sync);
+
+        self
+    }
+
+    /// Link the X offset and scale of this chart with another, adding the other chart to this
+    /// chart's group, or creating a group if this chart isn't in one.
+    pub fn link_x_with<Other>(&mut self, other: &mut Chart<Other>) {
+        let sync = match &self.sync {
+            Some(sync) => sync.clone(),
+            None => {
+                let sync = SyncGroup::new();
+
+                self.set_sync_group(Some(sync.clone()));
+
+                sync
+            }
+        };
+
+        other.set_sync_group(Some(sync));
+    }
+
+    #[inline]
+    /// Set entries of the legend drawn over the chart with egui's shapes, for series that aren't
+    /// labeled through plotters, such as ones drawn without `draw_series`. They're shown before
+    /// the labels read with `legend_recorder`. No legend is drawn if there are no entries, which
+    /// is the default.
+    pub fn set_legend(&mut self, legend: Vec<LegendEntry>) {
+        self.legend = legend
+    }
+
+    #[inline]
+    /// Set the entries of the legend drawn over the chart. Consumes self.
+    pub fn legend(mut self, legend: Vec<LegendEntry>) -> Self {
+        self.set_legend(legend);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable showing every series registered with the chart's [`SeriesRegistry`] in the
+    /// legend, after any entries set with `set_legend`. Disabled by default.
+    pub fn set_auto_legend(&mut self, auto_legend: bool) {
+        self.auto_legend = auto_legend
+    }
+
+    #[inline]
+    /// Enable/disable showing the registered series in the legend. Consumes self.
+    pub fn auto_legend(mut self, auto_legend: bool) -> Self {
+        self.set_auto_legend(auto_legend);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable logging a warning, once per context, when the chart is drawn with
+    /// feathering enabled in egui's tessellation options, as it causes artifacts in most charts.
+    /// Enabled by default.
+    pub fn set_warn_feathering(&mut self, warn_feathering: bool) {
+        self.warn_feathering = warn_feathering
+    }
+
+    #[inline]
+    /// Enable/disable warning when feathering is enabled. Consumes self.
+    pub fn warn_feathering(mut self, warn_feathering: bool) -> Self {
+        self.set_warn_feathering(warn_feathering);
+
+        self
+    }
+
+    #[inline]
+    /// Get a handle to register series by name from inside the builder callback, to pick their
+    /// colors and z-order, list them in the legend when `auto_legend` is enabled and find out
+    /// which one is hovered. See [`SeriesRegistry`].
+    pub fn series_registry(&self) -> SeriesRegistry {
+        self.series.clone()
+    }
+
+    #[inline]
+    /// Set the z-order of a series registered with the chart's [`SeriesRegistry`]. The chart
+    /// paints series from the lowest z to the highest, whatever order the builder callback draws
+    /// them in, so a fill can be kept behind a line whichever is added first. What belongs to
+    /// each series is told by when its color is taken from the registry.
+    ///
+    /// ```
+    /// # use egui::{epaint::Shape, CentralPanel, Color32, Context};
+    /// # use egui_plotter::Chart;
+    /// # use plotters::prelude::*;
+    /// let mut chart = Chart::new(());
+    /// let registry = chart.series_registry();
+    ///
+    /// registry.set_color("Line", &BLUE);
+    /// registry.set_color("Fill", &RED);
+    /// chart.set_series_z("Fill", -1);
+    ///
+    /// // The fill is drawn after the line, over it
+    /// chart.set_builder_cb(Box::new(move |area, _transform, _data| {
+    ///     let line = registry.color("Line");
+    ///     area.draw(&PathElement::new([(0, 0), (100, 100)], line))
+    ///         .unwrap();
+    ///
+    ///     let fill = registry.color("Fill");
+    ///     area.draw(&Rectangle::new([(0, 0), (100, 100)], fill.filled()))
+    ///         .unwrap();
+    /// }));
+    ///
+    /// let output = Context::default().run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| chart.draw(ui));
+    /// });
+    ///
+    /// let order: Vec<_> = output
+    ///     .shapes
+    ///     .iter()
+    ///     .filter_map(|clipped| match &clipped.shape {
+    ///         Shape::Path(path) if path.stroke.color == Color32::BLUE => Some("Line"),
+    ///         Shape::Rect(rect) if rect.fill == Color32::RED => Some("Fill"),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    ///
+    /// // But the fill is painted underneath the line
+    /// assert_eq!(order, ["Fill", "Line"]);
+    /// ```
+    pub fn set_series_z(&mut self, label: &str, z: i32) {
+        self.series.set_z(label, z);
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the style of the legend, such as its rounding, shadow and position.
+    pub fn set_legend_style(&mut self, legend_style: LegendStyle) {
+        self.legend_style = legend_style
+    }
+
+    #[inline]
+    /// Set the style of the legend. Consumes self.
+    pub fn legend_style(mut self, legend_style: LegendStyle) -> Self {
+        self.set_legend_style(legend_style);
+
+        self
+    }
+
+    #[inline]
+    /// Add a line across the chart at the given value of an axis, such as a threshold. A value
+    /// on the X axis draws a vertical line, and one on the Y axis a horizontal line. Drawn over the
+    /// data using the mapping recorded with `mapping_recorder`, so it's only drawn if a mapping
+    /// was recorded, and pans and zooms with the chart.
+    pub fn add_reference_line(&mut self, axis: Axis, value: f64, style: impl Into<ShapeStyle>) {
+        self.references
+            .push(Reference::Line(axis, value, style.into()))
+    }
+
+    #[inline]
+    /// Add a filled band across the chart covering the given range of an axis. Drawn the same
+    /// way as reference lines, and clipped to the data range.
+    pub fn add_reference_band(
+        &mut self,
+        axis: Axis,
+        range: Range<f64>,
+        fill: impl Into<ShapeStyle>,
+    ) {
+        self.references
+            .push(Reference::Band(axis, range, fill.into()))
+    }
+
+    #[inline]
+    /// Add a dashed grid over the chart, such as a measurement grid, with lines spaced in data
+    /// units or in pixels independently of the chart's own mesh. Drawn across the plotting area
+    /// the same way as reference lines, so it's only drawn if a mapping was recorded.
+    pub fn add_overlay_grid(&mut self, spacing: GridSpacing, style: impl Into<ShapeStyle>) {
+        self.references.push(Reference::Grid(spacing, style.into()))
+    }
+
+    #[inline]
+    /// Remove all reference lines, bands and grids.
+    pub fn clear_references(&mut self) {
+        self.references.clear()
+    }
+
+    #[inline]
+    /// Set the context menu callback, called to fill the menu shown when the plotting area is
+    /// right-clicked. The data coordinates right-clicked are passed along if the builder callback
+    /// records its mapping with the [`MappingRecorder`] from `mapping_recorder`, which also
+    /// limits the menu to the plotting area rather than the whole chart.
+    ///
+    /// ```ignore
+    /// chart.set_context_menu(Box::new(|ui, chart, pos| {
+    ///     if ui.button("Reset view").clicked() {
+    ///         chart.set_scale(1.0);
+    ///         ui.close_menu();
+    ///     }
+    ///
+    ///     if let Some((x, y)) = pos {
+    ///         ui.label(format!("{x:.2}, {y:.2}"));
+    ///     }
+    /// }));
+    /// ```
+    pub fn set_context_menu(&mut self, context_menu: ContextMenuCallback<Data>) {
+        self.context_menu = Some(context_menu)
+    }
+
+    #[inline]
+    /// Set the context menu callback. Consumes self.
+    pub fn context_menu(mut self, context_menu: ContextMenuCallback<Data>) -> Self {
+        self.set_context_menu(context_menu);
+
+        self
+    }
+
+    #[inline]
+    /// Hand the chart's shapes to a custom renderer, such as a wgpu or glow shader, instead of
+    /// having egui tessellate and paint them. While set, the shapes drawn by the builder callback
+    /// are collected and passed to the given callback, which returns the renderer specific
+    /// callback, like an `egui_glow::CallbackFn`, that the chart paints as an egui
+    /// `PaintCallback` covering the chart. Overlays like the legend and selection are still
+    /// painted by egui.
+    ///
+    /// ```ignore
+    /// chart.set_paint_callback(Some(Box::new(|_rect, shapes| {
+    ///     let meshes = MyRenderer::prepare(shapes);
+    ///
+    ///     Arc::new(egui_glow::CallbackFn::new(move |info, painter| {
+    ///         MyRenderer::paint(&meshes, info, painter.gl());
+    ///     }))
+    /// })));
+    /// ```
+    pub fn set_paint_callback(&mut self, paint_callback: Option<PaintCallbackBuilder>) {
+        self.paint_callback = paint_callback
+    }
+
+    #[inline]
+    /// Hand the chart's shapes to a custom renderer. Consumes self.
+    pub fn paint_callback(mut self, paint_callback: PaintCallbackBuilder) -> Self {
+        self.set_paint_callback(Some(paint_callback));
+
+        self
+    }
+
+    #[inline]
+    /// Show an inset of the region around the pointer, zoomed in further, while the pointer is
+    /// over the chart and it isn't being dragged. The inset is drawn next to the pointer by
+    /// calling the builder callback again with the scale and offset adjusted, so it shows the
+    /// same chart in full detail rather than an enlarged image of it. Meant for 2d charts, as 3d
+    /// charts are zoomed around their center rather than the pointer. Pass None to hide it again.
+    ///
+    /// ```
+    /// # use egui::{CentralPanel, Context, Event, Pos2, RawInput, Rect, Vec2};
+    /// # use egui_plotter::{Chart, MagnifierConfig};
+    /// # use std::{cell::Cell, rc::Rc};
+    /// let ctx = Context::default();
+    /// let draws = Rc::new(Cell::new(0));
+    /// let counter = draws.clone();
+    ///
+    /// let mut chart = Chart::new(())
+    ///     .magnifier(MagnifierConfig::default())
+    ///     .builder_cb(Box::new(move |_area, _transform, _data| {
+    ///         counter.set(counter.get() + 1)
+    ///     }));
+    ///
+    /// let input = RawInput {
+    ///     screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(400.0, 300.0))),
+    ///     events: vec![Event::PointerMoved(Pos2::new(200.0, 150.0))],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let _ = ctx.run(input, |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| chart.draw(ui));
+    /// });
+    ///
+    /// // Drawn once for the chart and once for the inset
+    /// assert_eq!(draws.get(), 2);
+    /// ```
+    pub fn set_magnifier(&mut self, magnifier: Option<MagnifierConfig>) {
+        self.magnifier = magnifier
+    }
+
+    #[inline]
+    /// Show an inset of the region around the pointer, zoomed in further. Consumes self.
+    pub fn magnifier(mut self, magnifier: MagnifierConfig) -> Self {
+        self.set_magnifier(Some(magnifier));
+
+        self
+    }
+
+    #[inline]
+    /// Set the builder callback.
+    pub fn set_builder_cb(&mut self, builder_cb: BuilderCallback<Data>) {
+        self.builder_cb = Some(builder_cb);
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the builder callback. Consumes self.
+    pub fn builder_cb(mut self, builder_cb: BuilderCallback<Data>) -> Self {
+        self.set_builder_cb(builder_cb);
+
+        self
+    }
+
+    #[inline]
+    /// Set the pitch of the chart.
+    pub fn set_pitch(&mut self, pitch: f64) {
+        self.transform.pitch = pitch
+    }
+
+    #[inline]
+    /// Set the pitch of the chart. Consumes self.
+    pub fn pitch(mut self, pitch: f64) -> Self {
+        self.set_pitch(pitch);
+
+        self
+    }
+
+    #[inline]
+    /// Set the yaw of the chart.
+    pub fn set_yaw(&mut self, yaw: f64) {
+        self.transform.yaw = yaw
+    }
+
+    #[inline]
+    /// Set the yaw of the chart. Consumes self.
+    pub fn yaw(mut self, yaw: f64) -> Self {
+        self.set_yaw(yaw);
+
+        self
+    }
+
+    #[inline]
+    /// Set the scale of the chart.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.transform.scale = scale;
+        self.target_scale = None;
+    }
+
+    #[inline]
+    /// Set the scale of the chart. Consumes self.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.set_scale(scale);
+
+        self
+    }
+
+    #[inline]
+    /// Set the pan offset of the chart, in points.
+    pub fn set_offset(&mut self, offset: (i32, i32)) {
+        (self.transform.x, self.transform.y) = offset;
+    }
+
+    #[inline]
+    /// Set the pan offset of the chart, in points. Consumes self.
+    pub fn offset(mut self, offset: (i32, i32)) -> Self {
+        self.set_offset(offset);
+
+        self
+    }
+
+    #[inline]
+    /// Set the whole transform of the chart at once, including its pan offset. Whether the chart
+    /// is 3d is kept, see `set_3d`.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = Transform {
+            three_d: self.transform.three_d,
+            ..transform
+        };
+        self.target_scale = None;
+    }
+
+    #[inline]
+    /// Set the whole transform of the chart at once. Consumes self.
+    ///
+    /// ```
+    /// # use egui_plotter::{Chart, Transform};
+    /// let chart = Chart::new(()).transform(Transform {
+    ///     scale: 2.0,
+    ///     x: 40,
+    ///     y: -10,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(chart.get_transform().scale, 2.0);
+    /// assert_eq!((chart.get_transform().x, chart.get_transform().y), (40, -10));
+    /// ```
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.set_transform(transform);
+
+        self
+    }
+
+    #[inline]
+    /// Get the current transform of the chart.
+    pub fn get_transform(&self) -> &Transform {
+        &self.transform
+    }
+
+    #[inline]
+    /// Set the scale of 3d projections at a chart scale of 1. Defaults to [`DEFAULT_3D_SCALE`],
+    /// which keeps rotated charts clear of plotters' rendering artifacts.
+    pub fn set_projection_scale(&mut self, projection_scale: f64) {
+        self.transform.projection_scale = projection_scale
+    }
+
+    #[inline]
+    /// Set the scale of 3d projections at a chart scale of 1. Consumes self.
+    pub fn projection_scale(mut self, projection_scale: f64) -> Self {
+        self.set_projection_scale(projection_scale);
+
+        self
+    }
+
+    /// Reset the zoom to a scale of 1, keeping the pan and rotation.
+    pub fn reset_zoom(&mut self) {
+        self.transform.scale = Transform::default().scale;
+        self.target_scale = None;
+    }
+
+    /// Reset the pan, moving the chart back to its original position and keeping the zoom and
+    /// rotation.
+    pub fn reset_pan(&mut self) {
+        let Transform { x, y, .. } = Transform::default();
+
+        (self.transform.x, self.transform.y) = (x, y);
+    }
+
+    /// Reset the pitch and yaw of a 3d chart, keeping the pan and zoom.
+    pub fn reset_rotation(&mut self) {
+        let Transform { pitch, yaw, .. } = Transform::default();
+
+        (self.transform.pitch, self.transform.yaw) = (pitch, yaw);
+    }
+
+    /// Reset the zoom, pan and rotation of the chart.
+    pub fn reset_view(&mut self) {
+        self.reset_zoom();
+        self.reset_pan();
+        self.reset_rotation();
+    }
+
+    #[inline]
+    /// Get the data of the chart as a reference.
+    pub fn get_data(&self) -> &Data {
+        &self.data
     }
 
     #[inline]
     /// Get the data of the chart as a mutable reference.
     pub fn get_data_mut(&mut self) -> &mut Data {
+        self.mark_dirty();
+
         &mut self.data
     }
 
-    /// Call the callback and draw the chart to a UI element.
+    #[inline]
+    /// Mark the chart as changed, so the texture from `render_to_texture` and the minimap are
+    /// drawn again. Done automatically when the data is borrowed mutably, the builder callback is
+    /// replaced or the transform changes, so only needed if the callback draws from some other
+    /// source.
+    pub fn mark_dirty(&mut self) {
+        self.revision = self.revision.wrapping_add(1)
+    }
+
+    #[inline]
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.drawing_rect
+    }
+
+    #[inline]
+    /// Get the id of the series hovered during the last call to `draw`, if any. Look its name up
+    /// with `SeriesRegistry::name`.
+    pub fn hovered_series(&self) -> Option<usize> {
+        self.series.hovered()
+    }
+
+    #[inline]
+    /// Set how far, in points, the mouse can be from a series for it to be hovered. Defaults to
+    /// `DEFAULT_HOVER_RADIUS`.
+    pub fn set_hover_radius(&mut self, hover_radius: f32) {
+        self.hover_radius = hover_radius
+    }
+
+    #[inline]
+    /// Set how far the mouse can be from a series for it to be hovered. Consumes self.
+    pub fn hover_radius(mut self, hover_radius: f32) -> Self {
+        self.set_hover_radius(hover_radius);
+
+        self
+    }
+
+    #[inline]
+    /// Set the opacity of the chart, from 0.0 to 1.0, for fading it in and out. Multiplies the
+    /// alpha of everything the chart draws, and nothing is drawn at 0.0. Defaults to 1.0.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the opacity of the chart. Consumes self.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.set_opacity(opacity);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable a transparent background, so the chart composites over whatever is behind
+    /// it. Background fills covering the whole chart, like `root.fill(&WHITE)` in the builder
+    /// callback, are skipped, leaving only the series, grid and text. Disabled by default.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Enable/disable a transparent background. Consumes self.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.set_transparent(transparent);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable math text in the chart's labels, where `^{...}` raises text into a
+    /// superscript and `_{...}` lowers it into a subscript. See [`EguiBackend::set_math_text`].
+    /// Disabled by default.
+    pub fn set_math_text(&mut self, math_text: bool) {
+        self.math_text = math_text;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Enable/disable math text in the chart's labels. Consumes self.
+    pub fn math_text(mut self, math_text: bool) -> Self {
+        self.set_math_text(math_text);
+
+        self
+    }
+
+    #[inline]
+    /// Set the extra space added between the letters of the chart's labels, in points. See
+    /// [`EguiBackend::set_letter_spacing`]. Defaults to 0.
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32) {
+        self.letter_spacing = letter_spacing;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the extra space added between the letters of the chart's labels. Consumes self.
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.set_letter_spacing(letter_spacing);
+
+        self
+    }
+
+    #[inline]
+    /// Set the units the stroke widths of the chart are given in. With `StrokeUnits::Data` lines
+    /// thicken as the chart is zoomed in. See [`EguiBackend::set_stroke_units`]. Defaults to
+    /// `StrokeUnits::Pixels`.
+    pub fn set_stroke_units(&mut self, stroke_units: StrokeUnits) {
+        self.stroke_units = stroke_units;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the units the stroke widths of the chart are given in. Consumes self.
+    pub fn stroke_units(mut self, stroke_units: StrokeUnits) -> Self {
+        self.set_stroke_units(stroke_units);
+
+        self
+    }
+
+    #[inline]
+    /// Enable/disable drawing in the largest square centered in the available space, rather than
+    /// stretching to fill it. With equal X and Y ranges this gives both axes the same pixels per
+    /// unit, so circles stay round. Disabled by default.
+    pub fn set_square(&mut self, square: bool) {
+        self.square = square;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Enable/disable drawing in the largest centered square. Consumes self.
+    pub fn square(mut self, square: bool) -> Self {
+        self.set_square(square);
+
+        self
+    }
+
+    #[inline]
+    /// Set the smallest size, in points, the chart is drawn at. Below it `draw` skips the builder
+    /// callback and outlines the space instead, as plotters can panic when its label areas and
+    /// margins don't fit. Defaults to zero, while the premade charts set a minimum of their own.
+    pub fn set_min_size(&mut self, min_size: Vec2) {
+        self.min_size = min_size;
+        self.mark_dirty();
+    }
+
+    #[inline]
+    /// Set the smallest size, in points, the chart is drawn at. Consumes self.
+    pub fn min_size(mut self, min_size: Vec2) -> Self {
+        self.set_min_size(min_size);
+
+        self
+    }
+
+    #[inline]
+    /// Trace the chart's series out over the given duration the first time the chart is shown,
+    /// or show them whole if None, which is the default. The builder callback is given the
+    /// fraction revealed so far in `Transform::reveal`, and only draws that much of each series
+    /// if it passes them through `Transform::revealed`. Setting this restarts the animation.
+    pub fn set_animate_draw(&mut self, duration: Option<Duration>) {
+        self.animate_draw = duration;
+        self.draw_elapsed = None;
+        self.transform.reveal = 1.0;
+    }
+
+    #[inline]
+    /// Trace the chart's series out over the given duration the first time the chart is shown.
+    /// Consumes self.
+    pub fn animate_draw(mut self, duration: Duration) -> Self {
+        self.set_animate_draw(Some(duration));
+
+        self
+    }
+
+    /// Return the rect to draw in within the given rect, the largest square centered in it if
+    /// `square` is set.
+    fn square_rect(&self, rect: Rect) -> Rect {
+        match self.square {
+            true => {
+                Rect::from_center_size(rect.center(), Vec2::splat(rect.width().min(rect.height())))
+            }
+            false => rect,
+        }
+    }
+
+    #[inline]
+    /// Get a handle to read the labels of plotters series into the chart's legend from inside the
+    /// builder callback. See [`LegendRecorder`].
+    ///
+    /// ```
+    /// # use egui::{CentralPanel, Color32, Context};
+    /// # use egui_plotter::Chart;
+    /// # use plotters::prelude::*;
+    /// let mut chart = Chart::new(());
+    /// let recorder = chart.legend_recorder();
+    ///
+    /// chart.set_builder_cb(Box::new(move |area, _transform, _data| {
+    ///     let mut chart = ChartBuilder::on(area)
+    ///         .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+    ///         .unwrap();
+    ///
+    ///     chart
+    ///         .draw_series(LineSeries::new([(0.0, 0.0), (10.0, 10.0)], &RED))
+    ///         .unwrap()
+    ///         .label("Rising")
+    ///         .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], RED));
+    ///
+    ///     recorder.record(&mut chart).unwrap();
+    /// }));
+    ///
+    /// let _ = Context::default().run(Default::default(), |ctx| {
+    ///     CentralPanel::default().show(ctx, |ui| chart.draw(ui));
+    /// });
+    ///
+    /// let entries = chart.legend_recorder().entries();
+    ///
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].label, "Rising");
+    /// assert_eq!(entries[0].color, Color32::from_rgb(255, 0, 0));
+    /// ```
+    pub fn legend_recorder(&self) -> LegendRecorder {
+        self.legend_recorder.clone()
+    }
+
+    #[inline]
+    /// Get a handle to record the chart's coordinate mapping with from inside the builder
+    /// callback. Needed for `screen_to_data` and `visible_range`. See [`MappingRecorder`].
+    pub fn mapping_recorder(&self) -> MappingRecorder {
+        self.mapping.clone()
+    }
+
+    #[inline]
+    /// Get the coordinate mapping last recorded by the builder callback.
+    pub fn mapping(&self) -> Option<CoordMapping> {
+        self.mapping.get()
+    }
+
+    /// Convert a position on screen into data coordinates, taking the current offset and scale
+    /// into account. Returns None if the chart hasn't been drawn or no mapping was recorded.
+    pub fn screen_to_data(&self, pos: Pos2) -> Option<(f64, f64)> {
+        let bounds = self.drawing_rect?;
+        let mapping = self.mapping.get()?;
+
+        let scale = self
+            .mouse
+            .screen_scale(self.transform.scale, self.transform.three_d);
+
+        Some(mapping.pixel_to_data(self.transform.untransform(pos, bounds, scale)))
+    }
+
+    /// Convert data coordinates to a position on screen, after panning and zooming. The inverse
+    /// of `screen_to_data`. Returns None if the chart hasn't been drawn or no mapping was
+    /// recorded.
+    pub fn data_to_screen(&self, data: (f64, f64)) -> Option<Pos2> {
+        let bounds = self.drawing_rect?;
+        let mapping = self.mapping.get()?;
+
+        let scale = self
+            .mouse
+            .screen_scale(self.transform.scale, self.transform.three_d);
+
+        Some(
+            self.transform
+                .transform(mapping.data_to_pixel(data), bounds, scale),
+        )
+    }
+
+    /// Get the X and Y ranges of the data currently visible in the chart's drawing rect, after
+    /// panning and zooming. Returns None if the chart hasn't been drawn or no mapping was
+    /// recorded.
+    pub fn visible_range(&self) -> Option<(Range<f64>, Range<f64>)> {
+        let bounds = self.drawing_rect?;
+
+        let (x_start, y_start) = self.screen_to_data(bounds.left_bottom())?;
+        let (x_end, y_end) = self.screen_to_data(bounds.right_top())?;
+
+        Some((x_start..x_end, y_start..y_end))
+    }
+
+    /// Get the rect the plotting area was drawn in, in screen coordinates. Returns None if the
+    /// chart hasn't been drawn or no mapping was recorded.
+    fn plot_rect(&self) -> Option<Rect> {
+        let bounds = self.drawing_rect?;
+        let mapping = self.mapping.get()?;
+
+        let scale = self
+            .mouse
+            .screen_scale(self.transform.scale, self.transform.three_d);
+
+        let (x_range, y_range) = (mapping.x_range(), mapping.y_range());
+
+        let start = mapping.data_to_pixel((x_range.start, y_range.start));
+        let end = mapping.data_to_pixel((x_range.end, y_range.end));
+
+        Some(Rect::from_two_pos(
+            self.transform.transform(start, bounds, scale),
+            self.transform.transform(end, bounds, scale),
+        ))
+    }
+
+    /// Show the context menu if the plotting area was right-clicked.
+    fn handle_context_menu(&mut self, ui: &Ui, drawing_rect: Rect) {
+        let mut context_menu = match self.context_menu.take() {
+            Some(context_menu) => context_menu,
+            None => return,
+        };
+
+        let rect = self
+            .plot_rect()
+            .map_or(drawing_rect, |rect| rect.intersect(drawing_rect));
+
+        let response = ui.interact(rect, self.id.with("context_menu"), Sense::click());
+
+        if response.secondary_clicked() {
+            self.context_pos = response
+                .interact_pointer_pos()
+                .and_then(|pos| self.screen_to_data(pos));
+        }
+
+        let pos = self.context_pos;
+
+        response.context_menu(|ui| context_menu(ui, self, pos));
+
+        // Keep any callback set from within the menu
+        self.context_menu.get_or_insert(context_menu);
+    }
+
+    #[inline]
+    /// Request a screenshot of the chart. The screenshot is taken of the next frame the chart is
+    /// drawn in, so it contains the actual rendered pixels including the theme, and becomes
+    /// available through `take_screenshot` a frame or two later once the integration has
+    /// captured it. Requires an integration that supports `ViewportCommand::Screenshot`, such as
+    /// eframe with the wgpu or glow renderer.
+    ///
+    /// The screenshot is at the resolution of the window, as the integration captures what's on
+    /// screen rather than drawing the chart again. Use `render_to_image` to render the chart at a
+    /// higher scale instead.
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_requested = true
+    }
+
+    #[inline]
+    /// Take the last screenshot captured after a call to `request_screenshot`, cropped to the
+    /// chart's drawing rect. Returns None if no screenshot is ready.
+    pub fn take_screenshot(&mut self) -> Option<ColorImage> {
+        self.screenshot.take()
+    }
+
+    /// Crop any screenshot delivered to this viewport down to the rect the chart was drawn in,
+    /// and send a new screenshot request if one is pending.
+    fn handle_screenshot(&mut self, ui: &Ui, drawing_rect: Rect) {
+        let ctx = ui.ctx();
+
+        if let Some(rect) = self.screenshot_rect {
+            let viewport_id = ctx.viewport_id();
+            let pixels_per_point = ctx.pixels_per_point();
+
+            let image = ui.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    Event::Screenshot {
+                        viewport_id: id,
+                        image,
+                    } if *id == viewport_id => Some(image.clone()),
+                    _ => None,
+                })
+            });
+
+            if let Some(image) = image {
+                let [width, height] = image.size;
+                let image_rect = Rect::from_min_size(
+                    Pos2::ZERO,
+                    Vec2::new(width as f32, height as f32) / pixels_per_point,
+                );
+
+                let rect = rect.intersect(image_rect);
+
+                // A chart scrolled entirely off screen produces an empty screenshot
+                self.screenshot = match rect.is_positive() {
+                    true => Some(image.region(&rect, Some(pixels_per_point))),
+                    false => Some(ColorImage::new([0, 0], Default::default())),
+                };
+                self.screenshot_rect = None;
+            }
+        }
+
+        if self.screenshot_requested {
+            ctx.send_viewport_cmd(ViewportCommand::Screenshot);
+
+            self.screenshot_requested = false;
+            self.screenshot_rect = Some(drawing_rect);
+        }
+    }
+
+    /// Call the callback and draw the chart to a UI element. If the space is smaller than the
+    /// minimum size, see `set_min_size`, a placeholder outline is drawn instead.
     pub fn draw(&mut self, ui: &Ui) {
+        let rect = self.square_rect(ui.max_rect());
+
+        if rect.width() < self.min_size.x || rect.height() < self.min_size.y {
+            ui.painter()
+                .rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+            self.drawing_rect = None;
+
+            return;
+        }
+
+        // Take the callback out while drawing so it can be borrowed alongside the chart
+        let mut builder_cb = self.builder_cb.take();
+
+        self.draw_with(ui, |area, transform, data| {
+            if let Some(cb) = &mut builder_cb {
+                cb(area, transform, data);
+            }
+        });
+
+        self.builder_cb = builder_cb;
+
+        self.draw_magnifier(ui);
+    }
+
+    /// Draw the magnifier's inset next to the pointer if it's over the chart, by calling the
+    /// builder callback again zoomed in around the point under the pointer.
+    fn draw_magnifier(&mut self, ui: &Ui) {
+        let (Some(magnifier), Some(drawing_rect)) = (self.magnifier, self.drawing_rect) else {
+            return;
+        };
+
+        let pointer = ui
+            .input(|input| input.pointer.hover_pos())
+            .filter(|pos| drawing_rect.contains(*pos));
+
+        let Some(pointer) = pointer.filter(|_| !self.interacting) else {
+            return;
+        };
+
+        // Keep the inset inside the chart, flipping it to the other side of the pointer
+        let size = magnifier.size;
+        let mut min = pointer + Vec2::splat(MAGNIFIER_OFFSET);
+
+        if min.x + size.x > drawing_rect.max.x {
+            min.x = pointer.x - MAGNIFIER_OFFSET - size.x;
+        }
+
+        if min.y + size.y > drawing_rect.max.y {
+            min.y = pointer.y - MAGNIFIER_OFFSET - size.y;
+        }
+
+        let rect = Rect::from_min_size(min, size);
+        let inset_ui = Ui::new(
+            ui.ctx().clone(),
+            ui.layer_id(),
+            self.id.with("magnifier"),
+            rect,
+            rect.intersect(ui.clip_rect()),
+        );
+
+        let zoom = magnifier.zoom.abs();
+        let scale = self
+            .mouse
+            .screen_scale(self.transform.scale, self.transform.three_d);
+        let inset_scale = self
+            .mouse
+            .screen_scale(self.transform.scale * zoom as f64, self.transform.three_d);
+
+        // Offset the inset so the point under the pointer lands in its center. The backend is
+        // given the chart's size, so it's laid out the same and only the scale and offset differ
+        let center = drawing_rect.size() / 2.0;
+        let offset = Vec2::new(self.transform.x as f32, self.transform.y as f32);
+        let from_center = (pointer - drawing_rect.min - center - offset) / scale * inset_scale;
+        let inset_offset = (size / 2.0 - center - from_center).round();
+
+        let transform = Transform {
+            scale: self.transform.scale * zoom as f64,
+            x: inset_offset.x as i32,
+            y: inset_offset.y as i32,
+            ..self.transform
+        };
+
+        let painter = ui.painter().with_clip_rect(rect);
+
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+        // Keep the mapping of the main chart, as the inset records its own while drawing
+        let main_mapping = self.mapping.get();
+
+        let mut backend = EguiBackend::new(&inset_ui)
+            .size(drawing_rect.size())
+            .opacity(self.opacity)
+            .transparent(self.transparent)
+            .math_text(self.math_text)
+            .letter_spacing(self.letter_spacing)
+            .stroke_units(self.stroke_units)
+            .legend_recorder(Some(self.legend_recorder.clone()))
+            .series_registry(Some(self.series.clone()));
+
+        backend.set_offset((transform.x, transform.y));
+        backend.set_axis_scale(inset_scale.x, inset_scale.y);
+
+        let mut area = backend.into_drawing_area();
+
+        self.series.end_series();
+
+        if let Some(cb) = &mut self.builder_cb {
+            cb(&mut area, &transform, &self.data);
+        }
+
+        self.series.end_series();
+
+        self.draw_references(&area);
+
+        area.present().unwrap();
+
+        self.mapping.set(main_mapping);
+
+        painter.rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+    }
+
+    #[inline]
+    /// Wrap the chart in an egui widget, to add it with `ui.add` like any other widget. See
+    /// [`ChartWidget`].
+    pub fn widget(&mut self) -> ChartWidget<'_, Data> {
+        ChartWidget {
+            chart: self,
+            size: None,
+        }
+    }
+
+    /// Draw the chart to a UI element using the given callback instead of the builder callback,
+    /// returning whatever the callback returns. Useful for getting values computed while drawing,
+    /// such as the hovered point, back out of the callback. Mouse input is handled the same as in
+    /// `draw`. The callback is always called, so the minimum size isn't checked.
+    pub fn draw_with<T, F>(&mut self, ui: &Ui, cb: F) -> T
+    where
+        F: FnOnce(&mut DrawingArea<EguiBackend, Shift>, &Transform, &Data) -> T,
+    {
+        let shapes = RefCell::new(Vec::new());
+
+        if self.warn_feathering {
+            warn_feathering(ui.ctx());
+        }
+
+        let square_rect = self.square_rect(ui.max_rect());
+        let square_ui = Ui::new(
+            ui.ctx().clone(),
+            ui.layer_id(),
+            ui.id().with("square"),
+            square_rect,
+            ui.clip_rect(),
+        );
+
+        let mut backend = EguiBackend::new(&square_ui)
+            .size(square_rect.size())
+            .opacity(self.opacity)
+            .transparent(self.transparent)
+            .math_text(self.math_text)
+            .letter_spacing(self.letter_spacing)
+            .stroke_units(self.stroke_units)
+            .legend_recorder(Some(self.legend_recorder.clone()))
+            .series_registry(Some(self.series.clone()));
+        let drawing_rect = backend.drawing_rect();
+
+        if self.paint_callback.is_some() {
+            backend.set_capture(Some(&shapes));
+        }
+
         let transform = &mut self.transform;
+        let mut selection = None;
+
+        if let Some(sync) = &self.sync {
+            sync.sync(transform, &mut self.last_sync);
+        }
 
         // First, get mouse data
         ui.input(|input| {
             let pointer = &input.pointer;
             let delta = pointer.delta();
 
-            // Adjust the pitch/yaw if the primary button is pressed and rotation is enabled
-            if self.mouse.rotate && self.mouse.rotate_bind.is_down(pointer) {
+            // Charts that are linked only respond while hovered, so input isn't applied once per
+            // chart in the group
+            let interactive = self.sync.is_none()
+                || pointer
+                    .hover_pos()
+                    .is_some_and(|pos| drawing_rect.contains(pos));
+
+            let (drag, rotate) = match interactive {
+                true => self.mouse.active_actions(input),
+                false => (false, false),
+            };
+
+            let (scroll_pan, scroll_zoom) = match interactive {
+                true => self.mouse.scroll_actions(input),
+                false => (false, false),
+            };
+
+            let scrolling = (scroll_pan || scroll_zoom) && input.scroll_delta != Vec2::ZERO;
+            let pinching = self.mouse.zoom && interactive && input.zoom_delta() != 1.0;
+
+            self.interacting =
+                drag || rotate || scrolling || pinching || self.box_zoom_start.is_some();
+
+            // Adjust the pitch/yaw if the rotate bind is pressed and rotation is enabled
+            if rotate {
                 let pitch_delta = delta.y * self.mouse.pitch_scale;
                 let yaw_delta = delta.x * self.mouse.yaw_scale;
 
@@ -343,8 +2274,26 @@ impl<Data> Chart<Data> {
                 transform.yaw += -yaw_delta as f64;
             }
 
-            // Adjust the x/y if the middle button is down and dragging is enabled
-            if self.mouse.drag && self.mouse.drag_bind.is_down(pointer) {
+            // Adjust the x/y if the drag bind is down and dragging is enabled, or when scrolling pans
+            if drag || scroll_pan {
+                let delta = match (drag, scroll_pan) {
+                    (true, true) => delta + input.scroll_delta,
+                    (true, false) => delta,
+                    (false, _) => input.scroll_delta,
+                };
+
+                // Locked axes don't pan
+                let delta = Vec2::new(
+                    match self.mouse.lock_x {
+                        true => 0.0,
+                        false => delta.x,
+                    },
+                    match self.mouse.lock_y {
+                        true => 0.0,
+                        false => delta.y,
+                    },
+                );
+
                 let x_delta = delta.x;
                 let y_delta = delta.y;
 
@@ -353,23 +2302,507 @@ impl<Data> Chart<Data> {
             }
 
             // Adjust zoom if zoom is enabled
-            if self.mouse.zoom {
+            if scroll_zoom && input.scroll_delta.y != 0.0 {
                 let scale_delta = input.scroll_delta.y * self.mouse.zoom_scale;
 
                 // !TODO! make scaling exponential
-                transform.scale = (transform.scale + scale_delta as f64).abs();
+                match self.mouse.smooth_zoom {
+                    true => {
+                        let target = self.target_scale.unwrap_or(transform.scale);
+
+                        self.target_scale = Some((target + scale_delta as f64).abs());
+                    }
+                    false => transform.scale = (transform.scale + scale_delta as f64).abs(),
+                }
             }
+
+            // Integrations turn Ctrl+scroll into zoom events rather than scrolling
+            if self.mouse.zoom
+                && interactive
+                && self.mouse.scroll_action == ScrollAction::PanWithZoomModifier
+            {
+                transform.scale *= input.zoom_delta() as f64;
+            }
+
+            // Track the box being dragged, zooming into it once the bind is released
+            if self.mouse.box_zoom {
+                match (
+                    self.mouse.box_zoom_bind.is_down(pointer),
+                    self.box_zoom_start,
+                ) {
+                    (true, None) => {
+                        self.box_zoom_start = pointer
+                            .interact_pos()
+                            .filter(|pos| drawing_rect.contains(*pos))
+                    }
+                    (true, Some(start)) => {
+                        selection = pointer
+                            .interact_pos()
+                            .map(|end| Rect::from_two_pos(start, end))
+                    }
+                    (false, Some(start)) => {
+                        if let Some(end) = pointer.interact_pos() {
+                            transform.fit_selection(
+                                Rect::from_two_pos(start, end),
+                                drawing_rect,
+                                (self.mouse.lock_x, self.mouse.lock_y),
+                            );
+                            self.target_scale = None;
+                        }
+
+                        self.box_zoom_start = None;
+                    }
+                    (false, None) => {}
+                }
+            }
+        });
+
+        // Step the animations by the time since the last frame
+        self.advance(ui.input(|input| input.stable_dt));
+
+        if self.is_animating() {
+            ui.ctx().request_repaint();
+        }
+
+        let transform = &mut self.transform;
+
+        if let Some(sync) = &self.sync {
+            sync.sync(transform, &mut self.last_sync);
+        }
+
+        backend.set_offset((transform.x, transform.y));
+        let scale = self.mouse.screen_scale(transform.scale, transform.three_d);
+
+        backend.set_axis_scale(scale.x, scale.y);
+
+        // Nudge the chart so a major gridline on each axis, and so every other one the scale
+        // keeps a whole number of pixels apart, lands on a whole pixel
+        if let (true, false, Some(mapping)) = (
+            self.mouse.snap_to_grid,
+            transform.three_d,
+            self.mapping.get(),
+        ) {
+            let pixels_per_point = ui.ctx().pixels_per_point();
+            let gridline = transform.transform(mapping.major_gridline(), drawing_rect, scale)
+                * pixels_per_point;
+            let nudge = (gridline.round() - gridline) / pixels_per_point;
+
+            backend.set_subpixel_offset((nudge.x, nudge.y));
+        }
+
+        self.drawing_rect = Some(drawing_rect);
+
+        // Find the hovered series using the mapping and series from the last frame
+        let pointer = ui
+            .input(|input| input.pointer.hover_pos())
+            .filter(|pos| drawing_rect.contains(*pos));
+
+        let to_screen = self.mapping.get().map(|mapping| {
+            let transform = *transform;
+            let scale = self.mouse.screen_scale(transform.scale, transform.three_d);
+
+            move |data| transform.transform(mapping.data_to_pixel(data), drawing_rect, scale)
         });
 
-        let mut area = EguiBackend::new(ui)
-            .offset((transform.x, transform.y))
-            .scale(transform.scale as f32)
-            .into_drawing_area();
+        self.series.update(pointer, self.hover_radius, to_screen);
+
+        let mut area = backend.into_drawing_area();
+
+        // Labels are recorded again on each draw, so series that are gone drop out of the legend
+        self.legend_recorder.clear();
+
+        self.series.end_series();
+
+        let result = cb(&mut area, transform, &self.data);
+
+        self.series.end_series();
+
+        self.draw_references(&area);
+
+        area.present().unwrap();
+
+        drop(area);
+
+        if let Some(paint_callback) = &mut self.paint_callback {
+            let callback = paint_callback(drawing_rect, shapes.into_inner());
+
+            ui.painter()
+                .with_clip_rect(drawing_rect)
+                .add(PaintCallback {
+                    rect: drawing_rect,
+                    callback,
+                });
+        }
+
+        let mut legend = self.legend.clone();
+
+        legend.extend(self.legend_recorder.entries());
+
+        if self.auto_legend {
+            legend.extend(self.series.legend_entries());
+        }
+
+        draw_legend(ui, drawing_rect, &legend, &self.legend_style, self.opacity);
+
+        if let Some(selection) = selection {
+            let painter = ui.painter().with_clip_rect(drawing_rect);
+
+            painter.extend(selection_shapes(ui, selection));
+        }
+
+        self.handle_screenshot(ui, drawing_rect);
+        self.handle_context_menu(ui, drawing_rect);
+
+        if self.interacting || self.is_animating() {
+            self.mark_active();
+        }
+
+        result
+    }
+
+    /// Draw the reference lines and bands over the chart, if a mapping was recorded.
+    fn draw_references(&self, area: &DrawingArea<EguiBackend, Shift>) {
+        if let Some(mapping) = self.mapping.get() {
+            for reference in &self.references {
+                reference.draw(area, &mapping);
+            }
+        }
+    }
+
+    /// Render the chart offscreen into a texture of the given size in points, which can then be
+    /// drawn any number of times as an egui `Image`, tinted, rotated and so on. The chart is only
+    /// rendered again when it's dirty: when the size, scale or transform changed or see
+    /// `mark_dirty`. Otherwise the same texture is returned.
+    ///
+    /// Rendering is done on the CPU, so it's slower than `draw` and best kept to charts that
+    /// don't change every frame. The legend isn't included and mouse input isn't handled. Must
+    /// be called during a frame, after `Context::run` has started.
+    ///
+    /// ```ignore
+    /// let texture = chart.render_to_texture(ui.ctx(), Vec2::new(400.0, 300.0));
+    ///
+    /// ui.add(Image::new((texture.id(), Vec2::new(400.0, 300.0))).tint(Color32::LIGHT_BLUE));
+    /// ```
+    pub fn render_to_texture(&mut self, ctx: &Context, size: Vec2) -> TextureHandle {
+        let pixels_per_point = ctx.pixels_per_point();
+        let pixels = (size * pixels_per_point).round();
+        let pixels = [pixels.x.max(0.0) as usize, pixels.y.max(0.0) as usize];
+
+        let state = Some((self.transform, pixels, self.revision));
+
+        if let Some(texture) = &self.texture {
+            if self.texture_state == state {
+                return texture.clone();
+            }
+        }
+
+        let image = self.render_image(ctx, size, 1.0, pixels);
+
+        let texture = match self.texture.take() {
+            Some(mut texture) => {
+                texture.set(image, TextureOptions::LINEAR);
+
+                texture
+            }
+            None => ctx.load_texture("egui_plotter_chart", image, TextureOptions::LINEAR),
+        };
+
+        self.texture = Some(texture.clone());
+        self.texture_state = state;
+
+        texture
+    }
+
+    /// Render the chart offscreen into an image of the given size in points, at `scale` times the
+    /// resolution of the screen, such as 2 or 3 for figures to be printed. The chart is drawn
+    /// again at the higher resolution, so text and lines stay crisp rather than being upscaled,
+    /// while the layout is the same as at a scale of 1. The image is `size * scale` times the
+    /// context's pixels per point pixels across.
+    ///
+    /// Like `render_to_texture` the legend isn't included, and it must be called during a frame.
+    ///
+    /// ```
+    /// # use egui::{Context, Vec2};
+    /// # use egui_plotter::Chart;
+    /// let mut chart = Chart::new(());
+    ///
+    /// let ctx = Context::default();
+    ///
+    /// let _ = ctx.run(Default::default(), |ctx| {
+    ///     let image = chart.render_to_image(ctx, Vec2::new(400.0, 300.0), 2.0);
+    ///
+    ///     assert_eq!(image.size, [800, 600]);
+    /// });
+    /// ```
+    pub fn render_to_image(&mut self, ctx: &Context, size: Vec2, scale: f32) -> ColorImage {
+        let pixels = (size * scale * ctx.pixels_per_point()).round();
+        let pixels = [pixels.x.max(0.0) as usize, pixels.y.max(0.0) as usize];
+
+        self.render_image(ctx, size, scale, pixels)
+    }
+
+    /// Render the chart into an image of the given number of pixels, laid out at the given size
+    /// in points and enlarged by `scale`.
+    fn render_image(
+        &mut self,
+        ctx: &Context,
+        size: Vec2,
+        scale: f32,
+        pixels: [usize; 2],
+    ) -> ColorImage {
+        let rect = Rect::from_min_size(Pos2::ZERO, size);
+        let square_rect = self.square_rect(rect);
+        // The backend enlarges the drawing from the corner of its rect, so move the corner to
+        // where it ends up in the enlarged image
+        let ui = Ui::new(
+            ctx.clone(),
+            LayerId::background(),
+            Id::new("egui_plotter_texture"),
+            Rect::from_min_size(square_rect.min * scale, square_rect.size()),
+            Rect::from_min_size(Pos2::ZERO, size * scale),
+        );
+
+        let shapes = RefCell::new(Vec::new());
+        let texture_scale = self
+            .mouse
+            .screen_scale(self.transform.scale, self.transform.three_d);
+
+        let mut backend = EguiBackend::new(&ui)
+            .offset((self.transform.x, self.transform.y))
+            .axis_scale(texture_scale.x, texture_scale.y);
+
+        backend.set_capture(Some(&shapes));
+        backend.set_opacity(self.opacity);
+        backend.set_transparent(self.transparent);
+        backend.set_math_text(self.math_text);
+        backend.set_letter_spacing(self.letter_spacing);
+        backend.set_stroke_units(self.stroke_units);
+        backend.set_legend_recorder(Some(self.legend_recorder.clone()));
+        backend.set_series_registry(Some(self.series.clone()));
+        backend.set_output_scale(scale);
+
+        let mut area = backend.into_drawing_area();
+
+        self.series.end_series();
 
         if let Some(cb) = &mut self.builder_cb {
-            cb(&mut area, transform, &self.data);
+            cb(&mut area, &self.transform, &self.data);
         }
 
+        self.series.end_series();
+
+        self.draw_references(&area);
+
         area.present().unwrap();
+
+        drop(area);
+
+        render_shapes(ctx, shapes.into_inner(), pixels)
+    }
+
+    /// Draw an overview of the whole chart, without any panning or zooming, into the given rect
+    /// of the ui. The region visible in the main chart is outlined, and clicking or dragging in
+    /// the minimap centers the main chart on that point.
+    ///
+    /// Call after `draw`, every frame. The builder callback is called again to draw the minimap,
+    /// with lines simplified to the minimap's resolution, and it must record its mapping with
+    /// the [`MappingRecorder`] from `mapping_recorder` for the visible region to be shown and
+    /// navigated. The overview is kept between frames and only drawn again once the chart
+    /// changes, see `mark_dirty`, or the minimap is resized.
+    pub fn draw_minimap(&mut self, ui: &mut Ui, rect: Rect) {
+        let response = ui.interact(rect, self.id.with("minimap"), Sense::click_and_drag());
+
+        let transform = Transform {
+            scale: 1.0,
+            x: 0,
+            y: 0,
+            reveal: 1.0,
+            ..self.transform
+        };
+
+        let key = (rect.size(), self.revision, transform);
+
+        if self.minimap.as_ref().map(|minimap| minimap.key) != Some(key) {
+            let minimap_ui = ui.child_ui(rect, *ui.layout());
+            let shapes = RefCell::new(Vec::new());
+
+            // Draw the overview, keeping the mapping of the main chart
+            let main_mapping = self.mapping.get();
+
+            let mut backend = EguiBackend::new(&minimap_ui)
+                .transparent(self.transparent)
+                .math_text(self.math_text)
+                .letter_spacing(self.letter_spacing)
+                .stroke_units(self.stroke_units)
+                .legend_recorder(Some(self.legend_recorder.clone()))
+                .series_registry(Some(self.series.clone()))
+                .path_simplify(Some(MINIMAP_SIMPLIFY));
+
+            backend.set_capture(Some(&shapes));
+
+            let mut area = backend.into_drawing_area();
+
+            self.series.end_series();
+
+            if let Some(cb) = &mut self.builder_cb {
+                cb(&mut area, &transform, &self.data);
+            }
+
+            self.series.end_series();
+
+            area.present().unwrap();
+
+            drop(area);
+
+            self.minimap = Some(Minimap {
+                key,
+                origin: rect.min,
+                shapes: shapes.into_inner(),
+                mapping: self.mapping.get(),
+            });
+
+            self.mapping.set(main_mapping);
+        }
+
+        let Some(minimap) = &self.minimap else {
+            return;
+        };
+
+        // The minimap may have moved since it was drawn
+        let delta = rect.min - minimap.origin;
+
+        for ClippedShape { clip_rect, shape } in &minimap.shapes {
+            let mut shape = shape.clone();
+            shape.translate(delta);
+
+            ui.painter()
+                .with_clip_rect(clip_rect.translate(delta))
+                .add(shape);
+        }
+
+        let main_mapping = self.mapping.get();
+        let minimap_mapping = minimap.mapping.clone();
+
+        let painter = ui.painter().with_clip_rect(rect);
+
+        painter.rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+        let (Some(main_mapping), Some(minimap_mapping), Some(bounds)) =
+            (main_mapping, minimap_mapping, self.drawing_rect)
+        else {
+            return;
+        };
+
+        let to_minimap = |data: (f64, f64)| {
+            let (x, y) = minimap_mapping.data_to_pixel(data);
+
+            rect.min + Vec2::new(x as f32, y as f32)
+        };
+
+        // Center the main chart on the point under the pointer
+        if let Some(pos) = response.interact_pointer_pos() {
+            let pixel = pos - rect.min;
+            let data = minimap_mapping.pixel_to_data((pixel.x as f64, pixel.y as f64));
+
+            let (x, y) = main_mapping.data_to_pixel(data);
+
+            let scale = self
+                .mouse
+                .screen_scale(self.transform.scale, self.transform.three_d);
+
+            let offset = -(Vec2::new(x as f32, y as f32) - bounds.size() / 2.0) * scale;
+
+            if !self.mouse.lock_x {
+                self.transform.x = offset.x.round() as i32;
+            }
+
+            if !self.mouse.lock_y {
+                self.transform.y = offset.y.round() as i32;
+            }
+        }
+
+        if let Some((x_range, y_range)) = self.visible_range() {
+            let viewport = Rect::from_two_pos(
+                to_minimap((x_range.start, y_range.start)),
+                to_minimap((x_range.end, y_range.end)),
+            );
+
+            let visuals = &ui.visuals().selection;
+
+            painter.rect_filled(viewport, 0.0, visuals.bg_fill.gamma_multiply(0.25));
+            painter.rect_stroke(viewport, 0.0, visuals.stroke);
+        }
+    }
+}
+
+/// Overview drawn by `Chart::draw_minimap`, kept between frames until the chart changes.
+struct Minimap {
+    /// Size of the minimap, and revision and transform of the chart it was drawn with.
+    key: (Vec2, u64, Transform),
+    /// Corner of the minimap the shapes were drawn at.
+    origin: Pos2,
+    shapes: Vec<ClippedShape>,
+    mapping: Option<CoordMapping>,
+}
+
+/// Widget drawing a chart in space allocated from the ui's layout, so it can be placed inside
+/// grids, scroll areas and other layouts like any other widget. Get one with `Chart::widget`.
+///
+/// The widget takes the size set with `desired_size`, or all the space available otherwise, and
+/// senses clicks and drags so they aren't passed on to a surrounding scroll area. The chart
+/// handles mouse input itself as it does with `draw`.
+///
+/// ```ignore
+/// let response = ui.add(self.chart.widget().desired_size(Vec2::new(400.0, 300.0)));
+///
+/// if response.double_clicked() {
+///     self.chart.reset_view();
+/// }
+/// ```
+pub struct ChartWidget<'a, Data> {
+    chart: &'a mut Chart<Data>,
+    size: Option<Vec2>,
+}
+
+impl<Data> ChartWidget<'_, Data> {
+    #[inline]
+    /// Set the size to allocate for the chart, in points, instead of all the space available.
+    /// Consumes self.
+    pub fn desired_size(mut self, size: Vec2) -> Self {
+        self.size = Some(size);
+
+        self
+    }
+}
+
+impl<Data> Widget for ChartWidget<'_, Data> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let size = self.size.unwrap_or_else(|| ui.available_size_before_wrap());
+        let size = Vec2::new(
+            match size.x.is_finite() {
+                true => size.x,
+                false => DEFAULT_WIDGET_SIZE,
+            },
+            match size.y.is_finite() {
+                true => size.y,
+                false => DEFAULT_WIDGET_SIZE,
+            },
+        );
+
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+
+        if ui.is_rect_visible(rect) {
+            let chart_ui = Ui::new(
+                ui.ctx().clone(),
+                ui.layer_id(),
+                response.id.with("chart"),
+                rect,
+                ui.clip_rect().intersect(rect),
+            );
+
+            self.chart.draw(&chart_ui);
+        }
+
+        response
     }
 }
@@ -0,0 +1,354 @@
+//! Column chart with a categorical X axis.
+
+use std::sync::Arc;
+
+use egui::{Rect, Sense, Ui};
+use plotters::{
+    coord::Shift,
+    prelude::{
+        ChartBuilder, DrawingArea, DrawingAreaErrorKind, DrawingBackend, Histogram,
+        IntoSegmentedCoord, SegmentValue,
+    },
+    style::{
+        full_palette::{BLUE_700, GREY},
+        Color, FontDesc, FontFamily, FontStyle, RGBAColor, ShapeStyle, TextStyle, BLACK,
+    },
+};
+
+use crate::{Chart, CoordMapping, MouseConfig};
+
+use super::MIN_CHART_SIZE;
+
+const X_MARGIN: i32 = 25;
+const Y_MARGIN: i32 = 25;
+const LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+// Space between columns, in pixels
+const COLUMN_MARGIN: u32 = 4;
+// Fraction of the value range added above and below the columns
+const VALUE_HEADROOM: f64 = 0.05;
+const DEFAULT_MAX_LABEL_LEN: usize = 16;
+
+/// Callback called with the index of the column that was clicked.
+type BarClickCallback = Box<dyn FnMut(usize)>;
+
+#[derive(Clone)]
+struct CategoryConfig {
+    /// Labels of the columns, in order.
+    labels: Arc<[Arc<str>]>,
+    /// Values of the columns, in order.
+    values: Arc<[f64]>,
+    /// Style of the columns.
+    bar_style: ShapeStyle,
+    /// Style of the grid lines.
+    grid_style: ShapeStyle,
+    /// Color of the text.
+    text_color: RGBAColor,
+    /// Unit of the values.
+    unit: Arc<str>,
+    /// Caption of the chart.
+    caption: Arc<str>,
+    /// Labels longer than this many characters are truncated.
+    max_label_len: usize,
+}
+
+impl CategoryConfig {
+    /// Draw the chart to any plotters drawing area, returning the mapping between its pixels and
+    /// values, with column `i` spanning `i..i + 1` on the X axis.
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<CoordMapping, DrawingAreaErrorKind<DB::ErrorType>> {
+        let font_desc = FontDesc::new(
+            FontFamily::Monospace,
+            CAPTION_SIZE as f64,
+            FontStyle::Normal,
+        );
+        let text_style = TextStyle::from(font_desc).color(&self.text_color);
+
+        let columns = self.labels.len().max(1);
+
+        let plot_area = area
+            .margin(Y_MARGIN, Y_MARGIN, X_MARGIN, X_MARGIN)
+            .titled(&self.caption, text_style.clone())?;
+
+        // Shorten the labels further if they wouldn't fit under their column
+        let (width, _) = plot_area.dim_in_pixel();
+        let column_width = width.saturating_sub(LABEL_AREA as u32) / columns as u32;
+        let (char_width, _) = plot_area.estimate_text_size("0", &text_style)?;
+        let fitting_len = (column_width / char_width.max(1)) as usize;
+
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .map(|label| truncate_label(label, self.max_label_len.min(fitting_len)))
+            .collect();
+
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((0.0f64, 0.0f64), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+
+        let headroom = match max - min > 0.0 {
+            true => (max - min) * VALUE_HEADROOM,
+            false => 1.0,
+        };
+
+        let y_range = match min < 0.0 {
+            true => min - headroom..max + headroom,
+            false => 0.0..max + headroom,
+        };
+
+        // Segmented coordinates can't be mapped back from pixels, so map the same area linearly
+        let mapping = CoordMapping::from_chart(
+            &ChartBuilder::on(&plot_area)
+                .x_label_area_size(LABEL_AREA)
+                .y_label_area_size(LABEL_AREA)
+                .build_cartesian_2d(0.0..columns as f64, y_range.clone())?,
+        );
+
+        let mut chart = ChartBuilder::on(&plot_area)
+            .x_label_area_size(LABEL_AREA)
+            .y_label_area_size(LABEL_AREA)
+            .build_cartesian_2d((0..columns as i32 - 1).into_segmented(), y_range)?;
+
+        // Label each column by position, so duplicate names still get their own column
+        let label_formatter = |value: &SegmentValue<i32>| match value {
+            SegmentValue::CenterOf(index) => {
+                labels.get(*index as usize).cloned().unwrap_or_default()
+            }
+            _ => String::new(),
+        };
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(columns)
+            .x_label_formatter(&label_formatter)
+            .label_style(text_style.clone())
+            .bold_line_style(self.grid_style)
+            .light_line_style(self.grid_style.color.mix(0.3))
+            .y_desc(self.unit.to_string())
+            .draw()?;
+
+        chart.draw_series(
+            Histogram::vertical(&chart)
+                .style(self.bar_style)
+                .margin(COLUMN_MARGIN)
+                .data(
+                    self.values
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, value)| value.is_finite())
+                        .map(|(index, value)| (index as i32, *value)),
+                ),
+        )?;
+
+        Ok(mapping)
+    }
+
+    /// Return the index of the column at the given data coordinates, if any.
+    fn column_at(&self, pos: (f64, f64)) -> Option<usize> {
+        let (x, y) = pos;
+
+        if x < 0.0 {
+            return None;
+        }
+
+        let index = x as usize;
+        let value = *self.values.get(index)?;
+
+        (value.min(0.0)..=value.max(0.0))
+            .contains(&y)
+            .then_some(index)
+    }
+}
+
+/// Shorten a label to at most `max_len` characters, ending it with an ellipsis if it was cut.
+fn truncate_label(label: &str, max_len: usize) -> String {
+    match label.chars().count() > max_len {
+        true => label
+            .chars()
+            .take(max_len.saturating_sub(1))
+            .chain(['…'])
+            .collect(),
+        false => label.to_string(),
+    }
+}
+
+/// Column chart with a categorical X axis.
+///
+/// ## Usage
+///
+/// Create a new chart with `CategoryData::new()`. Arguments are as follows:
+///  * `columns`: A slice of tuples, where the first element is the label of the column and the
+///    second is its value. Columns are shown in order, and duplicate labels get their own column.
+///  * `unit`: String describing the values.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// Long labels are truncated, see `set_max_label_len`, and shortened further if they don't fit
+/// under their column. Like the other charts, it can be panned and zoomed with the mouse.
+///
+/// ```
+/// # use egui_plotter::charts::CategoryData;
+/// let chart = CategoryData::new(&[("Apples", 3.0), ("Pears", 5.0), ("Plums", 1.5)], "kg", "Fruit");
+/// ```
+pub struct CategoryData {
+    chart: Chart<CategoryConfig>,
+    on_bar_click: Option<BarClickCallback>,
+}
+
+impl CategoryData {
+    /// Create a new CategoryData chart. See [Usage](#usage).
+    pub fn new(columns: &[(&str, f64)], unit: &str, caption: &str) -> Self {
+        let labels: Arc<[Arc<str>]> = columns.iter().map(|(label, _)| (*label).into()).collect();
+        let values: Arc<[f64]> = columns.iter().map(|(_, value)| *value).collect();
+
+        let bar_style = ShapeStyle {
+            color: BLUE_700.to_rgba(),
+            filled: true,
+            stroke_width: 1,
+        };
+
+        let grid_style = ShapeStyle {
+            color: GREY.to_rgba(),
+            filled: false,
+            stroke_width: 1,
+        };
+
+        let config = CategoryConfig {
+            labels,
+            values,
+            bar_style,
+            grid_style,
+            text_color: BLACK.to_rgba(),
+            unit: unit.into(),
+            caption: caption.into(),
+            max_label_len: DEFAULT_MAX_LABEL_LEN,
+        };
+
+        let chart = Chart::new(config)
+            .mouse(MouseConfig::enabled())
+            .min_size(MIN_CHART_SIZE);
+        let recorder = chart.mapping_recorder();
+
+        let chart = chart.builder_cb(Box::new(move |area, _t, data| {
+            recorder.set(Some(data.draw(area).unwrap()));
+        }));
+
+        Self {
+            chart,
+            on_bar_click: None,
+        }
+    }
+
+    #[inline]
+    /// Set the style of the columns.
+    pub fn set_bar_style(&mut self, bar_style: ShapeStyle) {
+        self.chart.get_data_mut().bar_style = bar_style
+    }
+
+    #[inline]
+    /// Set the style of the columns. Consumes self.
+    pub fn bar_style(mut self, bar_style: ShapeStyle) -> Self {
+        self.set_bar_style(bar_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the grid.
+    pub fn set_grid_style(&mut self, grid_style: ShapeStyle) {
+        self.chart.get_data_mut().grid_style = grid_style
+    }
+
+    #[inline]
+    /// Set the style of the grid. Consumes self.
+    pub fn grid_style(mut self, grid_style: ShapeStyle) -> Self {
+        self.set_grid_style(grid_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the number of characters labels are truncated to, with an ellipsis marking the cut.
+    /// Defaults to 16.
+    pub fn set_max_label_len(&mut self, max_label_len: usize) {
+        self.chart.get_data_mut().max_label_len = max_label_len
+    }
+
+    #[inline]
+    /// Set the number of characters labels are truncated to. Consumes self.
+    pub fn max_label_len(mut self, max_label_len: usize) -> Self {
+        self.set_max_label_len(max_label_len);
+
+        self
+    }
+
+    #[inline]
+    /// Set a callback called with the index of a column when it's clicked, for drilling down into
+    /// a category. Pass None to remove it.
+    pub fn set_on_bar_click(&mut self, on_bar_click: Option<BarClickCallback>) {
+        self.on_bar_click = on_bar_click
+    }
+
+    #[inline]
+    /// Set a callback called with the index of a column when it's clicked. Consumes self.
+    pub fn on_bar_click(mut self, on_bar_click: BarClickCallback) -> Self {
+        self.set_on_bar_click(Some(on_bar_click));
+
+        self
+    }
+
+    /// Draw the chart to a Ui, calling the bar click callback if a column was clicked.
+    pub fn draw(&mut self, ui: &Ui) {
+        self.chart.draw(ui);
+
+        let (Some(on_bar_click), Some(rect)) = (&mut self.on_bar_click, self.chart.drawing_rect())
+        else {
+            return;
+        };
+
+        let response = ui.interact(rect, self.chart.id().with("bar_click"), Sense::click());
+
+        let column = response
+            .clicked()
+            .then(|| response.interact_pointer_pos())
+            .flatten()
+            .and_then(|pos| self.chart.screen_to_data(pos))
+            .and_then(|pos| self.chart.get_data().column_at(pos));
+
+        if let Some(index) = column {
+            on_bar_click(index);
+        }
+    }
+
+    #[inline]
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.chart.drawing_rect()
+    }
+}
@@ -0,0 +1,223 @@
+//! Playback clock used to animate timecharts, which can be shared between charts.
+
+use std::sync::{Mutex, PoisonError};
+
+use instant::Instant;
+
+#[derive(Debug, Clone, Copy)]
+/// Tracks how much playback time has elapsed, whether it's paused, and how fast it runs. Time
+/// only moves forward through `advance`, which `tick` calls with the wall-clock time between
+/// frames, so playback can also be stepped deterministically.
+pub(crate) struct Clock {
+    /// Kept as f64 so small steps still add up over long playback.
+    elapsed: f64,
+    started: bool,
+    paused: bool,
+    last_tick: Option<Instant>,
+    playback_speed: f32,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            elapsed: 0.0,
+            started: false,
+            paused: false,
+            last_tick: None,
+            playback_speed: 1.0,
+        }
+    }
+}
+
+impl Clock {
+    /// Set the elapsed playback time, in seconds, keeping the clock paused if it's paused or
+    /// stopped.
+    pub(crate) fn set_time(&mut self, time: f64) {
+        if !self.started {
+            self.started = true;
+            self.paused = true;
+        }
+
+        self.elapsed = time;
+    }
+
+    /// Set the playback speed. A speed of 0.0 pauses playback instead, keeping the previous speed
+    /// for when it's resumed, and negative or non-finite speeds are ignored.
+    pub(crate) fn set_playback_speed(&mut self, speed: f32) {
+        if speed == 0.0 {
+            if self.started {
+                self.paused = true;
+                self.last_tick = None;
+            }
+
+            return;
+        }
+
+        if speed.is_finite() && speed > 0.0 {
+            self.playback_speed = speed;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn playback_speed(&self) -> f32 {
+        self.playback_speed
+    }
+
+    #[inline]
+    pub(crate) fn start_playback(&mut self) {
+        self.elapsed = 0.0;
+        self.started = true;
+        self.paused = false;
+        self.last_tick = None;
+    }
+
+    #[inline]
+    /// Start playback if it isn't started, and pause it.
+    pub(crate) fn pause(&mut self) {
+        self.started = true;
+        self.paused = true;
+        self.last_tick = None;
+    }
+
+    #[inline]
+    pub(crate) fn stop_playback(&mut self) {
+        self.started = false;
+        self.paused = false;
+        self.last_tick = None;
+    }
+
+    pub(crate) fn toggle_playback(&mut self) {
+        match self.started {
+            true => {
+                self.paused = !self.paused;
+                self.last_tick = None;
+            }
+            false => self.start_playback(),
+        }
+    }
+
+    #[inline]
+    /// Return true if playback has been started, even if it's paused.
+    pub(crate) fn is_started(&self) -> bool {
+        self.started
+    }
+
+    #[inline]
+    pub(crate) fn is_playing(&self) -> bool {
+        self.started && !self.paused
+    }
+
+    /// Step playback forward by the given number of seconds, scaled by the playback speed. Does
+    /// nothing unless playing.
+    pub(crate) fn advance(&mut self, dt: f64) {
+        if self.is_playing() {
+            self.elapsed += self.playback_speed as f64 * dt;
+        }
+    }
+
+    /// Advance by the wall-clock time since the last tick. Ticking more than once a frame, as
+    /// charts sharing a clock do, only advances it once.
+    pub(crate) fn tick(&mut self) {
+        if !self.is_playing() {
+            self.last_tick = None;
+
+            return;
+        }
+
+        let now = Instant::now();
+
+        if let Some(last_tick) = self.last_tick {
+            self.advance(now.duration_since(last_tick).as_secs_f64());
+        }
+
+        self.last_tick = Some(now);
+    }
+
+    /// Return the playback time elapsed, in seconds and scaled by the playback speed, or None if
+    /// playback hasn't been started.
+    pub(crate) fn elapsed(&self) -> Option<f64> {
+        self.started.then_some(self.elapsed)
+    }
+}
+
+#[derive(Debug, Default)]
+/// Playback clock that can be shared between timecharts, so they advance in lockstep and can all
+/// be played, paused and seeked from one control. Give the same clock to each chart with
+/// `set_clock`; the charts' own playback functions then control the shared clock.
+///
+/// ```ignore
+/// let clock = Arc::new(SharedClock::new());
+///
+/// let cpu = TimeData::new(&cpu_points, "%", "CPU").clock(Some(clock.clone()));
+/// let memory = TimeData::new(&memory_points, "MiB", "Memory").clock(Some(clock.clone()));
+///
+/// clock.toggle_playback();
+/// ```
+///
+/// Each chart's time is its start time plus the time elapsed on the clock. Charts that reach their
+/// end stay on their last frame rather than stopping the clock.
+pub struct SharedClock(Mutex<Clock>);
+
+impl SharedClock {
+    #[inline]
+    /// Create a new, stopped clock.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run a function on the inner clock. A clock poisoned by a panic is still usable, as it's
+    /// only a couple of timestamps.
+    pub(crate) fn with<T>(&self, f: impl FnOnce(&mut Clock) -> T) -> T {
+        f(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    #[inline]
+    /// Set the time elapsed on the clock to resume playback at. Time is in seconds.
+    pub fn set_time(&self, time: f32) {
+        self.with(|clock| clock.set_time(time as f64))
+    }
+
+    #[inline]
+    /// Step playback forward by the given number of seconds, as if that much time had passed.
+    /// Useful for driving charts deterministically, such as in tests.
+    pub fn advance(&self, dt: f32) {
+        self.with(|clock| clock.advance(dt as f64))
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Speeds must be
+    /// positive: 0.0 pauses playback, and negative or non-finite speeds are ignored.
+    pub fn set_playback_speed(&self, speed: f32) {
+        self.with(|clock| clock.set_playback_speed(speed))
+    }
+
+    #[inline]
+    /// Return the speed the clock runs at.
+    pub fn get_playback_speed(&self) -> f32 {
+        self.with(|clock| clock.playback_speed())
+    }
+
+    #[inline]
+    /// Start/enable playback.
+    pub fn start_playback(&self) {
+        self.with(|clock| clock.start_playback())
+    }
+
+    #[inline]
+    /// Stop/disable playback.
+    pub fn stop_playback(&self) {
+        self.with(|clock| clock.stop_playback())
+    }
+
+    #[inline]
+    /// Toggle playback.
+    pub fn toggle_playback(&self) {
+        self.with(|clock| clock.toggle_playback())
+    }
+
+    #[inline]
+    /// Return true if playback is currently enabled & underway.
+    pub fn is_playing(&self) -> bool {
+        self.with(|clock| clock.is_playing())
+    }
+}
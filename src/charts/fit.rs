@@ -0,0 +1,71 @@
+//! Helpers for fitting chart ranges to data
+
+use std::ops::Range;
+
+/// Fraction of the value used to pad a zero width range, or the absolute padding if the value is
+/// zero.
+const ZERO_SPAN_PAD: f64 = 0.1;
+
+/// Compute X and Y ranges that fit all of the given points, suitable for `build_cartesian_2d`.
+///
+/// `margin` is the fraction of each range's span added to both of its ends, so a margin of `0.05`
+/// pads each side by 5%. Non-finite points are ignored. If there are no points `0..1` is returned
+/// for both axes, and ranges that would have no width (such as when there is only one point) are
+/// widened so they can still be drawn.
+///
+/// ```
+/// # use egui_plotter::charts::fit_ranges;
+/// let (x_range, y_range) = fit_ranges([(0.0, 1.0), (10.0, 3.0)], 0.1);
+///
+/// assert_eq!(x_range, -1.0..11.0);
+/// assert!((y_range.start - 0.8).abs() < 1e-9 && (y_range.end - 3.2).abs() < 1e-9);
+///
+/// let (x_range, _) = fit_ranges([(5.0, 5.0)], 0.1);
+///
+/// assert!(x_range.start < 5.0 && x_range.end > 5.0);
+/// ```
+pub fn fit_ranges<I>(points: I, margin: f64) -> (Range<f64>, Range<f64>)
+where
+    I: IntoIterator<Item = (f64, f64)>,
+{
+    let mut min_x = f64::INFINITY;
+    let mut min_y = f64::INFINITY;
+    let mut max_x = f64::NEG_INFINITY;
+    let mut max_y = f64::NEG_INFINITY;
+
+    for (x, y) in points {
+        if !(x.is_finite() && y.is_finite()) {
+            continue;
+        }
+
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    // No finite points were given
+    if min_x > max_x {
+        return (0.0..1.0, 0.0..1.0);
+    }
+
+    (
+        pad_range(min_x..max_x, margin),
+        pad_range(min_y..max_y, margin),
+    )
+}
+
+/// Pad a range by a fraction of its span, widening it if it has no width. Plotters divides by a
+/// range's width when mapping it to pixels, so a range fitted to identical points would otherwise
+/// produce NaN coordinates.
+pub(crate) fn pad_range(range: Range<f64>, margin: f64) -> Range<f64> {
+    let pad = match range.start == range.end {
+        true => match range.start == 0.0 {
+            true => ZERO_SPAN_PAD,
+            false => range.start.abs() * ZERO_SPAN_PAD,
+        },
+        false => (range.end - range.start) * margin,
+    };
+
+    (range.start - pad)..(range.end + pad)
+}
@@ -1,11 +1,32 @@
 //! Various type of premade charts.
 
+use egui::Vec2;
+
+mod category;
+#[cfg(feature = "timechart")]
+mod clock;
+mod fit;
+mod polar;
+#[cfg(feature = "export")]
+mod scaled;
+#[cfg(feature = "timechart")]
+mod stacked;
 #[cfg(feature = "timechart")]
 mod timedata;
 #[cfg(feature = "timechart")]
 mod xytime;
 
+pub use category::CategoryData;
+#[cfg(feature = "timechart")]
+pub use clock::SharedClock;
+pub use fit::fit_ranges;
+pub use polar::PolarChart;
+#[cfg(feature = "timechart")]
+pub use stacked::StackedAreaTimeData;
 #[cfg(feature = "timechart")]
 pub use timedata::TimeData;
 #[cfg(feature = "timechart")]
-pub use xytime::XyTimeData;
+pub use xytime::{AutoScale, Collision, LineInterp, PlaybackKeys, Smoothing, XyTimeData};
+
+/// Smallest size the premade charts are drawn at, leaving room for their margins and labels.
+const MIN_CHART_SIZE: Vec2 = Vec2::splat(100.0);
@@ -0,0 +1,335 @@
+//! Line chart in polar coordinates.
+
+use std::{f32::consts::TAU, sync::Arc};
+
+use egui::{Rect, Ui};
+use plotters::{
+    coord::Shift,
+    prelude::{ChartBuilder, DrawingArea, DrawingAreaErrorKind, DrawingBackend, PathElement, Text},
+    style::{
+        full_palette::{BLUE_700, GREY},
+        text_anchor::{HPos, Pos, VPos},
+        Color, FontDesc, FontFamily, FontStyle, RGBAColor, ShapeStyle, TextStyle, BLACK,
+    },
+};
+
+use crate::{elements::split_non_finite, Chart, CoordMapping, MouseConfig};
+
+use super::MIN_CHART_SIZE;
+
+const MARGIN: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+const DEFAULT_RINGS: usize = 4;
+const DEFAULT_SPOKES: usize = 12;
+// Number of segments each ring is drawn with
+const RING_SEGMENTS: usize = 128;
+// Fraction of the outer ring's radius added around it, leaving room for the angle labels
+const RADIUS_HEADROOM: f32 = 0.15;
+// Distance of the angle labels from the center, relative to the outer ring
+const ANGLE_LABEL_RADIUS: f32 = 1.08;
+
+#[derive(Clone)]
+struct PolarConfig {
+    /// Points of the line, as `(angle, radius)` with the angle in radians.
+    points: Arc<[(f32, f32)]>,
+    /// Style of the line.
+    line_style: ShapeStyle,
+    /// Style of the rings and spokes.
+    grid_style: ShapeStyle,
+    /// Color of the text.
+    text_color: RGBAColor,
+    /// Number of labeled rings.
+    rings: usize,
+    /// Number of labeled spokes.
+    spokes: usize,
+    /// Whether the last point is joined back to the first.
+    closed: bool,
+    /// Unit of the radii.
+    unit: Arc<str>,
+    /// Caption of the chart.
+    caption: Arc<str>,
+}
+
+impl PolarConfig {
+    /// Draw the chart to any plotters drawing area, returning the mapping between its pixels and
+    /// cartesian coordinates centered on the pole.
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<CoordMapping, DrawingAreaErrorKind<DB::ErrorType>> {
+        let font_desc = FontDesc::new(
+            FontFamily::Monospace,
+            CAPTION_SIZE as f64,
+            FontStyle::Normal,
+        );
+        let text_style = TextStyle::from(font_desc).color(&self.text_color);
+
+        let plot_area = area
+            .margin(MARGIN, MARGIN, MARGIN, MARGIN)
+            .titled(&self.caption, text_style.clone())?;
+
+        let max_radius = self
+            .points
+            .iter()
+            .map(|(_, radius)| radius.abs())
+            .filter(|radius| radius.is_finite())
+            .fold(0.0f32, f32::max);
+
+        let max_radius = match max_radius > 0.0 {
+            true => max_radius,
+            false => 1.0,
+        };
+
+        // Stretch the longer axis so rings stay round whatever the shape of the area
+        let (width, height) = plot_area.dim_in_pixel();
+        let aspect = width.max(1) as f32 / height.max(1) as f32;
+        let extent = max_radius * (1.0 + RADIUS_HEADROOM);
+
+        let mut chart = ChartBuilder::on(&plot_area).build_cartesian_2d(
+            -extent * aspect.max(1.0)..extent * aspect.max(1.0),
+            -extent / aspect.min(1.0)..extent / aspect.min(1.0),
+        )?;
+
+        let mapping = CoordMapping::from_chart(&chart);
+        let rings = self.rings.max(1);
+
+        for ring in 1..=rings {
+            let radius = max_radius * ring as f32 / rings as f32;
+
+            chart.draw_series([PathElement::new(
+                (0..=RING_SEGMENTS)
+                    .map(|segment| {
+                        to_cartesian(segment as f32 / RING_SEGMENTS as f32 * TAU, radius)
+                    })
+                    .collect::<Vec<_>>(),
+                self.grid_style,
+            )])?;
+
+            chart.draw_series([Text::new(
+                format!("{}{}", format_radius(radius), self.unit),
+                (radius, 0.0),
+                text_style.pos(Pos::new(HPos::Left, VPos::Bottom)),
+            )])?;
+        }
+
+        for spoke in 0..self.spokes {
+            let angle = spoke as f32 / self.spokes as f32 * TAU;
+
+            chart.draw_series([PathElement::new(
+                vec![(0.0, 0.0), to_cartesian(angle, max_radius)],
+                self.grid_style,
+            )])?;
+
+            chart.draw_series([Text::new(
+                format!("{}°", angle.to_degrees().round()),
+                to_cartesian(angle, max_radius * ANGLE_LABEL_RADIUS),
+                text_style.pos(Pos::new(HPos::Center, VPos::Center)),
+            )])?;
+        }
+
+        let closing = match self.closed {
+            true => self.points.first().copied(),
+            false => None,
+        };
+
+        let runs = split_non_finite(self.points.iter().copied().chain(closing), true);
+
+        chart.draw_series(runs.into_iter().map(|run| {
+            PathElement::new(
+                run.into_iter()
+                    .map(|(angle, radius)| to_cartesian(angle, radius))
+                    .collect::<Vec<_>>(),
+                self.line_style,
+            )
+        }))?;
+
+        Ok(mapping)
+    }
+}
+
+/// Convert an angle in radians, counterclockwise from the positive X axis, and a radius into
+/// cartesian coordinates. Angles outside of `0..TAU` wrap around.
+fn to_cartesian(angle: f32, radius: f32) -> (f32, f32) {
+    let angle = angle.rem_euclid(TAU);
+
+    (radius * angle.cos(), radius * angle.sin())
+}
+
+/// Format a ring's radius with at most two decimals, dropping trailing zeros.
+fn format_radius(radius: f32) -> String {
+    format!("{:.2}", radius)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Line chart in polar coordinates, with labeled rings and spokes.
+///
+/// ## Usage
+///
+/// Create a new chart with `PolarChart::new()`. Arguments are as follows:
+///  * `points`: A slice of `(angle, radius)` tuples, with the angle in radians counterclockwise
+///    from the positive X axis. Angles outside of `0..TAU` wrap around, so a line may cross from
+///    just under a full turn to just over zero.
+///  * `unit`: String appended to the ring labels.
+///  * `caption`: String to be shown as the caption of the chart.
+///
+/// The outer ring is placed at the largest radius in the data. Non-finite points break the line.
+/// Like the other charts, it can be panned and zoomed with the mouse.
+///
+/// ```
+/// # use egui_plotter::charts::PolarChart;
+/// # use std::f32::consts::PI;
+/// let points: Vec<(f32, f32)> = (0..36)
+///     .map(|i| (i as f32 * PI / 18.0, 1.0 + (i as f32 * PI / 6.0).sin()))
+///     .collect();
+///
+/// let chart = PolarChart::new(&points, " m", "Antenna gain").closed(true);
+/// ```
+pub struct PolarChart {
+    chart: Chart<PolarConfig>,
+}
+
+impl PolarChart {
+    /// Create a new PolarChart. See [Usage](#usage).
+    pub fn new(points: &[(f32, f32)], unit: &str, caption: &str) -> Self {
+        let line_style = ShapeStyle {
+            color: BLUE_700.to_rgba(),
+            filled: false,
+            stroke_width: 2,
+        };
+
+        let grid_style = ShapeStyle {
+            color: GREY.to_rgba(),
+            filled: false,
+            stroke_width: 1,
+        };
+
+        let config = PolarConfig {
+            points: points.into(),
+            line_style,
+            grid_style,
+            text_color: BLACK.to_rgba(),
+            rings: DEFAULT_RINGS,
+            spokes: DEFAULT_SPOKES,
+            closed: false,
+            unit: unit.into(),
+            caption: caption.into(),
+        };
+
+        let chart = Chart::new(config)
+            .mouse(MouseConfig::enabled())
+            .min_size(MIN_CHART_SIZE);
+        let recorder = chart.mapping_recorder();
+
+        let chart = chart.builder_cb(Box::new(move |area, _t, data| {
+            recorder.set(Some(data.draw(area).unwrap()));
+        }));
+
+        Self { chart }
+    }
+
+    #[inline]
+    /// Set the style of the line.
+    pub fn set_line_style(&mut self, line_style: ShapeStyle) {
+        self.chart.get_data_mut().line_style = line_style
+    }
+
+    #[inline]
+    /// Set the style of the line. Consumes self.
+    pub fn line_style(mut self, line_style: ShapeStyle) -> Self {
+        self.set_line_style(line_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the rings and spokes.
+    pub fn set_grid_style(&mut self, grid_style: ShapeStyle) {
+        self.chart.get_data_mut().grid_style = grid_style
+    }
+
+    #[inline]
+    /// Set the style of the rings and spokes. Consumes self.
+    pub fn grid_style(mut self, grid_style: ShapeStyle) -> Self {
+        self.set_grid_style(grid_style);
+
+        self
+    }
+
+    /// Set the text color of the chart.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the text color of the chart. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    #[inline]
+    /// Set the number of evenly spaced rings, each labeled with its radius. Defaults to 4.
+    pub fn set_rings(&mut self, rings: usize) {
+        self.chart.get_data_mut().rings = rings
+    }
+
+    #[inline]
+    /// Set the number of evenly spaced rings. Consumes self.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.set_rings(rings);
+
+        self
+    }
+
+    #[inline]
+    /// Set the number of evenly spaced spokes, each labeled with its angle in degrees. Defaults
+    /// to 12, one every 30°.
+    pub fn set_spokes(&mut self, spokes: usize) {
+        self.chart.get_data_mut().spokes = spokes
+    }
+
+    #[inline]
+    /// Set the number of evenly spaced spokes. Consumes self.
+    pub fn spokes(mut self, spokes: usize) -> Self {
+        self.set_spokes(spokes);
+
+        self
+    }
+
+    #[inline]
+    /// Set whether the last point is joined back to the first, closing the line into a loop.
+    /// Defaults to false.
+    pub fn set_closed(&mut self, closed: bool) {
+        self.chart.get_data_mut().closed = closed
+    }
+
+    #[inline]
+    /// Set whether the last point is joined back to the first. Consumes self.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.set_closed(closed);
+
+        self
+    }
+
+    #[inline]
+    /// Draw the chart to a Ui.
+    pub fn draw(&mut self, ui: &Ui) {
+        self.chart.draw(ui)
+    }
+
+    #[inline]
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.chart.drawing_rect()
+    }
+}
@@ -0,0 +1,193 @@
+//! Drawing backend wrapper that enlarges everything drawn through it, used to export charts at
+//! a higher resolution than they're laid out at.
+
+use plotters::prelude::*;
+use plotters_backend::{
+    BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingErrorKind,
+};
+
+/// Lays charts out at the size of the wrapped backend divided by `scale`, then enlarges
+/// coordinates, stroke widths, radii and font sizes by `scale` as they're drawn, so the result
+/// looks the same as the chart drawn at its logical size, only sharper.
+pub(crate) struct ScaledBackend<DB> {
+    inner: DB,
+    scale: f64,
+}
+
+impl<DB: DrawingBackend> ScaledBackend<DB> {
+    /// Wrap the given backend, enlarging everything by the given scale.
+    pub(crate) fn new(inner: DB, scale: f64) -> Self {
+        Self { inner, scale }
+    }
+
+    fn coord(&self, (x, y): BackendCoord) -> BackendCoord {
+        (
+            (x as f64 * self.scale).round() as i32,
+            (y as f64 * self.scale).round() as i32,
+        )
+    }
+
+    fn length(&self, length: u32) -> u32 {
+        (length as f64 * self.scale).round().max(1.0) as u32
+    }
+
+    /// Return the given style with its stroke width enlarged by the scale.
+    fn style<S: BackendStyle>(&self, style: &S) -> ScaledStyle {
+        ScaledStyle {
+            color: style.color(),
+            stroke_width: self.length(style.stroke_width()),
+        }
+    }
+
+    /// Return the given text style with its font enlarged by the scale.
+    fn text_style<'a, TStyle: BackendTextStyle>(&self, style: &'a TStyle) -> TextStyle<'a> {
+        let font = FontDesc::new(style.family(), style.size() * self.scale, style.style())
+            .transform(style.transform());
+
+        TextStyle {
+            font,
+            color: style.color(),
+            pos: style.anchor(),
+        }
+    }
+}
+
+/// Style with its stroke width enlarged by the scale.
+struct ScaledStyle {
+    color: BackendColor,
+    stroke_width: u32,
+}
+
+impl BackendStyle for ScaledStyle {
+    fn color(&self) -> BackendColor {
+        self.color
+    }
+
+    fn stroke_width(&self) -> u32 {
+        self.stroke_width
+    }
+}
+
+impl<DB: DrawingBackend> DrawingBackend for ScaledBackend<DB> {
+    type ErrorType = DB::ErrorType;
+
+    fn get_size(&self) -> (u32, u32) {
+        let (width, height) = self.inner.get_size();
+
+        (
+            (width as f64 / self.scale).round() as u32,
+            (height as f64 / self.scale).round() as u32,
+        )
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.inner.ensure_prepared()
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.inner.present()
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        // A pixel covers the square up to where the next one starts
+        let from = self.coord(point);
+        let to = self.coord((point.0 + 1, point.1 + 1));
+
+        self.inner
+            .draw_rect(from, (to.0 - 1, to.1 - 1), &color, true)
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.style(style);
+
+        self.inner
+            .draw_line(self.coord(from), self.coord(to), &style)
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.style(style);
+
+        self.inner.draw_rect(
+            self.coord(upper_left),
+            self.coord(bottom_right),
+            &style,
+            fill,
+        )
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.style(style);
+        let path: Vec<BackendCoord> = path.into_iter().map(|point| self.coord(point)).collect();
+
+        self.inner.draw_path(path, &style)
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.style(style);
+
+        self.inner
+            .draw_circle(self.coord(center), self.length(radius), &style, fill)
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.style(style);
+        let vert: Vec<BackendCoord> = vert.into_iter().map(|point| self.coord(point)).collect();
+
+        self.inner.fill_polygon(vert, &style)
+    }
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let style = self.text_style(style);
+
+        self.inner.draw_text(text, &style, self.coord(pos))
+    }
+
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let (width, height) = self
+            .inner
+            .estimate_text_size(text, &self.text_style(style))?;
+
+        Ok((
+            (width as f64 / self.scale).round() as u32,
+            (height as f64 / self.scale).round() as u32,
+        ))
+    }
+}
@@ -0,0 +1,478 @@
+//! Animatable stacked area chart, for values that add up to a total over time.
+
+use std::{cmp::Ordering, sync::Arc};
+
+use egui::{Rect, Ui};
+use plotters::{
+    coord::Shift,
+    prelude::{ChartBuilder, DrawingArea, DrawingAreaErrorKind, DrawingBackend, Polygon},
+    style::{
+        full_palette::GREY, Color, FontDesc, FontFamily, FontStyle, Palette, Palette99, RGBAColor,
+        ShapeStyle, TextStyle, BLACK,
+    },
+};
+
+use crate::{AnimationHandle, Chart, LegendEntry, MouseConfig};
+
+use super::{clock::Clock, MIN_CHART_SIZE};
+
+const MIN_DELTA: f32 = 0.000_010;
+const X_MARGIN: i32 = 25;
+const Y_MARGIN: i32 = 25;
+const LABEL_AREA: i32 = 25;
+const CAPTION_SIZE: i32 = 10;
+// Fraction of the span of the stacks added above the highest and below the lowest
+const VALUE_HEADROOM: f32 = 0.05;
+
+#[derive(Clone)]
+struct StackedAreaConfig {
+    /// Times of the samples.
+    times: Arc<[f32]>,
+    /// Values of each series at each sample time, bottom series first.
+    values: Arc<[Arc<[f32]>]>,
+    /// Number of samples shown.
+    shown: usize,
+    /// Time the chart is shown at.
+    time: f32,
+    /// Lowest and highest edge of the stacks shown, which the Y axis is fit to.
+    extent: (f32, f32),
+    /// Style of the area of each series.
+    fill_styles: Vec<ShapeStyle>,
+    /// Style of the grid lines.
+    grid_style: ShapeStyle,
+    /// Color of the text.
+    text_color: RGBAColor,
+    /// Unit of the values.
+    unit: Arc<str>,
+    /// Caption of the chart.
+    caption: Arc<str>,
+}
+
+impl StackedAreaConfig {
+    /// Draw the chart to any plotters drawing area.
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let font_desc = FontDesc::new(
+            FontFamily::Monospace,
+            CAPTION_SIZE as f64,
+            FontStyle::Normal,
+        );
+        let text_style = TextStyle::from(font_desc).color(&self.text_color);
+
+        let plot_area = area
+            .margin(Y_MARGIN, Y_MARGIN, X_MARGIN, X_MARGIN)
+            .titled(&self.caption, text_style.clone())?;
+
+        let times = &self.times[..self.shown];
+
+        // There's nothing to draw without any samples
+        let Some(&start) = times.first() else {
+            return Ok(());
+        };
+
+        let end = self.time.max(start + MIN_DELTA);
+
+        // Negative values stack below zero, and only the sides of zero with values get headroom
+        let (bottom, top) = self.extent;
+        let headroom = (top - bottom) * VALUE_HEADROOM;
+
+        let bottom = match bottom < 0.0 {
+            true => bottom - headroom,
+            false => bottom,
+        };
+
+        let top = match top > 0.0 {
+            true => top + headroom,
+            false => top,
+        };
+
+        let y_range = match top > bottom {
+            true => bottom..top,
+            false => 0.0..1.0,
+        };
+
+        let mut chart = ChartBuilder::on(&plot_area)
+            .x_label_area_size(LABEL_AREA)
+            .y_label_area_size(LABEL_AREA)
+            .build_cartesian_2d(start..end, y_range)?;
+
+        chart
+            .configure_mesh()
+            .label_style(text_style)
+            .bold_line_style(self.grid_style)
+            .light_line_style(self.grid_style.color.mix(0.25))
+            .y_desc(self.unit.to_string())
+            .draw()?;
+
+        // Stack the shown samples of each series on the sum of the ones below it
+        let mut stacks: Vec<Vec<f32>> = Vec::with_capacity(self.values.len());
+
+        for values in self.values.iter() {
+            let below = stacks.last();
+
+            let stack = (0..self.shown)
+                .map(|i| {
+                    let base = below.map_or(0.0, |below| below[i]);
+
+                    base + values[i]
+                })
+                .collect();
+
+            stacks.push(stack);
+        }
+
+        // Draw the areas from the top of the stack down, so the series below are drawn last
+        for (i, stack) in stacks.iter().enumerate().rev() {
+            let mut outline: Vec<(f32, f32)> = times.iter().copied().zip(stack.clone()).collect();
+
+            match i.checked_sub(1) {
+                Some(below) => outline.extend(
+                    times
+                        .iter()
+                        .copied()
+                        .zip(stacks[below].iter().copied())
+                        .rev(),
+                ),
+                None => outline.extend([(times[self.shown - 1], 0.0), (start, 0.0)]),
+            }
+
+            chart.draw_series([Polygon::new(outline, self.fill_styles[i])])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Animatable stacked area chart.
+///
+/// ## Usage
+/// **Ensure the `timechart` feature is enabled to use this type.**
+///
+/// Each series is given as a name and its values at each of the sample times, and is drawn as an
+/// area stacked on top of the series before it, so the top of the chart shows their total.
+/// Missing or non-finite values count as 0, and negative values stack downwards.
+///
+/// ```
+/// # use egui_plotter::charts::StackedAreaTimeData;
+/// let times = [0.0, 1.0, 2.0, 3.0];
+///
+/// let chart = StackedAreaTimeData::new(
+///     &times,
+///     &[
+///         ("Solar", &[1.0, 3.0, 4.0, 2.0]),
+///         ("Wind", &[2.0, 1.0, 2.0, 3.0]),
+///     ],
+///     "GW",
+///     "Generation",
+/// );
+/// ```
+///
+/// Like [`XyTimeData`](super::XyTimeData), the chart can be animated with `.toggle_playback()`.
+pub struct StackedAreaTimeData {
+    clock: Clock,
+    names: Arc<[Arc<str>]>,
+    times: Arc<[f32]>,
+    extents: Arc<[(f32, f32)]>,
+    chart: Chart<StackedAreaConfig>,
+}
+
+impl StackedAreaTimeData {
+    /// Create a new StackedAreaTimeData chart. See [Usage](#usage).
+    pub fn new(times: &[f32], series: &[(&str, &[f32])], unit: &str, caption: &str) -> Self {
+        // Sort the samples by time, keeping each series' values with their time
+        let mut order: Vec<usize> = (0..times.len()).collect();
+
+        order.sort_by(|a, b| times[*a].partial_cmp(&times[*b]).unwrap_or(Ordering::Equal));
+
+        let values: Vec<Arc<[f32]>> = series
+            .iter()
+            .map(|(_, values)| {
+                order
+                    .iter()
+                    .map(|i| values.get(*i).copied().filter(|value| value.is_finite()))
+                    .map(|value| value.unwrap_or(0.0))
+                    .collect()
+            })
+            .collect();
+
+        let times: Arc<[f32]> = order.iter().map(|i| times[*i]).collect();
+
+        // Lowest and highest edge of the stacks up to each sample, so the Y axis expands to fit
+        // as playback goes on
+        let extents: Arc<[(f32, f32)]> = (0..times.len())
+            .scan((0.0f32, 0.0f32), |(min, max), i| {
+                let mut edge = 0.0;
+
+                for values in &values {
+                    edge += values[i];
+
+                    *min = min.min(edge);
+                    *max = max.max(edge);
+                }
+
+                Some((*min, *max))
+            })
+            .collect();
+
+        let fill_styles = (0..series.len())
+            .map(|i| Palette99::pick(i).filled())
+            .collect::<Vec<_>>();
+
+        let names: Arc<[Arc<str>]> = series.iter().map(|(name, _)| (*name).into()).collect();
+
+        let grid_style = ShapeStyle {
+            color: GREY.to_rgba(),
+            filled: false,
+            stroke_width: 1,
+        };
+
+        let config = StackedAreaConfig {
+            times: times.clone(),
+            values: values.into(),
+            shown: times.len(),
+            time: times.last().copied().unwrap_or(0.0),
+            extent: extents.last().copied().unwrap_or((0.0, 0.0)),
+            fill_styles,
+            grid_style,
+            text_color: BLACK.to_rgba(),
+            unit: unit.into(),
+            caption: caption.into(),
+        };
+
+        let chart = Chart::new(config)
+            .mouse(MouseConfig::enabled())
+            .min_size(MIN_CHART_SIZE)
+            .builder_cb(Box::new(|area, _t, data| {
+                data.draw(area).unwrap();
+            }));
+
+        let mut stacked = Self {
+            clock: Clock::default(),
+            names,
+            times,
+            extents,
+            chart,
+        };
+
+        stacked.update_legend();
+
+        stacked
+    }
+
+    /// Label each series in the legend with the color of its area.
+    fn update_legend(&mut self) {
+        let legend = self
+            .names
+            .iter()
+            .zip(&self.chart.get_data().fill_styles)
+            .map(|(name, style)| LegendEntry::new(name.as_ref(), &style.color))
+            .collect();
+
+        self.chart.set_legend(legend);
+    }
+
+    /// Set the style of a series' area. Does nothing if there's no such series.
+    pub fn set_fill_style(&mut self, series: usize, fill_style: ShapeStyle) {
+        if let Some(style) = self.chart.get_data_mut().fill_styles.get_mut(series) {
+            *style = fill_style;
+        }
+
+        self.update_legend();
+    }
+
+    #[inline]
+    /// Set the style of a series' area. Consumes self.
+    pub fn fill_style(mut self, series: usize, fill_style: ShapeStyle) -> Self {
+        self.set_fill_style(series, fill_style);
+
+        self
+    }
+
+    /// Set the style of the grid.
+    pub fn set_grid_style(&mut self, grid_style: ShapeStyle) {
+        self.chart.get_data_mut().grid_style = grid_style
+    }
+
+    #[inline]
+    /// Set the style of the grid. Consumes self.
+    pub fn grid_style(mut self, grid_style: ShapeStyle) -> Self {
+        self.set_grid_style(grid_style);
+
+        self
+    }
+
+    #[inline]
+    /// Report to an animation handle whenever the chart is drawn while playing, animating or
+    /// being interacted with, or stop reporting with None. See [`AnimationHandle`].
+    pub fn set_animation_handle(&mut self, animation: Option<AnimationHandle>) {
+        self.chart.set_animation_handle(animation)
+    }
+
+    #[inline]
+    /// Report to an animation handle while playing, animating or being interacted with.
+    /// Consumes self.
+    pub fn animation_handle(mut self, animation: Option<AnimationHandle>) -> Self {
+        self.set_animation_handle(animation);
+
+        self
+    }
+
+    /// Set the color of the text.
+    pub fn set_text_color<T>(&mut self, color: T)
+    where
+        T: Into<RGBAColor>,
+    {
+        self.chart.get_data_mut().text_color = color.into()
+    }
+
+    #[inline]
+    /// Set the color of the text. Consumes self.
+    pub fn text_color<T>(mut self, color: T) -> Self
+    where
+        T: Into<RGBAColor>,
+    {
+        self.set_text_color(color);
+
+        self
+    }
+
+    /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
+    /// enabled.
+    pub fn draw(&mut self, ui: &Ui) {
+        self.clock.tick();
+
+        // Playback changes the chart every frame
+        if self.is_playing() {
+            self.chart.mark_active();
+        }
+
+        if self.clock.is_started() && !self.times.is_empty() {
+            let time = self.current_time();
+            let time_index = self.time_index(time);
+
+            let config = self.chart.get_data_mut();
+            config.shown = time_index + 1;
+            config.time = time;
+            config.extent = self.extents[time_index];
+        }
+
+        self.chart.draw(ui);
+    }
+
+    #[inline]
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.chart.drawing_rect()
+    }
+
+    /// Return the index of the last sample to be shown at the given time, the last sample at or
+    /// before the time. The first sample is always shown so there's something to draw.
+    fn time_index(&self, time: f32) -> usize {
+        self.times.partition_point(|probe| *probe <= time).max(1) - 1
+    }
+
+    /// Set the time to resume playback at. Time is in seconds.
+    pub fn set_time(&mut self, time: f32) {
+        self.clock.set_time(time as f64)
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds. Consumes self.
+    pub fn time(mut self, time: f32) -> Self {
+        self.set_time(time);
+
+        self
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Speeds must be
+    /// positive: 0.0 pauses playback, and negative or non-finite speeds are ignored.
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        self.clock.set_playback_speed(speed)
+    }
+
+    #[inline]
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Consumes self.
+    pub fn playback_speed(mut self, speed: f32) -> Self {
+        self.set_playback_speed(speed);
+
+        self
+    }
+
+    #[inline]
+    /// Start/enable playback of the chart.
+    pub fn start_playback(&mut self) {
+        self.clock.start_playback()
+    }
+
+    #[inline]
+    /// Stop/disable playback of the chart.
+    pub fn stop_playback(&mut self) {
+        self.clock.stop_playback()
+    }
+
+    #[inline]
+    /// Toggle playback of the chart.
+    pub fn toggle_playback(&mut self) {
+        self.clock.toggle_playback()
+    }
+
+    /// Step playback forward by the given number of seconds, as if that much time had passed.
+    /// Playback only moves while playing, but the chart's own animations are always stepped, see
+    /// `Chart::advance`.
+    pub fn advance(&mut self, dt: f32) {
+        self.clock.advance(dt as f64);
+        self.chart.advance(dt);
+    }
+
+    #[inline]
+    /// Return true if playback is currently enabled & underway.
+    pub fn is_playing(&self) -> bool {
+        self.clock.is_playing()
+    }
+
+    #[inline]
+    /// Return the time the chart starts at when playback is enabled, or 0.0 if there are no
+    /// samples.
+    pub fn start_time(&self) -> f32 {
+        self.times.first().copied().unwrap_or(0.0)
+    }
+
+    /// Return the current time to be animated when playback is enabled.
+    pub fn current_time(&mut self) -> f32 {
+        let Some(elapsed) = self.clock.elapsed() else {
+            return self.start_time();
+        };
+
+        let time_start = self.start_time();
+        let time_end = self.end_time();
+
+        // Ensure deltas are over 10us, otherwise they can cause overflows in the plotters library
+        let current_delta = MIN_DELTA + elapsed as f32;
+
+        match time_end - time_start > current_delta {
+            true => current_delta + time_start,
+            false => {
+                self.clock.stop_playback();
+
+                time_end
+            }
+        }
+    }
+
+    #[inline]
+    /// Return the time the chart finished animating at when playback is enabled, or 0.0 if there
+    /// are no samples.
+    pub fn end_time(&self) -> f32 {
+        self.times.last().copied().unwrap_or(0.0)
+    }
+
+    #[inline]
+    /// Return the speed the chart is animated at.
+    pub fn get_playback_speed(&self) -> f32 {
+        self.clock.playback_speed()
+    }
+}
@@ -1,9 +1,16 @@
 //! Animatable chart with data on the Y and time on the X axis
 
-use egui::Ui;
-use plotters::style::{RGBAColor, ShapeStyle};
+use std::{sync::Arc, time::Duration};
 
-use crate::charts::XyTimeData;
+use egui::{Pos2, Rect, Ui};
+use plotters::style::{FontTransform, RGBAColor, ShapeStyle, TextStyle};
+
+use crate::{
+    charts::{AutoScale, Collision, LineInterp, PlaybackKeys, SharedClock, Smoothing, XyTimeData},
+    AnimationHandle,
+};
+
+use super::xytime::Band;
 
 /// Animatable chart with time on the X axis and data on the Y axis.
 ///
@@ -14,7 +21,7 @@ use crate::charts::XyTimeData;
 /// 3 of which are just strings.
 ///
 ///  * `points`: A slice of tuples, arranged so that the first float is the time
-///  and the second is the data.
+///    and the second is the data.
 ///  * `unit`: String describing the data on the Y axis.
 ///  * `caption`: String to be shown as the caption of the chart.
 ///
@@ -28,22 +35,56 @@ pub struct TimeData {
 impl TimeData {
     /// Create a new TimeData chart. See [Usage](#usage).
     pub fn new(points: &[(f32, f32)], unit: &str, caption: &str) -> Self {
-        let points: Vec<(f32, f32, f32)> = points
-            .into_iter()
-            .map(|(data, time)| (*data, *time, *time))
-            .collect();
+        Self::from_records(points.iter().copied(), |point| point, unit, caption)
+    }
 
-        let chart = XyTimeData::new(&points, "seconds", unit, caption);
+    /// Create a new TimeData chart from records of any type, using `extract` to get the time and
+    /// value of each record, in the same `(time, value)` order as the points given to `new`.
+    /// Avoids having to collect the data into tuples first.
+    pub fn from_records<T, I, F>(records: I, mut extract: F, unit: &str, caption: &str) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(T) -> (f32, f32),
+    {
+        let chart = XyTimeData::from_records(
+            records,
+            |record| {
+                let (time, value) = extract(record);
+
+                (time, value, time)
+            },
+            "seconds",
+            unit,
+            caption,
+        );
 
         Self { chart }
     }
 
-    /// Set the time to resume playback at. Time is in seconds.
+    /// Store the ranges the chart expands through during playback at the given number of evenly
+    /// spaced keyframes, rather than one per point, to save memory with large datasets. See
+    /// `XyTimeData::with_range_keyframes`.
+    #[inline]
+    pub fn with_range_keyframes(self, keyframes: usize) -> Self {
+        Self {
+            chart: self.chart.with_range_keyframes(keyframes),
+        }
+    }
+
+    /// Set the time to resume playback at. Time is in seconds since the chart's start time.
     #[inline]
     pub fn set_time(&mut self, time: f32) {
         self.chart.set_time(time)
     }
 
+    /// Jump to a time and pause playback there, so every following `draw` renders exactly that
+    /// frame until playback is resumed. The time is a timestamp of the data, clamped to the
+    /// chart's start and end times.
+    #[inline]
+    pub fn seek_and_pause(&mut self, time: f32) {
+        self.chart.seek_and_pause(time)
+    }
+
     /// Set the time to resume playback at. Time is in seconds. Consumes self.
     #[inline]
     pub fn time(mut self, time: f32) -> Self {
@@ -52,7 +93,8 @@ impl TimeData {
         self
     }
 
-    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Speeds must be
+    /// positive: 0.0 pauses playback, and negative or non-finite speeds are ignored.
     #[inline]
     pub fn set_playback_speed(&mut self, speed: f32) {
         self.chart.set_playback_speed(speed)
@@ -66,6 +108,38 @@ impl TimeData {
         self
     }
 
+    #[inline]
+    /// Share a playback clock with other charts, so they play, pause and seek together. The
+    /// chart's playback functions control the shared clock while one is set. See
+    /// [`SharedClock`].
+    pub fn set_clock(&mut self, clock: Option<Arc<SharedClock>>) {
+        self.chart.set_clock(clock)
+    }
+
+    #[inline]
+    /// Share a playback clock with other charts. Consumes self.
+    pub fn clock(mut self, clock: Option<Arc<SharedClock>>) -> Self {
+        self.set_clock(clock);
+
+        self
+    }
+
+    #[inline]
+    /// Report to an animation handle whenever the chart is drawn while playing, animating or
+    /// being interacted with, or stop reporting with None. See [`AnimationHandle`].
+    pub fn set_animation_handle(&mut self, animation: Option<AnimationHandle>) {
+        self.chart.set_animation_handle(animation)
+    }
+
+    #[inline]
+    /// Report to an animation handle while playing, animating or being interacted with.
+    /// Consumes self.
+    pub fn animation_handle(mut self, animation: Option<AnimationHandle>) -> Self {
+        self.set_animation_handle(animation);
+
+        self
+    }
+
     #[inline]
     /// Set the style of the plotted line.
     pub fn set_line_style(&mut self, line_style: ShapeStyle) {
@@ -122,6 +196,103 @@ impl TimeData {
         self
     }
 
+    #[cfg(feature = "chrono")]
+    #[inline]
+    /// Format the time axis labels as UTC timestamps using a chrono format string, such as
+    /// `"%H:%M:%S"`. Times are treated as seconds after `epoch`, a Unix timestamp. Pass None to
+    /// label the time axis in plain seconds again.
+    pub fn set_time_format(&mut self, format: Option<&str>, epoch: i64) {
+        self.chart.set_time_format(format, epoch)
+    }
+
+    #[cfg(feature = "chrono")]
+    #[inline]
+    /// Format the time axis labels as UTC timestamps using a chrono format string. Consumes self.
+    pub fn time_format(mut self, format: Option<&str>, epoch: i64) -> Self {
+        self.set_time_format(format, epoch);
+
+        self
+    }
+
+    #[inline]
+    /// Only show points added in the given duration before the current time, fading them out as
+    /// they age to leave a trail behind the newest point. Pass None to show every point at full
+    /// opacity, which is the default.
+    pub fn set_trail(&mut self, trail: Option<Duration>) {
+        self.chart.set_trail(trail)
+    }
+
+    #[inline]
+    /// Only show points added in the given duration, fading them out as they age. Consumes self.
+    pub fn trail(mut self, trail: Option<Duration>) -> Self {
+        self.set_trail(trail);
+
+        self
+    }
+
+    #[inline]
+    /// Set whether points with NaN or infinite coordinates, such as gaps in sensor data, break
+    /// the line in two. If false they're dropped and the points either side are joined instead.
+    /// True by default.
+    pub fn set_break_on_nan(&mut self, break_on_nan: bool) {
+        self.chart.set_break_on_nan(break_on_nan)
+    }
+
+    #[inline]
+    /// Set whether points with NaN or infinite coordinates break the line in two. Consumes self.
+    pub fn break_on_nan(mut self, break_on_nan: bool) -> Self {
+        self.set_break_on_nan(break_on_nan);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the lines drawn at x=0 and y=0 when they're in view, or None to not draw
+    /// them, which is the default.
+    pub fn set_zero_axis_style(&mut self, zero_axis_style: Option<ShapeStyle>) {
+        self.chart.set_zero_axis_style(zero_axis_style)
+    }
+
+    #[inline]
+    /// Set the style of the lines drawn at x=0 and y=0. Consumes self.
+    pub fn zero_axis_style(mut self, zero_axis_style: Option<ShapeStyle>) -> Self {
+        self.set_zero_axis_style(zero_axis_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of a vertical line marking the current playback time. It moves smoothly
+    /// with the animation, stays put while paused and is hidden while playback is stopped. None,
+    /// the default, doesn't draw it.
+    pub fn set_now_marker(&mut self, now_marker: Option<ShapeStyle>) {
+        self.chart.set_now_marker(now_marker)
+    }
+
+    #[inline]
+    /// Set the style of a vertical line marking the current playback time. Consumes self.
+    pub fn now_marker(mut self, now_marker: Option<ShapeStyle>) -> Self {
+        self.set_now_marker(now_marker);
+
+        self
+    }
+
+    #[inline]
+    /// Shade the band between a lower and an upper series around the line, such as a confidence
+    /// interval. The values are matched to the points by index, in order of time. Pass None to
+    /// not draw a band, which is the default.
+    pub fn set_band(&mut self, band: Option<Band>) {
+        self.chart.set_band(band)
+    }
+
+    #[inline]
+    /// Shade the band between a lower and an upper series around the line. Consumes self.
+    pub fn band(mut self, band: Option<Band>) -> Self {
+        self.set_band(band);
+
+        self
+    }
+
     #[inline]
     /// Set the text color of the chart.
     pub fn set_text_color<T>(&mut self, color: T)
@@ -176,12 +347,296 @@ impl TimeData {
         self
     }
 
+    #[inline]
+    /// Set whether the ranges are expanded to keep the ratio between X and Y units, which is the
+    /// default. When disabled the data fills all of the available space and the ratio is ignored.
+    pub fn set_preserve_aspect(&mut self, preserve_aspect: bool) {
+        self.chart.set_preserve_aspect(preserve_aspect)
+    }
+
+    #[inline]
+    /// Set whether the ranges are expanded to keep the ratio between X and Y units. Consumes
+    /// self.
+    pub fn preserve_aspect(mut self, preserve_aspect: bool) -> Self {
+        self.set_preserve_aspect(preserve_aspect);
+
+        self
+    }
+
+    #[inline]
+    /// Set the rotation of the X axis labels, which can help when they overlap. Plotters only
+    /// turns text in quarter turns, so labels can be put on their side or upside down but not
+    /// tilted at any other angle. Labels turned on their side are given enough space below the
+    /// chart for the widest of them.
+    pub fn set_x_label_rotation(&mut self, rotation: FontTransform) {
+        self.chart.set_x_label_rotation(rotation)
+    }
+
+    #[inline]
+    /// Set the rotation of the X axis labels. Consumes self.
+    pub fn x_label_rotation(mut self, rotation: FontTransform) -> Self {
+        self.set_x_label_rotation(rotation);
+
+        self
+    }
+
+    #[inline]
+    /// Set what's done with X axis labels that would overlap each other, such as when the
+    /// chart is narrow. Defaults to `Collision::Overlap`, drawing every label.
+    pub fn set_label_collision(&mut self, label_collision: Collision) {
+        self.chart.set_label_collision(label_collision)
+    }
+
+    #[inline]
+    /// Set what's done with X axis labels that would overlap each other. Consumes self.
+    pub fn label_collision(mut self, label_collision: Collision) -> Self {
+        self.set_label_collision(label_collision);
+
+        self
+    }
+
+    #[inline]
+    /// Set the rotation of the Y axis labels.
+    pub fn set_y_label_rotation(&mut self, rotation: FontTransform) {
+        self.chart.set_y_label_rotation(rotation)
+    }
+
+    #[inline]
+    /// Set the rotation of the Y axis labels. Consumes self.
+    pub fn y_label_rotation(mut self, rotation: FontTransform) -> Self {
+        self.set_y_label_rotation(rotation);
+
+        self
+    }
+
+    #[inline]
+    /// Draw the grid over the data rather than behind it, so it stays visible over thick lines.
+    /// Behind by default.
+    pub fn set_grid_on_top(&mut self, grid_on_top: bool) {
+        self.chart.set_grid_on_top(grid_on_top)
+    }
+
+    #[inline]
+    /// Draw the grid over the data rather than behind it. Consumes self.
+    pub fn grid_on_top(mut self, grid_on_top: bool) -> Self {
+        self.set_grid_on_top(grid_on_top);
+
+        self
+    }
+
+    #[inline]
+    /// Fade out the minor grid as the chart is zoomed out, starting when its lines are twice the
+    /// given spacing in pixels apart and hiding it once they're closer than the spacing. Disabled
+    /// by default.
+    pub fn set_grid_fade(&mut self, min_spacing: Option<f32>) {
+        self.chart.set_grid_fade(min_spacing)
+    }
+
+    #[inline]
+    /// Fade out the minor grid as the chart is zoomed out. Consumes self.
+    pub fn grid_fade(mut self, min_spacing: Option<f32>) -> Self {
+        self.set_grid_fade(min_spacing);
+
+        self
+    }
+
+    #[inline]
+    /// Mirror the Y axis so the origin is at the top and Y increases downwards.
+    pub fn set_mirror_y(&mut self, mirror_y: bool) {
+        self.chart.set_mirror_y(mirror_y)
+    }
+
+    #[inline]
+    /// Mirror the Y axis so the origin is at the top and Y increases downwards. Consumes self.
+    pub fn mirror_y(mut self, mirror_y: bool) -> Self {
+        self.set_mirror_y(mirror_y);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the Y axis is scaled to fit the data. Defaults to `AutoScale::Expanding`, which
+    /// never shrinks; `AutoScale::Window` with a trail fits only the recent data instead.
+    pub fn set_y_autoscale(&mut self, y_autoscale: AutoScale) {
+        self.chart.set_y_autoscale(y_autoscale)
+    }
+
+    #[inline]
+    /// Set how the Y axis is scaled to fit the data. Consumes self.
+    pub fn y_autoscale(mut self, y_autoscale: AutoScale) -> Self {
+        self.set_y_autoscale(y_autoscale);
+
+        self
+    }
+
+    #[inline]
+    /// Set how consecutive points are connected. Stepped lines are useful for discrete signals.
+    pub fn set_interpolation(&mut self, interpolation: LineInterp) {
+        self.chart.set_interpolation(interpolation)
+    }
+
+    #[inline]
+    /// Set how consecutive points are connected. Consumes self.
+    pub fn interpolation(mut self, interpolation: LineInterp) -> Self {
+        self.set_interpolation(interpolation);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the line is smoothed between points, such as with a Catmull-Rom curve to smooth
+    /// out sparse data. Only linearly interpolated lines are smoothed.
+    pub fn set_smoothing(&mut self, smoothing: Smoothing) {
+        self.chart.set_smoothing(smoothing)
+    }
+
+    #[inline]
+    /// Set how the line is smoothed between points. Consumes self.
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.set_smoothing(smoothing);
+
+        self
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines drawn between major grid lines. The minor grid
+    /// lines are drawn using the subgrid style, so 0 hides the subgrid entirely.
+    pub fn set_minor_ticks(&mut self, minor_ticks: usize) {
+        self.chart.set_minor_ticks(minor_ticks)
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines drawn between major grid lines. Consumes self.
+    pub fn minor_ticks(mut self, minor_ticks: usize) -> Self {
+        self.set_minor_ticks(minor_ticks);
+
+        self
+    }
+
+    #[inline]
+    /// Set the subtitle shown below the caption.
+    pub fn set_subtitle(&mut self, subtitle: &str) {
+        self.chart.set_subtitle(subtitle)
+    }
+
+    #[inline]
+    /// Set the subtitle shown below the caption. Consumes self.
+    pub fn subtitle(mut self, subtitle: &str) -> Self {
+        self.set_subtitle(subtitle);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the caption. By default the caption uses the same style as the labels.
+    pub fn set_caption_style(&mut self, caption_style: TextStyle<'static>) {
+        self.chart.set_caption_style(caption_style)
+    }
+
+    #[inline]
+    /// Set the style of the caption. Consumes self.
+    pub fn caption_style(mut self, caption_style: TextStyle<'static>) -> Self {
+        self.set_caption_style(caption_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the subtitle. By default the subtitle uses the same style as the labels.
+    pub fn set_subtitle_style(&mut self, subtitle_style: TextStyle<'static>) {
+        self.chart.set_subtitle_style(subtitle_style)
+    }
+
+    #[inline]
+    /// Set the style of the subtitle. Consumes self.
+    pub fn subtitle_style(mut self, subtitle_style: TextStyle<'static>) -> Self {
+        self.set_subtitle_style(subtitle_style);
+
+        self
+    }
+
     /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
     /// enabled.
     pub fn draw(&mut self, ui: &Ui) {
         self.chart.draw(ui)
     }
 
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    #[inline]
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.chart.drawing_rect()
+    }
+
+    /// Return how many points are shown at the given time, the points at or before the time.
+    /// Points sharing a timestamp are always shown together, and the first point is shown even
+    /// before its time.
+    #[inline]
+    pub fn revealed_count(&self, time: f32) -> usize {
+        self.chart.revealed_count(time)
+    }
+
+    /// Return the index of the point nearest to the given position on screen, out of the points
+    /// revealed at the current time. Returns None if the position is outside the chart, or the
+    /// chart hasn't been drawn yet.
+    #[inline]
+    pub fn hovered_index(&self, pointer: Pos2) -> Option<usize> {
+        self.chart.hovered_index(pointer)
+    }
+
+    /// Emphasize the point at the given index with a marker, wherever the mouse is, once it's
+    /// revealed. Pass None to not highlight any point, which is the default.
+    #[inline]
+    pub fn set_highlight_index(&mut self, index: Option<usize>) {
+        self.chart.set_highlight_index(index)
+    }
+
+    /// Emphasize the point at the given index with a marker. Consumes self.
+    #[inline]
+    pub fn highlight_index(mut self, index: Option<usize>) -> Self {
+        self.set_highlight_index(index);
+
+        self
+    }
+
+    /// Render the chart as it would appear at the given time into a PNG image, laid out at the
+    /// given size and enlarged by `scale`. Pan and zoom applied by the mouse are not included in
+    /// the export.
+    ///
+    /// **Ensure the `export` feature is enabled to use this function.**
+    #[cfg(feature = "export")]
+    #[inline]
+    pub fn export_frame(
+        &self,
+        time: f32,
+        size: (u32, u32),
+        scale: f32,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        self.chart.export_frame(time, size, scale)
+    }
+
+    /// Control playback with the keyboard. This is opt-in, call it every frame before drawing
+    /// if you want the chart to respond to the given keys.
+    #[inline]
+    pub fn handle_input(&mut self, ui: &Ui, keys: PlaybackKeys) {
+        self.chart.handle_input(ui, keys)
+    }
+
+    /// Pause playback while the window isn't focused, resuming from the same point once focus
+    /// returns. Disabled by default.
+    #[inline]
+    pub fn set_pause_on_blur(&mut self, pause_on_blur: bool) {
+        self.chart.set_pause_on_blur(pause_on_blur)
+    }
+
+    /// Pause playback while the window isn't focused. Consumes self.
+    #[inline]
+    pub fn pause_on_blur(mut self, pause_on_blur: bool) -> Self {
+        self.set_pause_on_blur(pause_on_blur);
+
+        self
+    }
+
     /// Start/enable playback of the chart.
     #[inline]
     pub fn start_playback(&mut self) {
@@ -199,6 +654,15 @@ impl TimeData {
         self.chart.toggle_playback()
     }
 
+    /// Step playback forward by the given number of seconds, as if that much time had passed.
+    /// `draw` does this with the time since the last frame, so calling it yourself lets you
+    /// drive playback deterministically, such as in tests. Playback only moves while playing,
+    /// but the chart's own animations are always stepped, see `Chart::advance`.
+    #[inline]
+    pub fn advance(&mut self, dt: f32) {
+        self.chart.advance(dt)
+    }
+
     /// Return true if playback is currently enabled & underway.
     #[inline]
     pub fn is_playing(&self) -> bool {
@@ -217,12 +681,71 @@ impl TimeData {
         self.chart.current_time()
     }
 
+    /// Set the time to resume playback at as a fraction of the chart's duration, from 0.0 at the
+    /// start time to 1.0 at the end time.
+    #[inline]
+    pub fn set_progress(&mut self, progress: f32) {
+        self.chart.set_progress(progress)
+    }
+
+    /// Set the time to resume playback at as a fraction of the chart's duration. Consumes self.
+    #[inline]
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.set_progress(progress);
+
+        self
+    }
+
+    /// Return how far playback is through the chart's duration, from 0.0 at the start time to
+    /// 1.0 at the end time.
+    #[inline]
+    pub fn get_progress(&mut self) -> f32 {
+        self.chart.get_progress()
+    }
+
     /// Return the time the chart finished animating at when playback is enabled.
     #[inline]
     pub fn end_time(&self) -> f32 {
         self.chart.end_time()
     }
 
+    /// Append a `(time, value)` point to the chart, for charts fed with live data. Points are
+    /// expected to arrive in time order. If a capacity is set, the oldest points past it are
+    /// dropped.
+    #[inline]
+    pub fn push_point(&mut self, point: (f32, f32)) {
+        let (time, value) = point;
+
+        self.chart.push_point((time, value, time))
+    }
+
+    /// Set the most points the chart holds, dropping the oldest points whenever there are more.
+    /// Dropped points can't be recovered. Set to None to keep every point, which is the default.
+    #[inline]
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.chart.set_capacity(capacity)
+    }
+
+    /// Set the most points the chart holds, dropping the oldest points. Consumes self.
+    #[inline]
+    pub fn capacity(mut self, capacity: Option<usize>) -> Self {
+        self.set_capacity(capacity);
+
+        self
+    }
+
+    /// Return the chart's points, sorted by time like they're drawn.
+    #[inline]
+    pub fn points(&self) -> &[(f32, f32)] {
+        self.chart.points()
+    }
+
+    /// Return the times of the chart's points, sorted and in the same order as `points`.
+    #[inline]
+    pub fn times(&self) -> &[f32] {
+        self.chart.times()
+    }
+
     /// Return the speed the chart is animated at.
     #[inline]
     pub fn get_playback_speed(&self) -> f32 {
@@ -2,19 +2,36 @@
 
 use std::{cmp::Ordering, ops::Range, sync::Arc, time::Duration};
 
-use egui::Ui;
-use instant::Instant;
+use egui::{Key, Pos2, Rect, Ui};
 use plotters::{
-    prelude::ChartBuilder,
+    coord::{
+        cartesian::Cartesian2d,
+        ranged1d::{Ranged, ValueFormatter},
+        types::RangedCoordf32,
+        Shift,
+    },
+    prelude::{
+        ChartBuilder, ChartContext, Circle, DrawingArea, DrawingAreaErrorKind, DrawingBackend,
+        PathElement, Polygon,
+    },
     series::LineSeries,
     style::{
         full_palette::{GREY, GREY_700, RED_900},
         Color, FontDesc, RGBAColor, ShapeStyle, TextStyle, BLACK, WHITE,
     },
 };
-use plotters_backend::{FontFamily, FontStyle};
+use plotters_backend::{FontFamily, FontStyle, FontTransform};
+
+use crate::{
+    elements::split_runs, mirror_range, mult_range, AnimationHandle, Chart, MappingRecorder,
+    MouseConfig,
+};
 
-use crate::{mult_range, Chart, MouseConfig};
+use super::{
+    clock::{Clock, SharedClock},
+    fit::pad_range,
+    MIN_CHART_SIZE,
+};
 
 const MIN_DELTA: f32 = 0.000_010;
 const DEFAULT_RATIO: f32 = 1.0;
@@ -22,11 +39,211 @@ const X_MARGIN: i32 = 25;
 const Y_MARGIN: i32 = 25;
 const LABEL_AREA: i32 = 25;
 const CAPTION_SIZE: i32 = 10;
+// Plotters' default number of labels and maximum light lines per label, used to estimate the
+// spacing of the minor grid
+const MESH_LABELS: f64 = 10.0;
+const MESH_LIGHT_LINES: usize = 10;
+// Number of X labels plotters asks for by default, which fewer are dropped from on collision
+const DEFAULT_X_LABELS: usize = 11;
+// Minimum space between neighbouring X labels, in pixels, before they're considered colliding
+const LABEL_GAP: f32 = 4.0;
+// Smallest fraction of their size X labels are shrunk to on collision
+const MIN_LABEL_SHRINK: f32 = 0.5;
+// Radius of the marker drawn on the highlighted point
+const HIGHLIGHT_RADIUS: i32 = 5;
+
+/// Lower and upper values of a band shaded around the line, and the style it's filled with.
+pub(crate) type Band = (Arc<[f32]>, Arc<[f32]>, ShapeStyle);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// How the plotted line connects consecutive points.
+pub enum LineInterp {
+    /// Connect points with a straight line.
+    #[default]
+    Linear,
+    /// Step to the next point's Y value at the current point's X value.
+    StepBefore,
+    /// Hold the current point's Y value until the next point's X value.
+    StepAfter,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// How the straight segments of a linearly interpolated line are smoothed before drawing.
+pub enum Smoothing {
+    /// Draw the straight segments as they are.
+    #[default]
+    None,
+    /// Draw a smooth Catmull-Rom curve through the points, with each segment split into the
+    /// given number of samples.
+    CatmullRom { samples: usize },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+/// How the Y axis is scaled to fit the data as playback goes on.
+pub enum AutoScale {
+    /// Always show the given range, from the first value to the second.
+    Fixed(f32, f32),
+    /// Grow to fit every point shown so far, never shrinking.
+    #[default]
+    Expanding,
+    /// Fit only the points currently visible, which are those within the trail if one is set,
+    /// so the range shrinks again once an outlier has passed.
+    Window,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+/// What's done with X axis labels that would overlap each other, such as on narrow charts.
+pub enum Collision {
+    /// Draw every label, even if they overlap.
+    #[default]
+    Overlap,
+    /// Draw fewer labels, further apart, until they no longer overlap.
+    Drop,
+    /// Turn the labels on their side, giving them extra space below the chart.
+    Rotate,
+    /// Shrink the labels until they no longer overlap, down to half their size.
+    Shrink,
+}
+
+impl LineInterp {
+    /// Expand a set of points into the points of the line to be drawn.
+    fn interpolate(&self, points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        match self {
+            Self::Linear => points.to_vec(),
+            Self::StepBefore | Self::StepAfter => {
+                let mut stepped = Vec::with_capacity(points.len() * 2);
+
+                for (i, point) in points.iter().enumerate() {
+                    if i > 0 {
+                        let (prev_x, prev_y) = points[i - 1];
+                        let (x, y) = *point;
+
+                        match self {
+                            Self::StepBefore => stepped.push((prev_x, y)),
+                            _ => stepped.push((x, prev_y)),
+                        }
+                    }
+
+                    stepped.push(*point);
+                }
+
+                stepped
+            }
+        }
+    }
+}
+
+impl Smoothing {
+    /// Expand a set of points and their times into the vertices of the curve to be drawn, with
+    /// the time of each vertex interpolated linearly along its segment.
+    fn smooth(&self, points: &[(f32, f32)], times: &[f32]) -> (Vec<(f32, f32)>, Vec<f32>) {
+        let samples = match self {
+            Self::None => return (points.to_vec(), times.to_vec()),
+            Self::CatmullRom { samples } => (*samples).max(1),
+        };
+
+        let mut smoothed = Vec::with_capacity(points.len() * samples);
+        let mut smoothed_times = Vec::with_capacity(points.len() * samples);
+
+        for i in 0..points.len().saturating_sub(1) {
+            // Repeat the end points so the curve passes through them
+            let p0 = points[i.saturating_sub(1)];
+            let p1 = points[i];
+            let p2 = points[i + 1];
+            let p3 = points[(i + 2).min(points.len() - 1)];
+
+            for sample in 0..samples {
+                let t = sample as f32 / samples as f32;
+
+                smoothed.push((
+                    catmull_rom(p0.0, p1.0, p2.0, p3.0, t),
+                    catmull_rom(p0.1, p1.1, p2.1, p3.1, t),
+                ));
+                smoothed_times.push(times[i] + (times[i + 1] - times[i]) * t);
+            }
+        }
+
+        smoothed.extend(points.last());
+        smoothed_times.extend(times.last());
+
+        (smoothed, smoothed_times)
+    }
+}
+
+/// Cut a line off at the given time, ending it partway along the segment to the first vertex
+/// after the time. Vertex times must be sorted.
+fn clip_line(points: &mut Vec<(f32, f32)>, times: &mut Vec<f32>, time: f32) {
+    let shown = times.partition_point(|probe| *probe <= time);
+
+    if shown == 0 || shown == times.len() {
+        points.truncate(shown);
+        times.truncate(shown);
+
+        return;
+    }
+
+    let (start, end) = (times[shown - 1], times[shown]);
+    let ((start_x, start_y), (end_x, end_y)) = (points[shown - 1], points[shown]);
+
+    // The end vertex is after the time, so it's always later than the start vertex
+    let t = (time - start) / (end - start);
+
+    points.truncate(shown);
+    times.truncate(shown);
+
+    points.push((
+        start_x + (end_x - start_x) * t,
+        start_y + (end_y - start_y) * t,
+    ));
+    times.push(time);
+}
+
+/// Evaluate a uniform Catmull-Rom spline between p1 and p2 at t.
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Keys used to control playback when calling `handle_input` on a timechart. Set a key to `None`
+/// to leave that action unbound.
+pub struct PlaybackKeys {
+    /// Key that toggles playback.
+    pub toggle: Option<Key>,
+    /// Key that seeks forward by `seek_step` seconds.
+    pub seek_forward: Option<Key>,
+    /// Key that seeks backward by `seek_step` seconds.
+    pub seek_backward: Option<Key>,
+    /// Amount of time to seek by, in seconds.
+    pub seek_step: f32,
+}
+
+impl Default for PlaybackKeys {
+    fn default() -> Self {
+        Self {
+            toggle: Some(Key::Space),
+            seek_forward: Some(Key::ArrowRight),
+            seek_backward: Some(Key::ArrowLeft),
+            seek_step: 1.0,
+        }
+    }
+}
 
 #[derive(Clone)]
 struct XyTimeConfig {
     /// Points to be plotted. A slice of X, Y f32 pairs.
     points: Arc<[(f32, f32)]>,
+    /// Times of the plotted points.
+    times: Arc<[f32]>,
+    /// Time the chart is shown at.
+    time: f32,
+    /// How long points remain visible for, fading out over that time.
+    trail: Option<Duration>,
     /// Ranges at different time points.
     range: (Range<f32>, Range<f32>),
     /// Style of the plotted line.
@@ -49,6 +266,600 @@ struct XyTimeConfig {
     ratio: f32,
     /// Caption of the chart.
     caption: Arc<str>,
+    /// Whether the Y axis is mirrored, placing the origin at the top.
+    mirror_y: bool,
+    /// How consecutive points are connected.
+    interpolation: LineInterp,
+    /// How the segments of linearly interpolated lines are smoothed.
+    smoothing: Smoothing,
+    /// Points after those shown, along with their times, which the line is drawn partway towards
+    /// during playback and which shape the end of smoothed curves.
+    upcoming: Vec<((f32, f32), f32)>,
+    /// Whether points with NaN or infinite coordinates break the line, rather than being dropped.
+    break_on_nan: bool,
+    /// Maximum number of minor grid lines between major grid lines.
+    minor_ticks: Option<usize>,
+    /// Subtitle shown below the caption.
+    subtitle: Option<Arc<str>>,
+    /// Style of the caption, uses the chart's text style if None.
+    caption_style: Option<TextStyle<'static>>,
+    /// Style of the subtitle, uses the chart's text style if None.
+    subtitle_style: Option<TextStyle<'static>>,
+    /// Whether the ranges are adjusted to keep the ratio between X and Y units.
+    preserve_aspect: bool,
+    /// Whether the grid is drawn over the data rather than behind it.
+    grid_on_top: bool,
+    /// Minimum spacing in pixels of the minor grid before it starts fading out.
+    grid_fade: Option<f32>,
+    /// Rotation of the X axis labels.
+    x_label_rotation: FontTransform,
+    /// Rotation of the Y axis labels.
+    y_label_rotation: FontTransform,
+    /// What's done with X axis labels that would overlap.
+    label_collision: Collision,
+    /// Style of the lines drawn at x=0 and y=0, not drawn if None.
+    zero_axis_style: Option<ShapeStyle>,
+    /// Lower and upper values of the band shaded around the line at each point, and its style.
+    band: Option<Band>,
+    /// Index of the point emphasized with a marker, if any.
+    highlight: Option<usize>,
+    /// Style of the vertical line marking the playback position, not drawn if None.
+    now_marker: Option<ShapeStyle>,
+    /// X coordinate of the playback position, None while playback is stopped.
+    now: Option<f32>,
+    /// Records the mapping of the chart as it's drawn, to find points on screen.
+    mapping: MappingRecorder,
+    /// Format of the X axis labels as timestamps, and the Unix timestamp X is relative to.
+    #[cfg(feature = "chrono")]
+    time_format: Option<(Arc<str>, i64)>,
+}
+
+impl XyTimeConfig {
+    /// Draw the chart to any plotters drawing area.
+    fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        scale: f64,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let font_style = FontStyle::Normal;
+        let font_family = FontFamily::Monospace;
+        let font_size = CAPTION_SIZE;
+
+        let font_desc = FontDesc::new(font_family, font_size as f64, font_style);
+
+        let text_style = TextStyle::from(font_desc).color(&self.text_color);
+
+        // Draw the caption and subtitle ourselves so each reserves the space it needs
+        let mut plot_area = area.margin(Y_MARGIN, Y_MARGIN, X_MARGIN, X_MARGIN);
+
+        let caption_style = self.caption_style.as_ref().unwrap_or(&text_style);
+
+        plot_area = plot_area.titled(&self.caption, caption_style.clone())?;
+
+        if let Some(subtitle) = &self.subtitle {
+            let subtitle_style = self.subtitle_style.as_ref().unwrap_or(&text_style);
+
+            plot_area = plot_area.titled(subtitle, subtitle_style.clone())?;
+        }
+
+        let Some(mut chart) = self.build_chart(&plot_area, LABEL_AREA)? else {
+            return Ok(());
+        };
+
+        let (x_labels, x_label_rotation, x_label_size) = self.fit_x_labels(&chart, &text_style)?;
+
+        let x_label_style = TextStyle::from(text_style.font.resize(x_label_size))
+            .color(&self.text_color)
+            .transform(x_label_rotation.clone());
+
+        // X labels turned on their side need room below the chart for the widest of them
+        let x_label_area = self.x_label_area(&chart, x_labels, &x_label_style)?;
+
+        if x_label_area != LABEL_AREA {
+            match self.build_chart(&plot_area, x_label_area)? {
+                Some(rotated) => chart = rotated,
+                None => return Ok(()),
+            }
+        }
+
+        self.mapping.record(&chart);
+
+        let mut minor_ticks = self.minor_ticks;
+        let mut subgrid_style = self.subgrid_style;
+
+        // Fade the minor grid out as its lines get closer together, hiding it completely once
+        // they're closer than the minimum spacing
+        if let Some(min_spacing) = self.grid_fade {
+            let (x_pixels, y_pixels) = chart.plotting_area().get_pixel_range();
+
+            let pixels = (x_pixels.end - x_pixels.start)
+                .abs()
+                .min((y_pixels.end - y_pixels.start).abs()) as f64
+                * scale;
+
+            let lines = MESH_LABELS * (minor_ticks.unwrap_or(MESH_LIGHT_LINES) + 1) as f64;
+            let spacing = pixels / lines;
+
+            let fade = (spacing / min_spacing as f64 - 1.0).clamp(0.0, 1.0);
+
+            match fade > 0.0 {
+                true => subgrid_style.color = subgrid_style.color.mix(fade),
+                false => minor_ticks = Some(0),
+            }
+        }
+
+        #[cfg(feature = "chrono")]
+        let time_formatter = self
+            .time_format
+            .as_ref()
+            .map(|(format, epoch)| move |x: &f32| format_time(*x, format, *epoch));
+
+        let draw_mesh = |chart: &mut ChartContext<DB, _>| {
+            let mut mesh = chart.configure_mesh();
+
+            #[cfg(feature = "chrono")]
+            if let Some(time_formatter) = &time_formatter {
+                mesh.x_label_formatter(time_formatter);
+            }
+
+            if let Some(minor_ticks) = minor_ticks {
+                mesh.x_max_light_lines(minor_ticks)
+                    .y_max_light_lines(minor_ticks);
+            }
+
+            mesh.label_style(text_style.clone())
+                .x_labels(x_labels)
+                .x_label_style(x_label_style.clone())
+                .y_label_style(text_style.clone().transform(self.y_label_rotation.clone()))
+                .bold_line_style(self.grid_style)
+                .light_line_style(subgrid_style)
+                .axis_style(self.axes_style)
+                .x_desc(self.x_unit.to_string())
+                .set_all_tick_mark_size(4)
+                .y_desc(self.y_unit.to_string())
+                .draw()
+        };
+
+        // Plotters draws in call order, so the grid is drawn either before or after the data
+        if !self.grid_on_top {
+            draw_mesh(&mut chart)?;
+        }
+
+        // Emphasize the zero axes, skipping any that aren't visible
+        if let Some(zero_axis_style) = self.zero_axis_style {
+            let x_range = chart.x_range();
+            let y_range = chart.y_range();
+
+            let spans_zero = |range: &Range<f32>| {
+                range.start.min(range.end) <= 0.0 && range.start.max(range.end) >= 0.0
+            };
+
+            if spans_zero(&x_range) {
+                chart.draw_series(LineSeries::new(
+                    [(0.0, y_range.start), (0.0, y_range.end)],
+                    zero_axis_style,
+                ))?;
+            }
+
+            if spans_zero(&y_range) {
+                chart.draw_series(LineSeries::new(
+                    [(x_range.start, 0.0), (x_range.end, 0.0)],
+                    zero_axis_style,
+                ))?;
+            }
+        }
+
+        if let Some(band) = &self.band {
+            self.draw_band(&mut chart, band)?;
+        }
+
+        // Split the line at gaps in the data, interpolating each part on its own
+        let runs = split_runs(
+            self.points
+                .iter()
+                .copied()
+                .zip(self.times.iter().copied())
+                .chain(self.upcoming.iter().copied()),
+            |((x, y), _)| x.is_finite() && y.is_finite(),
+            self.break_on_nan,
+        );
+
+        for run in runs {
+            let (points, times): (Vec<(f32, f32)>, Vec<f32>) = run.into_iter().unzip();
+
+            self.draw_line(&mut chart, &points, &times)?;
+        }
+
+        // Only points revealed so far can be highlighted
+        if let Some((x, y)) = self
+            .highlight
+            .and_then(|index| self.points.get(index))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+        {
+            chart.draw_series([Circle::new(
+                (*x, *y),
+                HIGHLIGHT_RADIUS,
+                self.line_style.filled(),
+            )])?;
+        }
+
+        if let (Some(now_marker), Some(x)) = (self.now_marker, self.now) {
+            let y_range = chart.y_range();
+
+            chart.draw_series(LineSeries::new(
+                [(x, y_range.start), (x, y_range.end)],
+                now_marker,
+            ))?;
+        }
+
+        if self.grid_on_top {
+            draw_mesh(&mut chart)?;
+        }
+
+        Ok(())
+    }
+
+    /// Lay the chart out on the area with the given height for the X labels, fitting the ranges
+    /// to it. Returns None if the area is too small to draw the chart in.
+    #[allow(clippy::type_complexity)]
+    fn build_chart<'a, DB: DrawingBackend>(
+        &self,
+        plot_area: &'a DrawingArea<DB, Shift>,
+        x_label_area: i32,
+    ) -> Result<
+        Option<ChartContext<'a, DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>>,
+        DrawingAreaErrorKind<DB::ErrorType>,
+    > {
+        let area_ratio = {
+            let (x_range, y_range) = plot_area.get_pixel_range();
+
+            let x_delta = ((x_range.end - x_range.start).abs() - LABEL_AREA) as f32;
+            let y_delta = ((y_range.end - y_range.start).abs() - x_label_area) as f32;
+
+            x_delta / y_delta
+        };
+
+        // Return if the ratio is invalid(meaning the chart can't be drawn)
+        if !area_ratio.is_finite() {
+            return Ok(None);
+        }
+
+        // All points sharing an X or Y value leave a range with no width, which can't be mapped
+        let (x_range, y_range) = self.range.clone();
+        let pad = |range: Range<f32>| {
+            let range = pad_range(range.start as f64..range.end as f64, 0.0);
+
+            range.start as f32..range.end as f32
+        };
+
+        let (x_range, y_range) = (pad(x_range), pad(y_range));
+
+        let (x_range, y_range) = match self.preserve_aspect {
+            true => {
+                // The data ratio is inverse, as if our X range is smaller we
+                // want to make sure the X axis is expanded to compensate
+                let data_ratio = {
+                    let x_delta = (x_range.end - x_range.start).abs();
+                    let y_delta = (y_range.end - y_range.start).abs();
+
+                    y_delta / x_delta
+                };
+
+                let display_ratio = self.ratio * data_ratio * area_ratio;
+
+                match display_ratio.partial_cmp(&1.0).unwrap_or(Ordering::Equal) {
+                    Ordering::Equal => (x_range, y_range),
+                    Ordering::Greater => (mult_range(x_range, display_ratio), y_range),
+                    Ordering::Less => (x_range, mult_range(y_range, 1.0 / display_ratio)),
+                }
+            }
+            false => (x_range, y_range),
+        };
+
+        let y_range = match self.mirror_y {
+            true => mirror_range(y_range),
+            false => y_range,
+        };
+
+        let mut builder = ChartBuilder::on(plot_area);
+
+        // Keep the X labels next to the origin when the Y axis is mirrored
+        match self.mirror_y {
+            true => builder.top_x_label_area_size(x_label_area),
+            false => builder.x_label_area_size(x_label_area),
+        };
+
+        let chart = builder
+            .y_label_area_size(LABEL_AREA)
+            .build_cartesian_2d(x_range, y_range)?;
+
+        Ok(Some(chart))
+    }
+
+    /// Return the number, rotation and font size of the X axis labels, changed from the defaults
+    /// as set by the label collision mode if the labels would otherwise overlap.
+    fn fit_x_labels<DB: DrawingBackend>(
+        &self,
+        chart: &ChartContext<DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+        text_style: &TextStyle,
+    ) -> Result<(usize, FontTransform, f64), DrawingAreaErrorKind<DB::ErrorType>> {
+        let unchanged = (
+            DEFAULT_X_LABELS,
+            self.x_label_rotation.clone(),
+            CAPTION_SIZE as f64,
+        );
+
+        // Labels already on their side are left alone
+        let sideways = matches!(
+            self.x_label_rotation,
+            FontTransform::Rotate90 | FontTransform::Rotate270
+        );
+
+        if self.label_collision == Collision::Overlap || sideways {
+            return Ok(unchanged);
+        }
+
+        let area = chart.plotting_area();
+        let x_range = chart.x_range();
+        let y = chart.y_range().start;
+        let coord = RangedCoordf32::from(x_range);
+
+        // Center and width of each label drawn when plotters is asked for the given number
+        let measure = |count: usize| {
+            coord
+                .key_points(count)
+                .into_iter()
+                .map(|x| {
+                    let (label_width, _) =
+                        area.estimate_text_size(&self.format_x(x), text_style)?;
+
+                    let (pixel, _) = area.map_coordinate(&(x, y));
+
+                    Ok((pixel as f32, label_width as f32))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let labels = measure(DEFAULT_X_LABELS)?;
+
+        if label_fit(&labels) >= 1.0 {
+            return Ok(unchanged);
+        }
+
+        let (count, rotation, size) = unchanged;
+
+        Ok(match self.label_collision {
+            Collision::Overlap => (count, rotation, size),
+            Collision::Drop => {
+                let mut count = count;
+
+                while count > 1 && label_fit(&measure(count)?) < 1.0 {
+                    count -= 1;
+                }
+
+                (count, rotation, size)
+            }
+            Collision::Rotate => (count, FontTransform::Rotate90, size),
+            Collision::Shrink => {
+                let shrink = label_fit(&labels).max(MIN_LABEL_SHRINK) as f64;
+
+                (count, rotation, size * shrink)
+            }
+        })
+    }
+
+    /// Return the height of the area below the chart given to the given number of X labels drawn
+    /// in the given style. Labels on their side take up their width rather than their height.
+    fn x_label_area<DB: DrawingBackend>(
+        &self,
+        chart: &ChartContext<DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+        count: usize,
+        style: &TextStyle,
+    ) -> Result<i32, DrawingAreaErrorKind<DB::ErrorType>> {
+        if !matches!(
+            style.font.get_transform(),
+            FontTransform::Rotate90 | FontTransform::Rotate270
+        ) {
+            return Ok(LABEL_AREA);
+        }
+
+        // Measure the labels as they read, before they're turned
+        let upright = TextStyle::from(style.font.transform(FontTransform::None));
+
+        let area = chart.plotting_area();
+        let coord = RangedCoordf32::from(chart.x_range());
+
+        let mut extra = 0;
+
+        for x in coord.key_points(count) {
+            let (width, height) = area.estimate_text_size(&self.format_x(x), &upright)?;
+
+            extra = extra.max(width as i32 - height as i32);
+        }
+
+        Ok(LABEL_AREA + extra)
+    }
+
+    /// Format an X value the way it's labeled on the axis.
+    fn format_x(&self, x: f32) -> String {
+        #[cfg(feature = "chrono")]
+        if let Some((format, epoch)) = &self.time_format {
+            return format_time(x, format, *epoch);
+        }
+
+        RangedCoordf32::format(&x)
+    }
+
+    /// Fill the region between the lower and upper values of the band at each point shown, behind
+    /// the line. Points outside the trail and points with non-finite values are left out.
+    fn draw_band<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+        band: &Band,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let (lower, upper, style) = band;
+
+        let start = match self.trail {
+            Some(trail) => self.time - trail.as_secs_f32(),
+            None => f32::NEG_INFINITY,
+        };
+
+        let (lower, upper): (Vec<_>, Vec<_>) = self
+            .points
+            .iter()
+            .zip(self.times.iter())
+            .zip(lower.iter().zip(upper.iter()))
+            .filter(|((_, time), _)| **time >= start)
+            .map(|(((x, _), _), (lower, upper))| ((*x, *lower), (*x, *upper)))
+            .filter(|((x, lower), (_, upper))| {
+                x.is_finite() && lower.is_finite() && upper.is_finite()
+            })
+            .unzip();
+
+        if lower.len() < 2 {
+            return Ok(());
+        }
+
+        let outline: Vec<(f32, f32)> = upper.into_iter().chain(lower.into_iter().rev()).collect();
+
+        chart.draw_series([Polygon::new(outline, style.filled())])?;
+
+        Ok(())
+    }
+
+    /// Draw a single unbroken part of the line, along with its trail.
+    fn draw_line<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf32, RangedCoordf32>>,
+        points: &[(f32, f32)],
+        times: &[f32],
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let (mut points, mut times) = match self.interpolation {
+            LineInterp::Linear => self.smoothing.smooth(points, times),
+            interpolation => {
+                // Interpolate the times the same way as the points to get the time of each vertex
+                let times: Vec<(f32, f32)> = times.iter().map(|time| (*time, *time)).collect();
+
+                (
+                    interpolation.interpolate(points),
+                    interpolation
+                        .interpolate(&times)
+                        .into_iter()
+                        .map(|(_, time)| time)
+                        .collect(),
+                )
+            }
+        };
+
+        clip_line(&mut points, &mut times, self.time);
+
+        match self.trail {
+            Some(trail) => {
+                let trail = trail.as_secs_f32();
+
+                // Draw each segment faded by the age of its newest end, dropping expired ones
+                let segments =
+                    points
+                        .windows(2)
+                        .zip(times.windows(2))
+                        .filter_map(|(points, times)| {
+                            let time = times[1];
+                            let age = self.time - time;
+
+                            (age <= trail).then(|| {
+                                let fade = match trail > 0.0 {
+                                    true => 1.0 - age / trail,
+                                    false => 1.0,
+                                };
+
+                                let style = ShapeStyle {
+                                    color: self.line_style.color.mix(fade.clamp(0.0, 1.0) as f64),
+                                    ..self.line_style
+                                };
+
+                                PathElement::new(points.to_vec(), style)
+                            })
+                        });
+
+                chart.draw_series(segments)?;
+            }
+            None => {
+                chart.draw_series(LineSeries::new(points, self.line_style))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compute the ranges fitting every point up to each multiple of `step` and the last point.
+fn expanding_ranges(points: &[(f32, f32)], step: usize) -> Vec<(Range<f32>, Range<f32>)> {
+    let mut ranges = Vec::with_capacity(points.len().div_ceil(step) + 1);
+
+    for (index, point) in points.iter().copied().enumerate() {
+        push_range(&mut ranges, index, step, point);
+    }
+
+    ranges
+}
+
+/// Add the keyframe for the point at the given index to the expanding ranges of the points before
+/// it. Keyframes fall on every `step` points and on the last point, so the previous keyframe is
+/// replaced if it was only there for being last.
+fn push_range(
+    ranges: &mut Vec<(Range<f32>, Range<f32>)>,
+    index: usize,
+    step: usize,
+    point: (f32, f32),
+) {
+    let (x, y) = point;
+
+    let (x_range, y_range) = match index {
+        0 => (x..x, y..y),
+        _ => match (index - 1) % step {
+            0 => ranges.last().unwrap().clone(),
+            _ => ranges.pop().unwrap(),
+        },
+    };
+
+    ranges.push((
+        x_range.start.min(x)..x_range.end.max(x),
+        y_range.start.min(y)..y_range.end.max(y),
+    ));
+}
+
+/// Return the fraction of their size labels have to be shrunk to so none overlap, given the center
+/// and width of each in pixels, or 1.0 or more if they already fit.
+fn label_fit(labels: &[(f32, f32)]) -> f32 {
+    labels
+        .windows(2)
+        .map(|pair| {
+            let [(from, from_width), (to, to_width)] = [pair[0], pair[1]];
+
+            ((to - from).abs() - LABEL_GAP) / ((from_width + to_width) / 2.0).max(f32::EPSILON)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(feature = "chrono")]
+/// Format seconds after the given Unix timestamp, falling back to plain seconds if the time is out
+/// of range or the format is invalid.
+fn format_time(seconds: f32, format: &str, epoch: i64) -> String {
+    use std::fmt::Write;
+
+    let whole = seconds.floor();
+    let nanos = ((seconds - whole) * 1e9) as u32;
+
+    let time = epoch
+        .checked_add(whole as i64)
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, nanos));
+
+    let mut label = String::new();
+
+    match time.map(|time| write!(label, "{}", time.format(format))) {
+        Some(Ok(())) => label,
+        _ => seconds.to_string(),
+    }
 }
 
 /// Animatable 2d line chart.
@@ -60,8 +871,8 @@ struct XyTimeConfig {
 /// 3 of which are just strings.
 ///
 ///  * `points`: A slice of tuples, arranged so that the first float is the x position, the second
-///  the y position, and the third is the time the next point is to be shown at(or in the case of
-///  the last point, the time the animation ends).
+///    the y position, and the third is the time the next point is to be shown at(or in the case of
+///    the last point, the time the animation ends).
 ///  * `x_unit`: String describing the data on the X axis.
 ///  * `y_unit`: String describing the data on the Y axis.
 ///  * `caption`: String to be shown as the caption of the chart.
@@ -69,20 +880,108 @@ struct XyTimeConfig {
 /// This will create a basic line chart with nothing fancy, which you can easily
 /// add to your egui project. You can also animate this chart with `.toggle_playback()`
 /// and adjust various parameters with the many `.set_` functions included.
+///
+/// Ranges with no width, such as when every point is the same, are widened by 10% of their value
+/// so the chart can still be drawn.
+///
+/// ```
+/// # use egui::{epaint::Shape, CentralPanel, Context};
+/// # use egui_plotter::charts::XyTimeData;
+/// let mut chart = XyTimeData::new(&[(1.0, 2.0, 0.0), (1.0, 2.0, 1.0)], "", "", "Still");
+///
+/// let ctx = Context::default();
+///
+/// let output = ctx.run(Default::default(), |ctx| {
+///     CentralPanel::default().show(ctx, |ui| chart.draw(ui));
+/// });
+///
+/// let points: Vec<_> = output
+///     .shapes
+///     .iter()
+///     .flat_map(|clipped| match &clipped.shape {
+///         Shape::Path(path) => path.points.clone(),
+///         Shape::LineSegment { points, .. } => points.to_vec(),
+///         _ => Vec::new(),
+///     })
+///     .collect();
+///
+/// let labels: Vec<_> = output
+///     .shapes
+///     .iter()
+///     .filter_map(|clipped| match &clipped.shape {
+///         Shape::Text(text) => Some(text.galley.job.text.as_str()),
+///         _ => None,
+///     })
+///     .collect();
+///
+/// assert!(points.iter().all(|point| point.x.is_finite() && point.y.is_finite()));
+/// assert!(labels.contains(&"2.0"));
+/// ```
 pub struct XyTimeData {
-    playback_start: Option<Instant>,
-    pause_start: Option<Instant>,
-    playback_speed: f32,
-    points: Arc<[(f32, f32)]>,
-    ranges: Arc<[(Range<f32>, Range<f32>)]>,
-    times: Arc<[f32]>,
+    clock: Clock,
+    shared_clock: Option<Arc<SharedClock>>,
+    pause_on_blur: bool,
+    blur_paused: bool,
+    points: Vec<(f32, f32)>,
+    /// Ranges at each keyframe, or None once points have been dropped, after which they're found
+    /// from the points themselves.
+    ranges: Option<Vec<(Range<f32>, Range<f32>)>>,
+    range_step: usize,
+    y_autoscale: AutoScale,
+    times: Vec<f32>,
+    /// Number of dropped points still at the start of `points` and `times`. They're only removed
+    /// once they make up half of them, so pushing a point takes constant time on average.
+    head: usize,
+    /// Number of points dropped since the band was last trimmed to match.
+    dropped: usize,
+    /// Whether the points changed since they were last handed to the chart.
+    stale: bool,
+    capacity: Option<usize>,
     chart: Chart<XyTimeConfig>,
 }
 
 impl XyTimeData {
     /// Create a new XyTimeData chart. See [Usage](#usage).
     pub fn new(points: &[(f32, f32, f32)], x_unit: &str, y_unit: &str, caption: &str) -> Self {
-        let mut points = points.to_vec();
+        Self::from_records(
+            points.iter().copied(),
+            |point| point,
+            x_unit,
+            y_unit,
+            caption,
+        )
+    }
+
+    /// Create a new XyTimeData chart from records of any type, using `extract` to get the X, Y
+    /// and time of each record. Avoids having to collect the data into tuples first.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// struct Sample {
+    ///     x: f32,
+    ///     y: f32,
+    ///     time: f32,
+    /// }
+    ///
+    /// let samples = vec![
+    ///     Sample { x: 0.0, y: 0.0, time: 0.0 },
+    ///     Sample { x: 1.0, y: 2.0, time: 1.0 },
+    /// ];
+    ///
+    /// let chart = XyTimeData::from_records(&samples, |s| (s.x, s.y, s.time), "m", "m", "Path");
+    /// ```
+    pub fn from_records<T, I, F>(
+        records: I,
+        extract: F,
+        x_unit: &str,
+        y_unit: &str,
+        caption: &str,
+    ) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(T) -> (f32, f32, f32),
+    {
+        let mut points: Vec<(f32, f32, f32)> = records.into_iter().map(extract).collect();
 
         // Sort by the time of the point
         points.sort_by(|a, b| {
@@ -110,34 +1009,9 @@ impl XyTimeData {
             })
             .collect();
 
-        // Ranges include the X range, Y range, and time in seconds
-        let mut ranges = Vec::<(Range<f32>, Range<f32>)>::with_capacity(points.len());
+        let ranges = expanding_ranges(&points, 1);
 
-        let mut min_x: f32 = f32::MAX;
-        let mut min_y: f32 = f32::MAX;
-        let mut max_x: f32 = f32::MIN;
-        let mut max_y: f32 = f32::MIN;
-
-        for point in &points {
-            let (x, y) = *point;
-
-            min_x = min_x.min(x);
-            min_y = min_y.min(y);
-            max_x = max_x.max(x);
-            max_y = max_y.max(y);
-
-            let range_x = min_x..max_x;
-            let range_y = min_y..max_y;
-
-            ranges.push((range_x, range_y));
-        }
-
-        // Turn all the vecs and strings into arcs since they are more or less read-only at
-        // this point
-
-        let points: Arc<[(f32, f32)]> = points.into();
-        let ranges: Arc<[(Range<f32>, Range<f32>)]> = ranges.into();
-        let times: Arc<[f32]> = times.into();
+        // Turn all the strings into arcs since they are more or less read-only at this point
 
         let x_unit: Arc<str> = x_unit.into();
         let y_unit: Arc<str> = y_unit.into();
@@ -171,7 +1045,12 @@ impl XyTimeData {
         let text_color = BLACK.to_rgba();
 
         let config = XyTimeConfig {
-            points: points.clone(),
+            points: points.as_slice().into(),
+            times: times.as_slice().into(),
+            time: *times.last().unwrap(),
+            trail: None,
+            grid_on_top: false,
+            break_on_nan: true,
             range: ranges.last().unwrap().clone(),
             line_style,
             grid_style,
@@ -183,126 +1062,298 @@ impl XyTimeData {
             y_unit,
             ratio: DEFAULT_RATIO,
             caption,
+            mirror_y: false,
+            interpolation: LineInterp::default(),
+            smoothing: Smoothing::default(),
+            upcoming: Vec::new(),
+            minor_ticks: None,
+            subtitle: None,
+            caption_style: None,
+            subtitle_style: None,
+            preserve_aspect: true,
+            grid_fade: None,
+            x_label_rotation: FontTransform::None,
+            label_collision: Collision::Overlap,
+            y_label_rotation: FontTransform::None,
+            zero_axis_style: None,
+            band: None,
+            highlight: None,
+            now_marker: None,
+            now: None,
+            mapping: MappingRecorder::new(),
+            #[cfg(feature = "chrono")]
+            time_format: None,
         };
 
-        let chart = Chart::new(config)
+        let mut chart = Chart::new(config)
             .mouse(MouseConfig::enabled())
-            .builder_cb(Box::new(|area, _t, data| {
-                let area_ratio = {
-                    let (x_range, y_range) = area.get_pixel_range();
-
-                    let x_delta =
-                        ((x_range.end - x_range.start).abs() - (X_MARGIN * 2) - LABEL_AREA) as f32;
-                    let y_delta = ((y_range.end - y_range.start).abs()
-                        - (Y_MARGIN * 2)
-                        - LABEL_AREA
-                        - CAPTION_SIZE) as f32;
-
-                    x_delta / y_delta
-                };
-
-                // Return if the ratio is invalid(meaning the chart can't be drawn)
-                if !area_ratio.is_finite() {
-                    return;
-                }
-
-                let (x_range, y_range) = data.range.clone();
-
-                // The data ratio is inverse, as if our X range is smaller we
-                // want to make sure the X axis is expanded to compensate
-                let data_ratio = {
-                    let x_delta = (x_range.end - x_range.start).abs();
-                    let y_delta = (y_range.end - y_range.start).abs();
-
-                    y_delta / x_delta
-                };
-
-                let display_ratio = data.ratio * data_ratio * area_ratio;
-
-                let (x_range, y_range) =
-                    match display_ratio.partial_cmp(&1.0).unwrap_or(Ordering::Equal) {
-                        Ordering::Equal => (x_range, y_range),
-                        Ordering::Greater => (mult_range(x_range, display_ratio), y_range),
-                        Ordering::Less => (x_range, mult_range(y_range, 1.0 / display_ratio)),
-                    };
-
-                let font_style = FontStyle::Normal;
-                let font_family = FontFamily::Monospace;
-                let font_size = CAPTION_SIZE;
-
-                let font_desc = FontDesc::new(font_family, font_size as f64, font_style);
-
-                let text_style = TextStyle::from(font_desc).color(&data.text_color);
-
-                let mut chart = ChartBuilder::on(area)
-                    .caption(data.caption.clone(), text_style.clone())
-                    .x_label_area_size(LABEL_AREA)
-                    .y_label_area_size(LABEL_AREA)
-                    .margin_left(X_MARGIN)
-                    .margin_right(X_MARGIN)
-                    .margin_top(Y_MARGIN)
-                    .margin_bottom(Y_MARGIN)
-                    .build_cartesian_2d(x_range, y_range)
-                    .unwrap();
-
-                chart
-                    .configure_mesh()
-                    .label_style(text_style.clone())
-                    .bold_line_style(data.grid_style)
-                    .light_line_style(data.subgrid_style)
-                    .axis_style(data.axes_style)
-                    .x_desc(&data.x_unit.to_string())
-                    .set_all_tick_mark_size(4)
-                    .y_desc(&data.y_unit.to_string())
-                    .draw()
-                    .unwrap();
-
-                chart
-                    .draw_series(LineSeries::new(data.points.to_vec(), data.line_style))
-                    .unwrap();
+            .min_size(MIN_CHART_SIZE)
+            .builder_cb(Box::new(|area, t, data| {
+                data.draw(area, t.scale).unwrap();
             }));
 
+        chart.get_data_mut().mapping = chart.mapping_recorder();
+
         Self {
-            playback_start: None,
-            pause_start: None,
-            playback_speed: 1.0,
+            clock: Clock::default(),
+            shared_clock: None,
+            pause_on_blur: false,
+            blur_paused: false,
             points,
-            ranges,
+            ranges: Some(ranges),
+            range_step: 1,
+            y_autoscale: AutoScale::default(),
             times,
+            head: 0,
+            dropped: 0,
+            stale: false,
+            capacity: None,
             chart,
         }
     }
 
-    /// Set the time to resume playback at. Time is in seconds.
-    pub fn set_time(&mut self, time: f32) {
-        let start_time = Some(Instant::now() - Duration::from_secs_f32(time));
-        match self.playback_start {
-            Some(_) => {
-                if let Some(_) = self.pause_start {
-                    self.pause_start = Some(Instant::now());
-                }
+    /// Store the ranges the chart expands through during playback at the given number of evenly
+    /// spaced keyframes, rather than one per point, interpolating between them. With a million
+    /// points this cuts the memory taken by ranges from 16 MB to a few kilobytes.
+    /// Between keyframes the range only approximates the points shown so far, so the newest
+    /// points can briefly overshoot the edge of the chart; more keyframes means less overshoot.
+    /// Passing 0 or at least as many keyframes as points keeps one per point, which is the
+    /// default.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points: Vec<(f32, f32, f32)> = (0..1_000_000)
+    ///     .map(|i| i as f32 / 1_000.0)
+    ///     .map(|t| (t.cos() * t, t.sin() * t, t))
+    ///     .collect();
+    ///
+    /// let chart = XyTimeData::new(&points, "m", "m", "Spiral").with_range_keyframes(1_000);
+    /// ```
+    pub fn with_range_keyframes(mut self, keyframes: usize) -> Self {
+        let len = self.points().len();
+
+        self.range_step = match keyframes {
+            0 => 1,
+            keyframes => len.div_ceil(keyframes).max(1),
+        };
+        self.ranges = Some(expanding_ranges(self.points(), self.range_step));
+        self.refresh_range();
 
-                self.playback_start = start_time;
-            }
-            None => {
-                self.playback_start = start_time;
-                self.pause_start = Some(Instant::now());
-            }
-        }
+        self
     }
 
-    #[inline]
-    /// Set the time to resume playback at. Time is in seconds. Consumes self.
+    /// Append a point to the chart, for charts fed with live data. Points are expected to arrive
+    /// in time order, which takes constant time on average, but one older than the newest point
+    /// is still placed in time order, with a NaN band value inserted along with it. If a capacity
+    /// is set, the oldest points past it are dropped, keeping the playhead at the same time.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let mut chart = XyTimeData::new(&[(0.0, 0.0, 0.0)], "m", "m", "Live").capacity(Some(2));
+    ///
+    /// chart.push_point((1.0, 1.0, 1.0));
+    /// chart.set_time(1.5);
+    /// chart.push_point((2.0, 4.0, 2.0));
+    ///
+    /// assert_eq!(chart.points(), &[(1.0, 1.0), (2.0, 4.0)]);
+    /// assert_eq!(chart.times(), &[1.0, 2.0]);
+    /// assert!((chart.current_time() - 1.5).abs() < 0.001);
+    /// ```
+    pub fn push_point(&mut self, point: (f32, f32, f32)) {
+        let (x, y, time) = point;
+        let index = self.times().partition_point(|probe| *probe <= time);
+
+        if index == self.times().len() {
+            self.points.push((x, y));
+            self.times.push(time);
+
+            if let Some(ranges) = &mut self.ranges {
+                push_range(ranges, index, self.range_step, (x, y));
+            }
+        } else {
+            self.points.insert(self.head + index, (x, y));
+            self.times.insert(self.head + index, time);
+
+            if self.ranges.is_some() {
+                self.ranges = Some(expanding_ranges(self.points(), self.range_step));
+            }
+
+            self.trim_band();
+
+            let config = self.chart.get_data_mut();
+
+            if let Some((lower, upper, _)) = &mut config.band {
+                for values in [lower, upper] {
+                    if index < values.len() {
+                        let mut inserted = values.to_vec();
+                        inserted.insert(index, f32::NAN);
+
+                        *values = inserted.into();
+                    }
+                }
+            }
+
+            config.highlight = config.highlight.map(|highlight| match highlight >= index {
+                true => highlight + 1,
+                false => highlight,
+            });
+        }
+
+        self.drop_excess();
+        self.stale = true;
+    }
+
+    /// Set the most points the chart holds, dropping the oldest points whenever there are more,
+    /// so charts running indefinitely don't keep growing. Dropped points can't be recovered.
+    /// Set to None to keep every point, which is the default. A capacity of 0 keeps 1 point.
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity.map(|capacity| capacity.max(1));
+
+        self.drop_excess();
+        self.stale = true;
+    }
+
+    #[inline]
+    /// Set the most points the chart holds, dropping the oldest points. Consumes self.
+    pub fn capacity(mut self, capacity: Option<usize>) -> Self {
+        self.set_capacity(capacity);
+
+        self
+    }
+
+    /// Drop the oldest points over capacity. The chart's own clock is moved back by as much as
+    /// the start time moves forward, so the playhead stays at the same time; a shared clock is
+    /// left alone for the other charts using it.
+    fn drop_excess(&mut self) {
+        let excess = self
+            .capacity
+            .map_or(0, |capacity| self.times().len().saturating_sub(capacity));
+
+        if excess == 0 {
+            return;
+        }
+
+        let start_time = self.start_time();
+
+        self.head += excess;
+        self.dropped += excess;
+        self.ranges = None;
+
+        if self.head * 2 >= self.points.len() {
+            self.points.drain(..self.head);
+            self.times.drain(..self.head);
+            self.head = 0;
+        }
+
+        let config = self.chart.get_data_mut();
+        config.highlight = config.highlight.and_then(|index| index.checked_sub(excess));
+
+        let shift = self.start_time() - start_time;
+
+        if let (None, Some(elapsed)) = (&self.shared_clock, self.clock.elapsed()) {
+            self.clock.set_time((elapsed - shift as f64).max(0.0));
+        }
+    }
+
+    /// Drop the band values of the points dropped since it was last trimmed.
+    fn trim_band(&mut self) {
+        let dropped = std::mem::take(&mut self.dropped);
+
+        if let Some((lower, upper, _)) = &mut self.chart.get_data_mut().band {
+            *lower = lower[dropped.min(lower.len())..].into();
+            *upper = upper[dropped.min(upper.len())..].into();
+        }
+    }
+
+    /// Hand the points to the chart if they changed since it was last drawn. While playback is
+    /// running, the shown points are picked from them on each draw instead.
+    fn sync_points(&mut self) {
+        if self.dropped > 0 {
+            self.trim_band();
+        }
+
+        if !self.stale || self.with_clock(|clock| clock.is_started()) {
+            return;
+        }
+
+        self.stale = false;
+
+        let config = self.chart.get_data_mut();
+
+        config.points = self.points[self.head..].into();
+        config.times = self.times[self.head..].into();
+        config.time = *self.times.last().unwrap();
+        config.upcoming.clear();
+
+        self.refresh_range();
+    }
+
+    /// Set the time to resume playback at. Time is in seconds since the chart's start time.
+    pub fn set_time(&mut self, time: f32) {
+        self.with_clock(|clock| clock.set_time(time as f64))
+    }
+
+    #[inline]
+    /// Set the time to resume playback at. Time is in seconds. Consumes self.
     pub fn time(mut self, time: f32) -> Self {
         self.set_time(time);
 
         self
     }
 
+    /// Jump to a time and pause playback there, so every following `draw` renders exactly that
+    /// frame until playback is resumed, however much time passes between frames. Useful for
+    /// screenshots of a particular moment. Unlike `set_time`, the time is a timestamp of the
+    /// data, clamped to the chart's start and end times, rather than the time elapsed since the
+    /// start.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(0.0, 0.0, 10.0), (1.0, 1.0, 12.0), (2.0, 4.0, 14.0)];
+    /// let mut chart = XyTimeData::new(&points, "m", "m", "Parabola");
+    ///
+    /// chart.start_playback();
+    /// chart.seek_and_pause(13.0);
+    /// chart.advance(1.0);
+    ///
+    /// assert!(!chart.is_playing());
+    /// assert!((chart.current_time() - 13.0).abs() < 0.001);
+    ///
+    /// chart.seek_and_pause(14.0);
+    ///
+    /// assert!((chart.current_time() - 14.0).abs() < 0.001);
+    /// assert!(!chart.is_playing());
+    /// ```
+    pub fn seek_and_pause(&mut self, time: f32) {
+        let time_start = self.start_time();
+        let elapsed = (time.clamp(time_start, self.end_time()) - time_start - MIN_DELTA).max(0.0);
+
+        self.with_clock(|clock| {
+            clock.pause();
+            clock.set_time(elapsed as f64);
+        })
+    }
+
     #[inline]
-    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half.
+    /// Set the playback speed. 1.0 is normal speed, 2.0 is double, & 0.5 is half. Speeds must be
+    /// positive: 0.0 pauses playback, and negative or non-finite speeds are ignored.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(0.0, 0.0, 0.0), (1.0, 1.0, 10.0)];
+    /// let mut chart = XyTimeData::new(&points, "m", "m", "Steps");
+    ///
+    /// chart.start_playback();
+    /// chart.advance(1.0);
+    /// chart.set_playback_speed(0.0);
+    /// chart.advance(1.0);
+    ///
+    /// assert!(!chart.is_playing());
+    /// assert!((chart.current_time() - 1.0).abs() < 0.001);
+    /// assert_eq!(chart.get_playback_speed(), 1.0);
+    /// ```
     pub fn set_playback_speed(&mut self, speed: f32) {
-        self.playback_speed = speed;
+        self.with_clock(|clock| clock.set_playback_speed(speed))
     }
 
     #[inline]
@@ -313,6 +1364,46 @@ impl XyTimeData {
         self
     }
 
+    #[inline]
+    /// Share a playback clock with other charts, so they play, pause and seek together. The
+    /// chart's playback functions control the shared clock while one is set. See
+    /// [`SharedClock`].
+    pub fn set_clock(&mut self, clock: Option<Arc<SharedClock>>) {
+        self.shared_clock = clock
+    }
+
+    #[inline]
+    /// Share a playback clock with other charts. Consumes self.
+    pub fn clock(mut self, clock: Option<Arc<SharedClock>>) -> Self {
+        self.set_clock(clock);
+
+        self
+    }
+
+    #[inline]
+    /// Report to an animation handle whenever the chart is drawn while playing, animating or
+    /// being interacted with, or stop reporting with None. See [`AnimationHandle`].
+    pub fn set_animation_handle(&mut self, animation: Option<AnimationHandle>) {
+        self.chart.set_animation_handle(animation)
+    }
+
+    #[inline]
+    /// Report to an animation handle while playing, animating or being interacted with.
+    /// Consumes self.
+    pub fn animation_handle(mut self, animation: Option<AnimationHandle>) -> Self {
+        self.set_animation_handle(animation);
+
+        self
+    }
+
+    /// Run a function on the clock driving playback, the shared one if set.
+    fn with_clock<T>(&mut self, f: impl FnOnce(&mut Clock) -> T) -> T {
+        match &self.shared_clock {
+            Some(shared_clock) => shared_clock.with(f),
+            None => f(&mut self.clock),
+        }
+    }
+
     /// Set the style of the plotted line.
     pub fn set_line_style(&mut self, line_style: ShapeStyle) {
         self.chart.get_data_mut().line_style = line_style;
@@ -365,6 +1456,131 @@ impl XyTimeData {
         self
     }
 
+    #[cfg(feature = "chrono")]
+    #[inline]
+    /// Format the X axis labels as UTC timestamps using a chrono format string, such as
+    /// `"%H:%M:%S"`. X values are treated as seconds after `epoch`, a Unix timestamp. Keeping
+    /// X values small and relative to an epoch avoids the rounding that large f32 timestamps
+    /// suffer from. Pass None to label X with plain values again.
+    pub fn set_time_format(&mut self, format: Option<&str>, epoch: i64) {
+        self.chart.get_data_mut().time_format = format.map(|format| (format.into(), epoch))
+    }
+
+    #[cfg(feature = "chrono")]
+    #[inline]
+    /// Format the X axis labels as UTC timestamps using a chrono format string. Consumes self.
+    pub fn time_format(mut self, format: Option<&str>, epoch: i64) -> Self {
+        self.set_time_format(format, epoch);
+
+        self
+    }
+
+    #[inline]
+    /// Only show points added in the given duration of chart time before the current time, fading
+    /// them out as they age to leave a trail behind the newest point. Pass None to show every
+    /// point at full opacity, which is the default.
+    pub fn set_trail(&mut self, trail: Option<Duration>) {
+        self.chart.get_data_mut().trail = trail;
+        self.refresh_range();
+    }
+
+    #[inline]
+    /// Only show points added in the given duration of chart time, fading them out as they age.
+    /// Consumes self.
+    pub fn trail(mut self, trail: Option<Duration>) -> Self {
+        self.set_trail(trail);
+
+        self
+    }
+
+    #[inline]
+    /// Set whether points with NaN or infinite coordinates, such as gaps in sensor data, break
+    /// the line in two. If false they're dropped and the points either side are joined instead.
+    /// True by default.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(0.0, 1.0, 0.0), (1.0, f32::NAN, 1.0), (2.0, 3.0, 2.0), (3.0, 2.0, 3.0)];
+    ///
+    /// let chart = XyTimeData::new(&points, "m", "m", "Gap").break_on_nan(true);
+    ///
+    /// chart.export_frame(3.0, (320, 240), 1.0).unwrap();
+    /// ```
+    pub fn set_break_on_nan(&mut self, break_on_nan: bool) {
+        self.chart.get_data_mut().break_on_nan = break_on_nan
+    }
+
+    #[inline]
+    /// Set whether points with NaN or infinite coordinates break the line in two. Consumes self.
+    pub fn break_on_nan(mut self, break_on_nan: bool) -> Self {
+        self.set_break_on_nan(break_on_nan);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the lines drawn at x=0 and y=0 when they're in view, or None to not draw
+    /// them, which is the default.
+    pub fn set_zero_axis_style(&mut self, zero_axis_style: Option<ShapeStyle>) {
+        self.chart.get_data_mut().zero_axis_style = zero_axis_style
+    }
+
+    #[inline]
+    /// Set the style of the lines drawn at x=0 and y=0. Consumes self.
+    pub fn zero_axis_style(mut self, zero_axis_style: Option<ShapeStyle>) -> Self {
+        self.set_zero_axis_style(zero_axis_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of a vertical line marking how far playback has reached, at the X
+    /// coordinate of the line at the current time. It moves smoothly between points, stays put
+    /// while paused and is hidden while playback is stopped. None, the default, doesn't draw it.
+    pub fn set_now_marker(&mut self, now_marker: Option<ShapeStyle>) {
+        self.chart.get_data_mut().now_marker = now_marker
+    }
+
+    #[inline]
+    /// Set the style of a vertical line marking how far playback has reached. Consumes self.
+    pub fn now_marker(mut self, now_marker: Option<ShapeStyle>) -> Self {
+        self.set_now_marker(now_marker);
+
+        self
+    }
+
+    #[inline]
+    /// Shade the band between a lower and an upper series around the line, such as a confidence
+    /// interval, filled with the given style behind the line. The values are matched to the
+    /// points by index, in order of time, and like the line only the points shown so far are
+    /// filled. Pass None to not draw a band, which is the default.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// # use plotters::style::{Color, BLUE};
+    /// let points = [(0.0, 1.0, 0.0), (1.0, 2.0, 1.0), (2.0, 1.5, 2.0)];
+    ///
+    /// let chart = XyTimeData::new(&points, "s", "m", "Forecast").band(Some((
+    ///     [0.5, 1.2, 0.5].into(),
+    ///     [1.5, 2.8, 2.5].into(),
+    ///     BLUE.mix(0.2).filled(),
+    /// )));
+    ///
+    /// chart.export_frame(2.0, (320, 240), 1.0).unwrap();
+    /// ```
+    pub fn set_band(&mut self, band: Option<Band>) {
+        self.dropped = 0;
+        self.chart.get_data_mut().band = band
+    }
+
+    #[inline]
+    /// Shade the band between a lower and an upper series around the line. Consumes self.
+    pub fn band(mut self, band: Option<Band>) -> Self {
+        self.set_band(band);
+
+        self
+    }
+
     /// Set the text color of the chart.
     pub fn set_text_color<T>(&mut self, color: T)
     where
@@ -421,84 +1637,699 @@ impl XyTimeData {
         self
     }
 
+    #[inline]
+    /// Set whether the ranges are expanded to keep the ratio between X and Y units, which is the
+    /// default. When disabled the data fills all of the available space and the ratio is ignored.
+    pub fn set_preserve_aspect(&mut self, preserve_aspect: bool) {
+        self.chart.get_data_mut().preserve_aspect = preserve_aspect
+    }
+
+    #[inline]
+    /// Set whether the ranges are expanded to keep the ratio between X and Y units. Consumes
+    /// self.
+    pub fn preserve_aspect(mut self, preserve_aspect: bool) -> Self {
+        self.set_preserve_aspect(preserve_aspect);
+
+        self
+    }
+
+    #[inline]
+    /// Set the rotation of the X axis labels, which can help when they overlap. Plotters only
+    /// turns text in quarter turns, so labels can be put on their side or upside down but not
+    /// tilted at any other angle. Labels turned on their side are given enough space below the
+    /// chart for the widest of them.
+    pub fn set_x_label_rotation(&mut self, rotation: FontTransform) {
+        self.chart.get_data_mut().x_label_rotation = rotation
+    }
+
+    #[inline]
+    /// Set the rotation of the X axis labels. Consumes self.
+    pub fn x_label_rotation(mut self, rotation: FontTransform) -> Self {
+        self.set_x_label_rotation(rotation);
+
+        self
+    }
+
+    #[inline]
+    /// Set what's done with X axis labels that would overlap each other, such as when the chart
+    /// is narrow. Labels are measured as they're drawn, so this adapts to the chart's size.
+    /// Defaults to `Collision::Overlap`, drawing every label. Has no effect on labels already
+    /// turned on their side with `set_x_label_rotation`.
+    pub fn set_label_collision(&mut self, label_collision: Collision) {
+        self.chart.get_data_mut().label_collision = label_collision
+    }
+
+    #[inline]
+    /// Set what's done with X axis labels that would overlap each other. Consumes self.
+    pub fn label_collision(mut self, label_collision: Collision) -> Self {
+        self.set_label_collision(label_collision);
+
+        self
+    }
+
+    #[inline]
+    /// Set the rotation of the Y axis labels.
+    pub fn set_y_label_rotation(&mut self, rotation: FontTransform) {
+        self.chart.get_data_mut().y_label_rotation = rotation
+    }
+
+    #[inline]
+    /// Set the rotation of the Y axis labels. Consumes self.
+    pub fn y_label_rotation(mut self, rotation: FontTransform) -> Self {
+        self.set_y_label_rotation(rotation);
+
+        self
+    }
+
+    #[inline]
+    /// Draw the grid over the data rather than behind it, so it stays visible over thick lines.
+    /// Behind by default.
+    pub fn set_grid_on_top(&mut self, grid_on_top: bool) {
+        self.chart.get_data_mut().grid_on_top = grid_on_top
+    }
+
+    #[inline]
+    /// Draw the grid over the data rather than behind it. Consumes self.
+    pub fn grid_on_top(mut self, grid_on_top: bool) -> Self {
+        self.set_grid_on_top(grid_on_top);
+
+        self
+    }
+
+    #[inline]
+    /// Fade out the minor grid as the chart is zoomed out, starting when its lines are twice the
+    /// given spacing in pixels apart and hiding it once they're closer than the spacing. Disabled
+    /// by default.
+    pub fn set_grid_fade(&mut self, min_spacing: Option<f32>) {
+        self.chart.get_data_mut().grid_fade = min_spacing
+    }
+
+    #[inline]
+    /// Fade out the minor grid as the chart is zoomed out. Consumes self.
+    pub fn grid_fade(mut self, min_spacing: Option<f32>) -> Self {
+        self.set_grid_fade(min_spacing);
+
+        self
+    }
+
+    #[inline]
+    /// Mirror the Y axis so the origin is at the top and Y increases downwards. Dragging and
+    /// zooming are applied in screen space, so they behave the same with or without mirroring.
+    pub fn set_mirror_y(&mut self, mirror_y: bool) {
+        self.chart.get_data_mut().mirror_y = mirror_y
+    }
+
+    #[inline]
+    /// Mirror the Y axis so the origin is at the top and Y increases downwards. Consumes self.
+    pub fn mirror_y(mut self, mirror_y: bool) -> Self {
+        self.set_mirror_y(mirror_y);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the Y axis is scaled to fit the data. Defaults to `AutoScale::Expanding`, which
+    /// never shrinks, so a single outlier in live data keeps the chart zoomed out; use
+    /// `AutoScale::Window` with a trail to fit only the recent data instead.
+    pub fn set_y_autoscale(&mut self, y_autoscale: AutoScale) {
+        self.y_autoscale = y_autoscale;
+        self.refresh_range();
+    }
+
+    #[inline]
+    /// Set how the Y axis is scaled to fit the data. Consumes self.
+    pub fn y_autoscale(mut self, y_autoscale: AutoScale) -> Self {
+        self.set_y_autoscale(y_autoscale);
+
+        self
+    }
+
+    #[inline]
+    /// Set how consecutive points are connected. Stepped lines are useful for discrete signals.
+    pub fn set_interpolation(&mut self, interpolation: LineInterp) {
+        self.chart.get_data_mut().interpolation = interpolation
+    }
+
+    #[inline]
+    /// Set how consecutive points are connected. Consumes self.
+    pub fn interpolation(mut self, interpolation: LineInterp) -> Self {
+        self.set_interpolation(interpolation);
+
+        self
+    }
+
+    #[inline]
+    /// Set how the line is smoothed between points, such as with a Catmull-Rom curve to smooth
+    /// out sparse data. Only linearly interpolated lines are smoothed, stepped lines are drawn
+    /// as they are. During playback the curve is revealed up to the current time, partway
+    /// through the segment being drawn. Defaults to `Smoothing::None`.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::{Smoothing, XyTimeData};
+    /// let points = [(0.0, 0.0, 0.0), (1.0, 2.0, 1.0), (2.0, 1.0, 2.0)];
+    ///
+    /// let chart = XyTimeData::new(&points, "m", "m", "Smooth")
+    ///     .smoothing(Smoothing::CatmullRom { samples: 8 });
+    /// ```
+    pub fn set_smoothing(&mut self, smoothing: Smoothing) {
+        self.chart.get_data_mut().smoothing = smoothing
+    }
+
+    #[inline]
+    /// Set how the line is smoothed between points. Consumes self.
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.set_smoothing(smoothing);
+
+        self
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines drawn between major grid lines. The minor grid
+    /// lines are drawn using the subgrid style, so 0 hides the subgrid entirely.
+    pub fn set_minor_ticks(&mut self, minor_ticks: usize) {
+        self.chart.get_data_mut().minor_ticks = Some(minor_ticks)
+    }
+
+    #[inline]
+    /// Set the maximum number of minor grid lines drawn between major grid lines. Consumes self.
+    pub fn minor_ticks(mut self, minor_ticks: usize) -> Self {
+        self.set_minor_ticks(minor_ticks);
+
+        self
+    }
+
+    #[inline]
+    /// Set the subtitle shown below the caption.
+    pub fn set_subtitle(&mut self, subtitle: &str) {
+        self.chart.get_data_mut().subtitle = Some(subtitle.into())
+    }
+
+    #[inline]
+    /// Set the subtitle shown below the caption. Consumes self.
+    pub fn subtitle(mut self, subtitle: &str) -> Self {
+        self.set_subtitle(subtitle);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the caption. By default the caption uses the same style as the labels.
+    pub fn set_caption_style(&mut self, caption_style: TextStyle<'static>) {
+        self.chart.get_data_mut().caption_style = Some(caption_style)
+    }
+
+    #[inline]
+    /// Set the style of the caption. Consumes self.
+    pub fn caption_style(mut self, caption_style: TextStyle<'static>) -> Self {
+        self.set_caption_style(caption_style);
+
+        self
+    }
+
+    #[inline]
+    /// Set the style of the subtitle. By default the subtitle uses the same style as the labels.
+    pub fn set_subtitle_style(&mut self, subtitle_style: TextStyle<'static>) {
+        self.chart.get_data_mut().subtitle_style = Some(subtitle_style)
+    }
+
+    #[inline]
+    /// Set the style of the subtitle. Consumes self.
+    pub fn subtitle_style(mut self, subtitle_style: TextStyle<'static>) -> Self {
+        self.set_subtitle_style(subtitle_style);
+
+        self
+    }
+
     /// Draw the chart to a Ui. Will also proceed to animate the chart if playback is currently
     /// enabled.
     pub fn draw(&mut self, ui: &Ui) {
-        if let Some(_) = self.playback_start {
-            let time = self.current_time();
+        if self.pause_on_blur {
+            self.handle_focus(ui.input(|input| input.focused));
+        }
 
-            let time_index = match self
-                .times
-                .binary_search_by(|probe| probe.partial_cmp(&time).unwrap_or(Ordering::Equal))
-            {
-                Ok(index) => index,
-                Err(index) => self.points.len().min(index),
-            };
+        self.with_clock(|clock| clock.tick());
+        self.sync_points();
+
+        // Playback changes the chart every frame
+        if self.is_playing() {
+            self.chart.mark_active();
+        }
+
+        if self.with_clock(|clock| clock.is_started()) {
+            let time = self.current_time();
+            let time_index = self.time_index(time);
 
             // The time index is always a valid index, so ensure the range is inclusive
-            let points = &self.points[..=time_index];
-            let range = self.ranges[time_index].clone();
+            let points = self.points()[..=time_index].into();
+            let times = self.times()[..=time_index].into();
+            let upcoming = self.upcoming(time_index);
+            let range = self.range_at(time, time_index);
+
+            let now = self.position_at(time);
 
             let config = self.chart.get_data_mut();
-            config.points = points.into();
+            config.points = points;
+            config.times = times;
+            config.upcoming = upcoming;
+            config.time = time;
             config.range = range;
+            config.now = now;
+        } else if self.chart.get_data().now.is_some() {
+            self.chart.get_data_mut().now = None;
         }
 
         self.chart.draw(ui);
     }
 
+    #[inline]
+    /// Get the rect the chart was drawn in during the last call to `draw`, in screen coordinates.
+    /// Returns None if the chart hasn't been drawn yet.
+    pub fn drawing_rect(&self) -> Option<Rect> {
+        self.chart.drawing_rect()
+    }
+
+    /// Return the index of the point nearest to the given position on screen, out of the points
+    /// revealed at the current time. Returns None if the position is outside the chart, or the
+    /// chart hasn't been drawn yet. Pass the index to `set_highlight_index` on other charts to
+    /// highlight the same point in each of them.
+    pub fn hovered_index(&self, pointer: Pos2) -> Option<usize> {
+        if !self.drawing_rect()?.contains(pointer) {
+            return None;
+        }
+
+        self.chart
+            .get_data()
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, (x, y))| x.is_finite() && y.is_finite())
+            .filter_map(|(index, (x, y))| {
+                let pos = self.chart.data_to_screen((*x as f64, *y as f64))?;
+
+                Some((index, pos.distance_sq(pointer)))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    #[inline]
+    /// Emphasize the point at the given index with a marker, wherever the mouse is, once it's
+    /// revealed. Pass None to not highlight any point, which is the default.
+    pub fn set_highlight_index(&mut self, index: Option<usize>) {
+        self.chart.get_data_mut().highlight = index
+    }
+
+    #[inline]
+    /// Emphasize the point at the given index with a marker. Consumes self.
+    pub fn highlight_index(mut self, index: Option<usize>) -> Self {
+        self.set_highlight_index(index);
+
+        self
+    }
+
+    /// Render the chart as it would appear at the given time into a PNG image. The chart is laid
+    /// out at the given size and enlarged by `scale`, so a scale of 2.0 gives an image twice as
+    /// wide and tall with sharper lines and text, for high DPI displays or print. Pan and zoom
+    /// applied by the mouse are not included in the export.
+    ///
+    /// **Ensure the `export` feature is enabled to use this function.**
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// # use image::GenericImageView;
+    /// let points = [(0.0, 1.0, 0.0), (1.0, 2.0, 1.0), (2.0, 0.5, 2.0)];
+    ///
+    /// let chart = XyTimeData::new(&points, "m", "m", "Export");
+    ///
+    /// let png = chart.export_frame(2.0, (320, 240), 2.0).unwrap();
+    ///
+    /// assert_eq!(image::load_from_memory(&png).unwrap().dimensions(), (640, 480));
+    /// ```
+    #[cfg(feature = "export")]
+    pub fn export_frame(
+        &self,
+        time: f32,
+        size: (u32, u32),
+        scale: f32,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+        use plotters::prelude::{BitMapBackend, IntoDrawingArea};
+
+        use super::scaled::ScaledBackend;
+
+        let scale = scale.max(f32::EPSILON) as f64;
+        let width = (size.0 as f64 * scale).round() as u32;
+        let height = (size.1 as f64 * scale).round() as u32;
+        let time_index = self.time_index(time);
+
+        let mut config = self.chart.get_data().clone();
+        // Keep the mapping of the chart on screen rather than recording the exported one
+        config.mapping = MappingRecorder::new();
+        config.points = self.points()[..=time_index].into();
+        config.times = self.times()[..=time_index].into();
+        config.upcoming = self.upcoming(time_index);
+
+        if let Some((lower, upper, _)) = &mut config.band {
+            *lower = lower[self.dropped.min(lower.len())..].into();
+            *upper = upper[self.dropped.min(upper.len())..].into();
+        }
+
+        config.time = time;
+        config.range = self.range_at(time, time_index);
+        config.now = self.position_at(time);
+
+        let mut buffer = vec![0u8; width as usize * height as usize * 3];
+
+        {
+            let backend = BitMapBackend::with_buffer(&mut buffer, (width, height));
+            let area = ScaledBackend::new(backend, scale).into_drawing_area();
+
+            area.fill(&config.background_color)?;
+            config.draw(&area, 1.0)?;
+            area.present()?;
+        }
+
+        let mut png = Vec::new();
+
+        PngEncoder::new(&mut png).write_image(&buffer, width, height, ColorType::Rgb8)?;
+
+        Ok(png)
+    }
+
+    /// Return the X and Y ranges to show at the given time, where `time_index` is the index of the
+    /// last point shown.
+    fn range_at(&self, time: f32, time_index: usize) -> (Range<f32>, Range<f32>) {
+        let (x_range, y_range) = self.expanding_range(time_index);
+
+        let y_range = match self.y_autoscale {
+            AutoScale::Fixed(min, max) => min..max,
+            AutoScale::Expanding => y_range,
+            AutoScale::Window => {
+                let start = match self.chart.get_data().trail {
+                    Some(trail) => time - trail.as_secs_f32(),
+                    None => f32::NEG_INFINITY,
+                };
+
+                self.points()[..=time_index]
+                    .iter()
+                    .zip(&self.times()[..=time_index])
+                    .filter(|((_, y), time)| **time >= start && y.is_finite())
+                    .map(|((_, y), _)| *y..*y)
+                    .reduce(|range, y| range.start.min(y.start)..range.end.max(y.end))
+                    .unwrap_or(y_range)
+            }
+        };
+
+        // The line is drawn on towards the next point, past the last point shown
+        let Some((x, y)) = self
+            .point_at(time)
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+        else {
+            return (x_range, y_range);
+        };
+
+        let x_range = x_range.start.min(x)..x_range.end.max(x);
+        let y_range = match self.y_autoscale {
+            AutoScale::Fixed(..) => y_range,
+            _ => y_range.start.min(y)..y_range.end.max(y),
+        };
+
+        (x_range, y_range)
+    }
+
+    /// Return the points after the given index that shape the end of the line, along with their
+    /// times. The first is the point the line is heading towards, and the second bends a smoothed
+    /// line into it.
+    fn upcoming(&self, time_index: usize) -> Vec<((f32, f32), f32)> {
+        let end = (time_index + 3).min(self.points().len());
+
+        self.points()[time_index + 1..end]
+            .iter()
+            .copied()
+            .zip(self.times()[time_index + 1..end].iter().copied())
+            .collect()
+    }
+
+    /// Return the ranges fitting every point up to the given index, interpolating between the
+    /// keyframes either side.
+    fn expanding_range(&self, index: usize) -> (Range<f32>, Range<f32>) {
+        let Some(ranges) = &self.ranges else {
+            return expanding_ranges(&self.points()[..=index], usize::MAX)
+                .pop()
+                .unwrap();
+        };
+
+        let key = index / self.range_step;
+        let (x_range, y_range) = ranges[key].clone();
+
+        let Some((next_x, next_y)) = ranges.get(key + 1) else {
+            return (x_range, y_range);
+        };
+
+        let key_index = key * self.range_step;
+        let next_index = ((key + 1) * self.range_step).min(self.points().len() - 1);
+        let t = (index - key_index) as f32 / (next_index - key_index) as f32;
+
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+
+        (
+            lerp(x_range.start, next_x.start)..lerp(x_range.end, next_x.end),
+            lerp(y_range.start, next_y.start)..lerp(y_range.end, next_y.end),
+        )
+    }
+
+    /// Update the range shown while playback is stopped, after a setting it depends on changed.
+    fn refresh_range(&mut self) {
+        let time = self.chart.get_data().time;
+        let range = self.range_at(time, self.time_index(time));
+
+        self.chart.get_data_mut().range = range;
+    }
+
+    /// Return the X coordinate of the line at the given time, interpolated between the points
+    /// either side. Returns None if it isn't finite.
+    fn position_at(&self, time: f32) -> Option<f32> {
+        let (x, _) = self.point_at(time)?;
+
+        x.is_finite().then_some(x)
+    }
+
+    /// Return the end of the line at the given time, interpolated between the points either side.
+    /// Returns None if there are no points.
+    fn point_at(&self, time: f32) -> Option<(f32, f32)> {
+        let next = self.times().partition_point(|probe| *probe <= time);
+
+        let point = match (next.checked_sub(1), self.points().get(next)) {
+            (Some(previous), Some((next_x, next_y))) => {
+                let (previous_x, previous_y) = self.points()[previous];
+                let (start, end) = (self.times()[previous], self.times()[next]);
+
+                let t = match end > start {
+                    true => (time - start) / (end - start),
+                    false => 1.0,
+                };
+
+                (
+                    previous_x + (next_x - previous_x) * t,
+                    previous_y + (next_y - previous_y) * t,
+                )
+            }
+            (Some(previous), None) => self.points()[previous],
+            (None, _) => *self.points().first()?,
+        };
+
+        Some(point)
+    }
+
+    /// Return the index of the last point to be shown at the given time, the last point at or
+    /// before the time. Points sharing a timestamp are revealed together, and the first point is
+    /// always shown so there's something to draw.
+    fn time_index(&self, time: f32) -> usize {
+        self.times().partition_point(|probe| *probe <= time).max(1) - 1
+    }
+
+    /// Return how many points are shown at the given time, the points at or before the time.
+    /// Points sharing a timestamp are always shown together, and the first point is shown even
+    /// before its time. The line is drawn on towards the next point as time passes between them.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [
+    ///     (0.0, 0.0, 0.0),
+    ///     (1.0, 1.0, 1.0),
+    ///     (2.0, 1.0, 1.0),
+    ///     (3.0, 1.0, 1.0),
+    ///     (4.0, 2.0, 2.0),
+    /// ];
+    ///
+    /// let chart = XyTimeData::new(&points, "m", "m", "Bursts");
+    ///
+    /// assert_eq!(chart.revealed_count(0.0), 1);
+    /// assert_eq!(chart.revealed_count(0.5), 1);
+    /// assert_eq!(chart.revealed_count(1.0), 4);
+    /// assert_eq!(chart.revealed_count(1.5), 4);
+    /// assert_eq!(chart.revealed_count(2.0), 5);
+    /// ```
+    pub fn revealed_count(&self, time: f32) -> usize {
+        self.time_index(time) + 1
+    }
+
+    /// Control playback with the keyboard. This is opt-in, call it every frame before drawing
+    /// if you want the chart to respond to the given keys.
+    ///
+    /// ```
+    /// # use egui::{CentralPanel, Context, Event, Key, Modifiers, RawInput};
+    /// # use egui_plotter::charts::{PlaybackKeys, XyTimeData};
+    /// let points = [(0.0, 0.0, 10.0), (1.0, 1.0, 12.0), (2.0, 4.0, 14.0)];
+    ///
+    /// let mut chart = XyTimeData::new(&points, "m", "m", "Parabola").time(2.0);
+    ///
+    /// let ctx = Context::default();
+    /// let mut press = |key| {
+    ///     let input = RawInput {
+    ///         events: vec![Event::Key {
+    ///             key,
+    ///             physical_key: None,
+    ///             pressed: true,
+    ///             repeat: false,
+    ///             modifiers: Modifiers::NONE,
+    ///         }],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let _ = ctx.run(input, |ctx| {
+    ///         CentralPanel::default().show(ctx, |ui| {
+    ///             chart.handle_input(ui, PlaybackKeys::default())
+    ///         });
+    ///     });
+    /// };
+    ///
+    /// press(Key::ArrowRight);
+    /// press(Key::ArrowLeft);
+    /// press(Key::ArrowLeft);
+    ///
+    /// assert!((chart.current_time() - 11.0).abs() < 0.001);
+    /// ```
+    pub fn handle_input(&mut self, ui: &Ui, keys: PlaybackKeys) {
+        let pressed = |key: Option<Key>| match key {
+            Some(key) => ui.input(|input| input.key_pressed(key)),
+            None => false,
+        };
+
+        let toggle = pressed(keys.toggle);
+        let seek_forward = pressed(keys.seek_forward);
+        let seek_backward = pressed(keys.seek_backward);
+
+        if toggle {
+            self.toggle_playback();
+        }
+
+        if seek_forward || seek_backward {
+            let step = match seek_forward {
+                true => keys.seek_step,
+                false => -keys.seek_step,
+            };
+
+            let time = (self.current_time() + step).clamp(self.start_time(), self.end_time());
+
+            self.set_time(time - self.start_time());
+        }
+    }
+
+    #[inline]
+    /// Pause playback while the window isn't focused, resuming from the same point once focus
+    /// returns. Disabled by default.
+    pub fn set_pause_on_blur(&mut self, pause_on_blur: bool) {
+        self.pause_on_blur = pause_on_blur
+    }
+
+    #[inline]
+    /// Pause playback while the window isn't focused. Consumes self.
+    pub fn pause_on_blur(mut self, pause_on_blur: bool) -> Self {
+        self.set_pause_on_blur(pause_on_blur);
+
+        self
+    }
+
+    /// Pause playback when focus is lost, and resume it when focus returns if it was paused by
+    /// losing focus.
+    fn handle_focus(&mut self, focused: bool) {
+        match (focused, self.blur_paused) {
+            (false, false) if self.is_playing() => {
+                self.toggle_playback();
+                self.blur_paused = true;
+            }
+            (true, true) => {
+                if !self.is_playing() && self.with_clock(|clock| clock.is_started()) {
+                    self.toggle_playback();
+                }
+
+                self.blur_paused = false;
+            }
+            _ => {}
+        }
+    }
+
     #[inline]
     /// Start/enable playback of the chart.
     pub fn start_playback(&mut self) {
-        self.playback_start = Some(Instant::now());
-        self.pause_start = None;
+        self.with_clock(|clock| clock.start_playback())
     }
 
     #[inline]
     /// Stop/disable playback of the chart.
     pub fn stop_playback(&mut self) {
-        self.playback_start = None;
-        self.pause_start = None;
+        self.with_clock(|clock| clock.stop_playback())
     }
 
+    #[inline]
     /// Toggle playback of the chart.
     pub fn toggle_playback(&mut self) {
-        match self.playback_start {
-            Some(playback_start) => match self.pause_start {
-                Some(pause_start) => {
-                    let delta = Instant::now().duration_since(pause_start);
-
-                    self.pause_start = None;
-                    self.playback_start = Some(playback_start + delta);
-                }
-                None => self.pause_start = Some(Instant::now()),
-            },
+        self.with_clock(|clock| clock.toggle_playback())
+    }
 
-            None => {
-                self.start_playback();
-            }
-        }
+    /// Step playback forward by the given number of seconds, as if that much time had passed.
+    /// `draw` does this with the time since the last frame, so calling it yourself lets you
+    /// drive playback deterministically, such as in tests. Playback only moves while playing,
+    /// but the chart's own animations are always stepped, see `Chart::advance`.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(0.0, 0.0, 0.0), (1.0, 1.0, 1.0), (2.0, 2.0, 2.0), (3.0, 3.0, 3.0)];
+    ///
+    /// let mut chart = XyTimeData::new(&points, "m", "m", "Steps");
+    ///
+    /// chart.start_playback();
+    /// chart.advance(0.5);
+    /// chart.advance(1.0);
+    ///
+    /// assert!((chart.current_time() - 1.5).abs() < 0.001);
+    /// ```
+    pub fn advance(&mut self, dt: f32) {
+        self.with_clock(|clock| clock.advance(dt as f64));
+        self.chart.advance(dt);
     }
 
     #[inline]
     /// Return true if playback is currently enabled & underway.
     pub fn is_playing(&self) -> bool {
-        self.playback_start != None && self.pause_start == None
+        match &self.shared_clock {
+            Some(shared_clock) => shared_clock.is_playing(),
+            None => self.clock.is_playing(),
+        }
     }
 
     #[inline]
     /// Return the time the chart starts at when playback is enabled.
     pub fn start_time(&self) -> f32 {
-        let time_start = *self.times.first().unwrap();
+        let time_start = *self.times().first().unwrap();
 
         time_start
     }
 
     /// Return the current time to be animated when playback is enabled.
     pub fn current_time(&mut self) -> f32 {
-        if let Some(playback_start) = self.playback_start {
-            let now = Instant::now();
-
+        if let Some(elapsed) = self.with_clock(|clock| clock.elapsed()) {
             let time_start = self.start_time();
             let time_end = self.end_time();
 
@@ -506,19 +2337,16 @@ impl XyTimeData {
 
             // Ensure deltas are over 10us, otherwise they can cause overflows
             // in the plotters library
-            let current_delta = MIN_DELTA
-                + self.playback_speed
-                    * match self.pause_start {
-                        Some(pause_start) => {
-                            pause_start.duration_since(playback_start).as_secs_f32()
-                        }
-                        None => now.duration_since(playback_start).as_secs_f32(),
-                    };
+            let current_delta = MIN_DELTA + elapsed as f32;
 
             match base_delta > current_delta {
                 true => current_delta + time_start,
                 false => {
-                    self.playback_start = None;
+                    // A shared clock keeps running for the other charts using it, and a paused
+                    // clock stays on the last frame
+                    if self.shared_clock.is_none() && self.clock.is_playing() {
+                        self.clock.stop_playback();
+                    }
 
                     time_end
                 }
@@ -528,17 +2356,79 @@ impl XyTimeData {
         }
     }
 
+    /// Set the time to resume playback at as a fraction of the chart's duration, from 0.0 at the
+    /// start time to 1.0 at the end time.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(0.0, 0.0, 10.0), (1.0, 1.0, 12.0), (2.0, 4.0, 14.0)];
+    ///
+    /// let mut chart = XyTimeData::new(&points, "m", "m", "Parabola").progress(0.5);
+    ///
+    /// assert!((chart.current_time() - 12.0).abs() < 0.001);
+    /// assert!((chart.get_progress() - 0.5).abs() < 0.001);
+    /// ```
+    pub fn set_progress(&mut self, progress: f32) {
+        let span = self.end_time() - self.start_time();
+
+        self.set_time(progress.clamp(0.0, 1.0) * span)
+    }
+
+    #[inline]
+    /// Set the time to resume playback at as a fraction of the chart's duration. Consumes self.
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.set_progress(progress);
+
+        self
+    }
+
+    /// Return how far playback is through the chart's duration, from 0.0 at the start time to
+    /// 1.0 at the end time.
+    pub fn get_progress(&mut self) -> f32 {
+        let time_start = self.start_time();
+        let span = self.end_time() - time_start;
+
+        match span > 0.0 {
+            true => ((self.current_time() - time_start) / span).clamp(0.0, 1.0),
+            false => 1.0,
+        }
+    }
+
     #[inline]
     /// Return the time the chart finished animating at when playback is enabled.
     pub fn end_time(&self) -> f32 {
-        let time_end = *self.times.last().unwrap();
+        let time_end = *self.times().last().unwrap();
 
         time_end
     }
 
+    #[inline]
+    /// Return the X and Y of the chart's points, sorted by time like they're drawn.
+    ///
+    /// ```
+    /// # use egui_plotter::charts::XyTimeData;
+    /// let points = [(1.0, 2.0, 1.0), (0.0, 0.0, 0.0)];
+    /// let chart = XyTimeData::new(&points, "m", "m", "Steps");
+    ///
+    /// assert_eq!(chart.points(), &[(0.0, 0.0), (1.0, 2.0)]);
+    /// assert_eq!(chart.times(), &[0.0, 1.0]);
+    /// ```
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points[self.head..]
+    }
+
+    #[inline]
+    /// Return the times of the chart's points, sorted and in the same order as `points`.
+    pub fn times(&self) -> &[f32] {
+        &self.times[self.head..]
+    }
+
     #[inline]
     /// Return the speed the chart is animated at.
     pub fn get_playback_speed(&self) -> f32 {
-        self.playback_speed
+        match &self.shared_clock {
+            Some(shared_clock) => shared_clock.get_playback_speed(),
+            None => self.clock.playback_speed(),
+        }
     }
 }
@@ -0,0 +1,137 @@
+//! Color ramps for mapping values to colors
+
+use std::ops::Range;
+
+use plotters::style::RGBColor;
+
+/// Colors of the viridis map at evenly spaced points, from low to high.
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 44, 122),
+    (59, 81, 139),
+    (44, 113, 142),
+    (33, 144, 141),
+    (39, 173, 129),
+    (92, 200, 99),
+    (170, 220, 50),
+    (253, 231, 37),
+];
+/// Colors of the diverging map below, at and above its center.
+const DIVERGING: [(u8, u8, u8); 3] = [(59, 76, 192), (247, 247, 247), (180, 4, 38)];
+/// Colors of the grayscale map, from low to high.
+const GRAYSCALE: [(u8, u8, u8); 2] = [(0, 0, 0), (255, 255, 255)];
+/// Smallest value mapped by `Colormap::over_log`, which zero and negative values are clamped to
+/// as they have no logarithm.
+pub const LOG_EPSILON: f32 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Well known color maps, turning values into colors so data can be encoded by color, such as
+/// along a line with `draw_colored_line`.
+///
+/// ```
+/// # use egui_plotter::Colormap;
+/// # use plotters::style::RGBColor;
+/// let color = Colormap::Viridis.over(0.0..10.0);
+///
+/// assert_eq!(color(0.0), RGBColor(68, 1, 84));
+/// assert_eq!(color(10.0), RGBColor(253, 231, 37));
+///
+/// // Anomalies from -1 to 3, white at zero rather than at the midpoint of the range
+/// let color = Colormap::Diverging { center: 0.0 }.over(-1.0..3.0);
+///
+/// assert_eq!(color(0.0), RGBColor(247, 247, 247));
+/// assert_eq!(color(3.0), RGBColor(180, 4, 38));
+/// ```
+pub enum Colormap {
+    /// Perceptually uniform map from dark purple through blue and green to yellow, readable in
+    /// grayscale and by colorblind viewers.
+    Viridis,
+    /// Map from blue below the center value through white at it to red above it, for data
+    /// centered on a value such as anomalies around zero. Values the same distance either side
+    /// of the center are equally intense, so only the side furthest from the center reaches the
+    /// full color.
+    Diverging {
+        /// Data value drawn in white.
+        center: f32,
+    },
+    /// Map from black to white.
+    Grayscale,
+}
+
+impl Colormap {
+    /// Return the color of a value from 0.0 at the low end of the map to 1.0 at the high end.
+    /// Values outside are clamped. Diverging maps are centered at 0.5.
+    pub fn color(&self, t: f32) -> RGBColor {
+        let stops: &[(u8, u8, u8)] = match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Diverging { .. } => &DIVERGING,
+            Self::Grayscale => &GRAYSCALE,
+        };
+
+        let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - index as f32;
+
+        let (from, to) = (stops[index], stops[index + 1]);
+        let lerp =
+            |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * fraction).round() as u8;
+
+        RGBColor(lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+    }
+
+    /// Return a function giving the color of a data value within the range, with values outside
+    /// of it clamped. Diverging maps are centered at their center value rather than the middle of
+    /// the range.
+    pub fn over(self, range: Range<f32>) -> impl Fn(f32) -> RGBColor {
+        move |value| {
+            let t = match self {
+                Self::Diverging { center } => {
+                    let spread = (range.start - center).abs().max((range.end - center).abs());
+
+                    match spread > 0.0 {
+                        true => 0.5 + (value - center) / spread / 2.0,
+                        false => 0.5,
+                    }
+                }
+                _ => {
+                    let width = range.end - range.start;
+
+                    match width != 0.0 {
+                        true => (value - range.start) / width,
+                        false => 0.5,
+                    }
+                }
+            };
+
+            self.color(t)
+        }
+    }
+
+    /// Return a function giving the color of a data value within the range on a logarithmic
+    /// scale, for data spanning orders of magnitude such as spectrograms. Each power of ten takes
+    /// up the same share of the map. Values, range bounds and diverging centers at or below zero
+    /// are clamped to `LOG_EPSILON`, so they're drawn at the low end of the map.
+    ///
+    /// ```
+    /// # use egui_plotter::Colormap;
+    /// # use plotters::style::RGBColor;
+    /// let color = Colormap::Grayscale.over_log(1.0..10000.0);
+    ///
+    /// assert_eq!(color(100.0), RGBColor(128, 128, 128));
+    /// assert_eq!(color(0.0), RGBColor(0, 0, 0));
+    /// assert_eq!(color(-5.0), RGBColor(0, 0, 0));
+    /// ```
+    pub fn over_log(self, range: Range<f32>) -> impl Fn(f32) -> RGBColor {
+        let log = |value: f32| value.max(LOG_EPSILON).log10();
+
+        let map = match self {
+            Self::Diverging { center } => Self::Diverging {
+                center: log(center),
+            },
+            map => map,
+        };
+        let color = map.over(log(range.start)..log(range.end));
+
+        move |value| color(log(value))
+    }
+}
@@ -0,0 +1,270 @@
+//! Helpers for drawing common elements onto any chart
+
+use std::ops::{Add, Sub};
+
+use plotters::{
+    chart::ChartContext,
+    coord::{cartesian::Cartesian2d, ranged1d::Ranged},
+    drawing::DrawingAreaErrorKind,
+    element::{Drawable, EmptyElement, ErrorBar, PathElement, PointCollection, Polygon},
+    style::{Color, RGBAColor, ShapeStyle},
+};
+use plotters_backend::{BackendCoord, DrawingBackend};
+
+/// Draw vertical error bars from `(x, y, err)` tuples, spanning from `y - err` to `y + err` with
+/// caps `cap_width` pixels wide. Uses the coordinates of the chart it's drawn on, so it can be
+/// combined with any other series in your builder callback.
+///
+/// ```
+/// # use egui_plotter::draw_error_bars;
+/// # use plotters::prelude::*;
+/// # let mut buffer = vec![0; 100 * 100 * 3];
+/// # let area = BitMapBackend::with_buffer(&mut buffer, (100, 100)).into_drawing_area();
+/// let mut chart = ChartBuilder::on(&area)
+///     .build_cartesian_2d(0f32..4f32, 0f32..4f32)
+///     .unwrap();
+///
+/// let data = [(1.0, 1.0, 0.5), (2.0, 2.5, 0.25), (3.0, 2.0, 1.0)];
+///
+/// draw_error_bars(&mut chart, data, 6, RED.into()).unwrap();
+/// ```
+pub fn draw_error_bars<DB, X, Y, I>(
+    chart: &mut ChartContext<DB, Cartesian2d<X, Y>>,
+    data: I,
+    cap_width: u32,
+    style: ShapeStyle,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+    X::ValueType: Clone,
+    Y::ValueType: Clone + Add<Output = Y::ValueType> + Sub<Output = Y::ValueType>,
+    I: IntoIterator<Item = (X::ValueType, Y::ValueType, Y::ValueType)>,
+{
+    chart.draw_series(data.into_iter().map(|(x, y, err)| {
+        ErrorBar::new_vertical(
+            x,
+            y.clone() - err.clone(),
+            y.clone(),
+            y + err,
+            style,
+            cap_width,
+        )
+    }))?;
+
+    Ok(())
+}
+
+/// Draw a line through the given points where each segment has its own color, given by `color`
+/// for the index of the point the segment starts at. Useful for encoding a third value, such as
+/// speed, along a line.
+///
+/// ```
+/// # use egui_plotter::draw_colored_line;
+/// # use plotters::prelude::*;
+/// # let mut buffer = vec![0; 100 * 100 * 3];
+/// # let area = BitMapBackend::with_buffer(&mut buffer, (100, 100)).into_drawing_area();
+/// let mut chart = ChartBuilder::on(&area)
+///     .build_cartesian_2d(0f32..10f32, -1f32..1f32)
+///     .unwrap();
+///
+/// let points: Vec<(f32, f32)> = (0..100).map(|x| x as f32 / 10.0).map(|x| (x, x.sin())).collect();
+///
+/// draw_colored_line(&mut chart, points, 2, |i| HSLColor(i as f64 / 100.0, 1.0, 0.5).to_rgba())
+///     .unwrap();
+/// ```
+pub fn draw_colored_line<DB, X, Y, I, F>(
+    chart: &mut ChartContext<DB, Cartesian2d<X, Y>>,
+    points: I,
+    stroke_width: u32,
+    mut color: F,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+    X::ValueType: Clone,
+    Y::ValueType: Clone,
+    I: IntoIterator<Item = (X::ValueType, Y::ValueType)>,
+    F: FnMut(usize) -> RGBAColor,
+{
+    let points: Vec<_> = points.into_iter().collect();
+
+    chart.draw_series(points.windows(2).enumerate().map(|(i, segment)| {
+        PathElement::new(segment.to_vec(), color(i).stroke_width(stroke_width))
+    }))?;
+
+    Ok(())
+}
+
+/// Draw an arrow from `from` to `to`, with a head `head_size` pixels long at `to`. The head is
+/// sized in pixels, so it stays the same size relative to the shaft as the chart is zoomed.
+/// Zero-length arrows aren't drawn. Call it for each vector to assemble a quiver plot.
+///
+/// ```
+/// # use egui_plotter::draw_arrow;
+/// # use plotters::prelude::*;
+/// # let mut buffer = vec![0; 100 * 100 * 3];
+/// # let area = BitMapBackend::with_buffer(&mut buffer, (100, 100)).into_drawing_area();
+/// let mut chart = ChartBuilder::on(&area)
+///     .build_cartesian_2d(-1f32..5f32, -1f32..5f32)
+///     .unwrap();
+///
+/// for x in 0..5 {
+///     for y in 0..5 {
+///         let (x, y) = (x as f32, y as f32);
+///         let (dx, dy) = (-y * 0.15, x * 0.15);
+///
+///         draw_arrow(&mut chart, (x, y), (x + dx, y + dy), 4, BLUE.into()).unwrap();
+///     }
+/// }
+/// ```
+pub fn draw_arrow<DB, X, Y>(
+    chart: &mut ChartContext<DB, Cartesian2d<X, Y>>,
+    from: (X::ValueType, Y::ValueType),
+    to: (X::ValueType, Y::ValueType),
+    head_size: u32,
+    style: ShapeStyle,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+{
+    let area = chart.plotting_area();
+
+    // Work in pixels relative to the plotting area, so the head has the same size everywhere
+    let (base_x, base_y) = area.get_base_pixel();
+    let pixel = |coord| {
+        let (x, y) = area.map_coordinate(&coord);
+
+        ((x - base_x) as f64, (y - base_y) as f64)
+    };
+
+    let (from_x, from_y) = pixel(from);
+    let (to_x, to_y) = pixel(to);
+
+    let (dx, dy) = (to_x - from_x, to_y - from_y);
+    let length = dx.hypot(dy);
+
+    if length < 1.0 {
+        return Ok(());
+    }
+
+    // Keep the head within the arrow, even when the arrow is shorter than the head
+    let head = (head_size as f64).min(length);
+    let (dir_x, dir_y) = (dx / length, dy / length);
+
+    let neck = (to_x - dir_x * head, to_y - dir_y * head);
+    let barb = |side: f64| {
+        (
+            (neck.0 - dir_y * head / 2.0 * side).round() as i32,
+            (neck.1 + dir_x * head / 2.0 * side).round() as i32,
+        )
+    };
+
+    let area = area.strip_coord_spec();
+    let round = |(x, y): (f64, f64)| (x.round() as i32, y.round() as i32);
+
+    area.draw(&PathElement::new(
+        vec![round((from_x, from_y)), round(neck)],
+        style,
+    ))?;
+    area.draw(&Polygon::new(
+        vec![round((to_x, to_y)), barb(1.0), barb(-1.0)],
+        style.filled(),
+    ))?;
+
+    Ok(())
+}
+
+/// Draw a plotters element given in pixel offsets, such as a marker or a label, anchored at a
+/// point in the chart's coordinates. The element is drawn through the backend like any series,
+/// so it follows the chart's pan and zoom. Draw several elements at the same point to build up
+/// composed markers and annotations.
+///
+/// ```
+/// # use egui_plotter::draw_at;
+/// # use plotters::prelude::*;
+/// # let mut buffer = vec![0; 100 * 100 * 3];
+/// # let area = BitMapBackend::with_buffer(&mut buffer, (100, 100)).into_drawing_area();
+/// let mut chart = ChartBuilder::on(&area)
+///     .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+///     .unwrap();
+///
+/// let peak = (5.0, 8.0);
+///
+/// draw_at(&mut chart, peak, Circle::new((0, 0), 4, RED.filled())).unwrap();
+/// draw_at(&mut chart, peak, Text::new("Peak", (6, -6), ("sans-serif", 12))).unwrap();
+/// ```
+pub fn draw_at<DB, X, Y, E>(
+    chart: &mut ChartContext<DB, Cartesian2d<X, Y>>,
+    at: (X::ValueType, Y::ValueType),
+    element: E,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+    E: Drawable<DB>,
+    for<'a> &'a E: PointCollection<'a, BackendCoord>,
+{
+    chart
+        .plotting_area()
+        .draw(&(EmptyElement::at(at) + element))
+}
+
+/// Split points into the runs of points with finite coordinates, for drawing data with gaps
+/// (NaN or infinite values) in it. If `break_on_nan` is true each run is drawn as its own line,
+/// leaving a gap, otherwise non-finite points are dropped and everything is returned as one
+/// run. Plotters turns coordinates into pixels before they reach the backend, so filter your
+/// data with this before plotting it rather than relying on the backend to catch them.
+///
+/// ```
+/// # use egui_plotter::split_non_finite;
+/// let points = [(0.0, 1.0), (1.0, f32::NAN), (2.0, 3.0), (3.0, 4.0)];
+///
+/// assert_eq!(
+///     split_non_finite(points, true),
+///     vec![vec![(0.0, 1.0)], vec![(2.0, 3.0), (3.0, 4.0)]],
+/// );
+/// assert_eq!(
+///     split_non_finite(points, false),
+///     vec![vec![(0.0, 1.0), (2.0, 3.0), (3.0, 4.0)]],
+/// );
+/// ```
+pub fn split_non_finite<X, Y, I>(points: I, break_on_nan: bool) -> Vec<Vec<(X, Y)>>
+where
+    X: Copy + Into<f64>,
+    Y: Copy + Into<f64>,
+    I: IntoIterator<Item = (X, Y)>,
+{
+    split_runs(
+        points,
+        |(x, y)| (*x).into().is_finite() && (*y).into().is_finite(),
+        break_on_nan,
+    )
+}
+
+/// Split items into the runs of items that pass `keep`, either breaking at each item that
+/// doesn't or dropping them and returning everything as one run. Empty runs are never returned.
+pub(crate) fn split_runs<T, I, F>(items: I, mut keep: F, split: bool) -> Vec<Vec<T>>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> bool,
+{
+    let mut runs = vec![Vec::new()];
+
+    for item in items {
+        match keep(&item) {
+            true => runs.last_mut().unwrap().push(item),
+            false if split && !runs.last().unwrap().is_empty() => runs.push(Vec::new()),
+            false => {}
+        }
+    }
+
+    runs.retain(|run| !run.is_empty());
+
+    runs
+}
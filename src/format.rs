@@ -0,0 +1,77 @@
+//! Formatting of numbers for axis labels
+
+/// SI prefixes from 10^-15 to 10^15, in steps of a thousand.
+const SI_PREFIXES: [&str; 11] = ["f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P"];
+/// Exponent of the smallest SI prefix.
+const SI_MIN_EXPONENT: i32 = -15;
+
+/// Format a value with an SI prefix, such as k for thousands or µ for millionths, rounded to the
+/// given number of significant figures. Trailing zeros are kept so labels line up. Values beyond
+/// the largest or smallest prefix, P and f, use those prefixes.
+///
+/// Pass it to plotters' `x_label_formatter` and `y_label_formatter`, or use it in your own labels.
+///
+/// ```
+/// # use egui_plotter::format_si;
+/// assert_eq!(format_si(0.0001999, 3), "200µ");
+/// assert_eq!(format_si(1500.0, 3), "1.50k");
+/// assert_eq!(format_si(-2_500_000.0, 2), "-2.5M");
+/// assert_eq!(format_si(999.96, 3), "1.00k");
+/// assert_eq!(format_si(0.0, 3), "0.00");
+/// ```
+pub fn format_si(value: f64, precision: usize) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+
+    let precision = precision.max(1);
+
+    if value == 0.0 {
+        return format_fixed(value, precision - 1);
+    }
+
+    let max_exponent = SI_MIN_EXPONENT + 3 * (SI_PREFIXES.len() as i32 - 1);
+    let mut exponent =
+        ((value.abs().log10() / 3.0).floor() as i32 * 3).clamp(SI_MIN_EXPONENT, max_exponent);
+
+    let format_at = |exponent: i32, magnitude: f64| {
+        let scaled = value / 10f64.powi(exponent);
+        let decimals = (precision as i32 - 1 - magnitude.log10().floor() as i32).max(0);
+
+        format_fixed(scaled, decimals as usize)
+    };
+
+    let mut label = format_at(exponent, (value / 10f64.powi(exponent)).abs());
+
+    // Rounding can carry the value up to the next prefix, as with 999.96 to three figures
+    if label
+        .parse::<f64>()
+        .is_ok_and(|rounded| rounded.abs() >= 1000.0)
+        && exponent < max_exponent
+    {
+        exponent += 3;
+        label = format_at(exponent, 1.0);
+    }
+
+    let prefix = SI_PREFIXES[((exponent - SI_MIN_EXPONENT) / 3) as usize];
+
+    format!("{}{}", label, prefix)
+}
+
+/// Format a value with a fixed number of decimals, without the minus sign on values that round
+/// to zero.
+///
+/// ```
+/// # use egui_plotter::format_fixed;
+/// assert_eq!(format_fixed(0.0001999, 2), "0.00");
+/// assert_eq!(format_fixed(-0.001, 2), "0.00");
+/// assert_eq!(format_fixed(3.14159, 3), "3.142");
+/// ```
+pub fn format_fixed(value: f64, decimals: usize) -> String {
+    let label = format!("{:.*}", decimals, value);
+
+    match label.strip_prefix('-') {
+        Some(unsigned) if unsigned.chars().all(|c| c == '0' || c == '.') => unsigned.to_string(),
+        _ => label,
+    }
+}
@@ -0,0 +1,279 @@
+//! Legends drawn with egui's shapes, for charts that want to match the look of the rest of the ui
+
+use std::{cell::RefCell, rc::Rc};
+
+use egui::{
+    epaint::{shape_transform::adjust_colors, Shadow},
+    Align2, Color32, FontSelection, Rect, Rounding, Shape, Stroke, TextStyle, Ui, Vec2,
+};
+use plotters::{
+    chart::ChartContext,
+    coord::CoordTranslate,
+    drawing::DrawingAreaErrorKind,
+    style::{Color, TRANSPARENT},
+};
+use plotters_backend::DrawingBackend;
+
+use crate::backend::backend_color;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Entry of a legend, a label shown next to a swatch of the series' color.
+pub struct LegendEntry {
+    /// Label of the series.
+    pub label: String,
+    /// Color of the series.
+    pub color: Color32,
+}
+
+impl LegendEntry {
+    #[inline]
+    /// Create a new entry from a label and a plotters color.
+    pub fn new<C: Color>(label: impl Into<String>, color: &C) -> Self {
+        Self {
+            label: label.into(),
+            color: backend_color(color.to_backend_color()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Shared handle used to read the labels given to series with plotters' `label` and `legend`
+/// into the legend a Chart draws with egui's shapes.
+///
+/// Get one from `Chart::legend_recorder`, move it into your builder callback and call `record`
+/// in place of `configure_series_labels().draw()` once the series are drawn:
+///
+/// ```ignore
+/// let chart = Chart::new(());
+/// let recorder = chart.legend_recorder();
+///
+/// let chart = chart.builder_cb(Box::new(move |area, _t, _d| {
+///     let mut chart = ChartBuilder::on(area)
+///         .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+///         .unwrap();
+///
+///     chart
+///         .draw_series(LineSeries::new([(0.0, 0.0), (10.0, 10.0)], &RED))
+///         .unwrap()
+///         .label("Rising")
+///         .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], RED));
+///
+///     recorder.record(&mut chart).unwrap();
+/// }));
+/// ```
+///
+/// Each entry takes the color of the first shape its legend element draws. Labels are only read
+/// by an [`EguiBackend`](crate::EguiBackend) given the recorder, which the Chart's backends are;
+/// other backends draw them with plotters as usual, without a box.
+pub struct LegendRecorder(Rc<RefCell<LegendCapture>>);
+
+#[derive(Debug, Default)]
+/// Lines of the legend captured while plotters draws it.
+struct LegendCapture {
+    recording: bool,
+    /// Label, top and color of each line, in the order they're drawn.
+    lines: Vec<(String, i32, Option<Color32>)>,
+}
+
+impl LegendRecorder {
+    #[inline]
+    /// Create a new, empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the labels of every series drawn on a chart, replacing any recorded before.
+    pub fn record<'a, DB: DrawingBackend + 'a, CT: CoordTranslate>(
+        &self,
+        chart: &mut ChartContext<'a, DB, CT>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        {
+            let mut capture = self.0.borrow_mut();
+
+            capture.recording = true;
+            capture.lines.clear();
+        }
+
+        let result = chart
+            .configure_series_labels()
+            .background_style(TRANSPARENT)
+            .border_style(TRANSPARENT)
+            .draw();
+
+        self.0.borrow_mut().recording = false;
+
+        result
+    }
+
+    /// Get an entry for each label last recorded. Labels without a legend element are given a
+    /// transparent swatch.
+    pub fn entries(&self) -> Vec<LegendEntry> {
+        self.0
+            .borrow()
+            .lines
+            .iter()
+            .map(|(label, _, color)| LegendEntry {
+                label: label.clone(),
+                color: color.unwrap_or(Color32::TRANSPARENT),
+            })
+            .collect()
+    }
+
+    #[inline]
+    /// Forget the recorded labels.
+    pub(crate) fn clear(&self) {
+        self.0.borrow_mut().lines.clear()
+    }
+
+    #[inline]
+    /// Return true while `record` is drawing the legend.
+    pub(crate) fn is_recording(&self) -> bool {
+        self.0.borrow().recording
+    }
+
+    #[inline]
+    /// Capture a line of the legend drawn with its top at the given height.
+    pub(crate) fn text(&self, label: &str, top: i32) {
+        self.0.borrow_mut().lines.push((label.into(), top, None))
+    }
+
+    /// Capture a shape of a legend element drawn around the given height, coloring the line
+    /// it's next to if it isn't colored yet. Transparent shapes, like the box plotters would
+    /// draw, are left out.
+    pub(crate) fn shape(&self, y: i32, color: Color32) {
+        if color.a() == 0 {
+            return;
+        }
+
+        let mut capture = self.0.borrow_mut();
+
+        let line = capture.lines.iter_mut().rev().find(|(_, top, _)| *top <= y);
+
+        if let Some((_, _, line_color @ None)) = line {
+            *line_color = Some(color);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Style of the legend drawn by a Chart.
+pub struct LegendStyle {
+    /// Corner radius of the legend box.
+    pub rounding: f32,
+    /// Shadow drawn under the legend box, if any.
+    pub shadow: Option<Shadow>,
+    /// Space between the edge of the legend box and the entries.
+    pub padding: f32,
+    /// Space between entries, and between swatches and labels.
+    pub spacing: f32,
+    /// Corner or edge of the chart the legend is placed in.
+    pub position: Align2,
+    /// Space between the legend box and the edge of the chart.
+    pub margin: f32,
+    /// Fill of the legend box, uses the ui's window fill if None.
+    pub fill: Option<Color32>,
+    /// Outline of the legend box, uses the ui's window stroke if None.
+    pub stroke: Option<Stroke>,
+}
+
+impl Default for LegendStyle {
+    fn default() -> Self {
+        Self {
+            rounding: 4.0,
+            shadow: None,
+            padding: 6.0,
+            spacing: 4.0,
+            position: Align2::RIGHT_TOP,
+            margin: 10.0,
+            fill: None,
+            stroke: None,
+        }
+    }
+}
+
+/// Draw a legend within the bounds of a chart.
+pub(crate) fn draw_legend(
+    ui: &Ui,
+    bounds: Rect,
+    entries: &[LegendEntry],
+    style: &LegendStyle,
+    opacity: f32,
+) {
+    if entries.is_empty() || opacity <= 0.0 {
+        return;
+    }
+
+    let painter = ui.painter().with_clip_rect(bounds);
+    let visuals = ui.visuals();
+
+    let font = FontSelection::Style(TextStyle::Body).resolve(ui.style());
+    let text_color = visuals.text_color().gamma_multiply(opacity);
+
+    let galleys: Vec<_> = entries
+        .iter()
+        .map(|entry| painter.layout_no_wrap(entry.label.clone(), font.clone(), text_color))
+        .collect();
+
+    let swatch = font.size;
+    let row_height = galleys
+        .iter()
+        .map(|galley| galley.size().y)
+        .fold(swatch, f32::max);
+    let label_width = galleys
+        .iter()
+        .map(|galley| galley.size().x)
+        .fold(0.0, f32::max);
+
+    let rows = entries.len() as f32;
+
+    let size = Vec2::new(
+        swatch + style.spacing + label_width,
+        row_height * rows + style.spacing * (rows - 1.0),
+    ) + Vec2::splat(style.padding * 2.0);
+
+    let rect = style
+        .position
+        .align_size_within_rect(size, bounds.shrink(style.margin));
+
+    let rounding = Rounding::same(style.rounding);
+
+    if let Some(shadow) = style.shadow {
+        let mut shadow = Shape::from(shadow.tessellate(rect, rounding));
+
+        adjust_colors(&mut shadow, &|color| *color = color.gamma_multiply(opacity));
+
+        painter.add(shadow);
+    }
+
+    let fill = style.fill.unwrap_or(visuals.window_fill);
+    let stroke = style.stroke.unwrap_or(visuals.window_stroke);
+
+    painter.rect(
+        rect,
+        rounding,
+        fill.gamma_multiply(opacity),
+        Stroke::new(stroke.width, stroke.color.gamma_multiply(opacity)),
+    );
+
+    let mut row = rect.min + Vec2::splat(style.padding);
+
+    for (entry, galley) in entries.iter().zip(galleys) {
+        let swatch_rect = Rect::from_min_size(
+            row + Vec2::new(0.0, (row_height - swatch) / 2.0),
+            Vec2::splat(swatch),
+        );
+
+        painter.rect_filled(
+            swatch_rect,
+            style.rounding.min(swatch / 4.0),
+            entry.color.gamma_multiply(opacity),
+        );
+
+        let label_pos =
+            row + Vec2::new(swatch + style.spacing, (row_height - galley.size().y) / 2.0);
+
+        painter.galley(label_pos, galley, text_color);
+
+        row.y += row_height + style.spacing;
+    }
+}
@@ -22,9 +22,14 @@
 //!
 //! See line 24 example below to see how to disable feathering.
 //!
+//! Charts log a warning through the `log` crate the first time they're drawn with feathering
+//! enabled, which can be turned off with `Chart::warn_feathering(false)`.
+//!
 //! ### Features
 //!
 //!  * `timechart` - Includes all the pre-made animatable charts like XyTimeData and TimeData.
+//!  * `export` - Adds `export_frame` to the timecharts, to render frames into PNG images.
+//!  * `chrono` - Adds `set_time_format` to the timecharts, to label X with dates and times.
 //!
 //! ## Examples
 //!
@@ -33,7 +38,7 @@
 //! [eframe](https://docs.rs/eframe/0.22.0/eframe/index.html#usage-native) and
 //! [plotters](https://docs.rs/plotters/0.3.4/plotters/index.html#quick-start).
 //!
-//! ```rust
+//! ```rust,no_run
 //! use eframe::egui::{self, CentralPanel, Visuals};
 //! use egui_plotter::EguiBackend;
 //! use plotters::prelude::*;
@@ -109,7 +114,7 @@
 //! user interactivity with your plotter charts. You can either make your own chart or
 //! use a prebuilt chart type included in the `charts` module.
 //!
-//! ```rust
+//! ```rust,no_run
 //! use eframe::egui::{self, CentralPanel, Key, Visuals};
 //! use egui_plotter::{Chart, MouseConfig};
 //! use plotters::prelude::*;
@@ -212,14 +217,35 @@
 //! }
 //! ```
 
+mod animation;
 mod backend;
 mod chart;
 pub mod charts;
+mod colormap;
+mod elements;
+mod format;
+mod legend;
+mod mapping;
+mod plot;
+mod raster;
+mod series;
+mod subplot;
 
-pub use backend::{EguiBackend, EguiBackendError};
+pub use animation::{AnimationDriver, AnimationHandle, DEFAULT_FPS};
+pub use backend::{EguiBackend, EguiBackendError, OverflowMode, ShapeClass, StrokeUnits};
 pub use chart::{
-    Chart, MouseButton, MouseConfig, Transform, DEFAULT_MOVE_SCALE, DEFAULT_SCROLL_SCALE,
+    mirror_range, Axis, Chart, ChartWidget, GridSpacing, MagnifierConfig, MouseButton, MouseConfig,
+    ScrollAction, SyncGroup, Transform, DEFAULT_3D_SCALE, DEFAULT_HOVER_RADIUS, DEFAULT_MOVE_SCALE,
+    DEFAULT_SCROLL_SCALE,
 };
+pub use colormap::{Colormap, LOG_EPSILON};
+pub use elements::{draw_arrow, draw_at, draw_colored_line, draw_error_bars, split_non_finite};
+pub use format::{format_fixed, format_si};
+pub use legend::{LegendEntry, LegendRecorder, LegendStyle};
+pub use mapping::{CoordMapping, MappingRecorder};
+pub use plot::plot;
+pub use series::SeriesRegistry;
+pub use subplot::SubplotGrid;
 
 #[cfg(feature = "timechart")]
 use std::ops::Range;
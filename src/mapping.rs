@@ -0,0 +1,199 @@
+//! Mapping between the pixels of a chart and the data plotted in it
+
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use plotters::{
+    chart::ChartContext,
+    coord::{cartesian::Cartesian2d, ranged1d::Ranged},
+};
+use plotters_backend::DrawingBackend;
+
+/// Number of labels plotters' meshes have by default, which limits the number of major gridlines.
+const MESH_LABELS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Linear mapping between the pixels of a 2d chart's plotting area and its data coordinates.
+///
+/// Pixels are in the coordinates of the backend, before the Chart's offset and scale are
+/// applied. Only linear axes are supported, logarithmic or categorical axes won't map correctly.
+///
+/// ```
+/// # use egui_plotter::CoordMapping;
+/// # use plotters::prelude::*;
+/// # let mut buffer = vec![0; 101 * 101 * 3];
+/// # let area = BitMapBackend::with_buffer(&mut buffer, (101, 101)).into_drawing_area();
+/// let chart = ChartBuilder::on(&area)
+///     .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+///     .unwrap();
+///
+/// let mapping = CoordMapping::from_chart(&chart);
+///
+/// assert_eq!(mapping.pixel_to_data((0.0, 100.0)), (0.0, 0.0));
+/// assert_eq!(mapping.data_to_pixel((5.0, 5.0)), (50.0, 50.0));
+/// ```
+pub struct CoordMapping {
+    x_pixels: (f64, f64),
+    y_pixels: (f64, f64),
+    x_range: Range<f64>,
+    y_range: Range<f64>,
+}
+
+impl CoordMapping {
+    /// Create a mapping from a chart built on a drawing area.
+    pub fn from_chart<DB, X, Y>(chart: &ChartContext<DB, Cartesian2d<X, Y>>) -> Self
+    where
+        DB: DrawingBackend,
+        X: Ranged,
+        Y: Ranged,
+        X::ValueType: Into<f64>,
+        Y::ValueType: Into<f64>,
+    {
+        let (x_pixels, y_pixels) = chart.plotting_area().get_pixel_range();
+
+        let x_range = chart.x_range();
+        let y_range = chart.y_range();
+
+        // Plotters maps the ends of ranges onto the last pixel, with Y going from the bottom up
+        Self {
+            x_pixels: (x_pixels.start as f64, (x_pixels.end - 1) as f64),
+            y_pixels: ((y_pixels.end - 1) as f64, y_pixels.start as f64),
+            x_range: x_range.start.into()..x_range.end.into(),
+            y_range: y_range.start.into()..y_range.end.into(),
+        }
+    }
+
+    #[inline]
+    /// Get the X range of the chart's data.
+    pub fn x_range(&self) -> Range<f64> {
+        self.x_range.clone()
+    }
+
+    #[inline]
+    /// Get the Y range of the chart's data.
+    pub fn y_range(&self) -> Range<f64> {
+        self.y_range.clone()
+    }
+
+    /// Convert a pixel position into data coordinates.
+    pub fn pixel_to_data(&self, pixel: (f64, f64)) -> (f64, f64) {
+        let (x, y) = pixel;
+
+        (
+            lerp(x, self.x_pixels, &self.x_range),
+            lerp(y, self.y_pixels, &self.y_range),
+        )
+    }
+
+    /// Get the pixel position of the first of the major gridlines plotters draws by default on
+    /// each axis, rounded to a whole pixel as plotters does.
+    pub(crate) fn major_gridline(&self) -> (f64, f64) {
+        let gridline = |range: &Range<f64>| {
+            let step = major_step(range);
+
+            (range.start.min(range.end) / step).ceil() * step
+        };
+
+        let (x, y) = self.data_to_pixel((gridline(&self.x_range), gridline(&self.y_range)));
+
+        (x.round(), y.round())
+    }
+
+    /// Convert data coordinates into a pixel position.
+    pub fn data_to_pixel(&self, data: (f64, f64)) -> (f64, f64) {
+        let (x, y) = data;
+
+        let (x_start, x_end) = self.x_pixels;
+        let (y_start, y_end) = self.y_pixels;
+
+        (
+            lerp(x, (self.x_range.start, self.x_range.end), &(x_start..x_end)),
+            lerp(y, (self.y_range.start, self.y_range.end), &(y_start..y_end)),
+        )
+    }
+}
+
+/// Smallest step of 1, 2 or 5 times a power of ten that gives at most `MESH_LABELS` multiples
+/// within the range, matching how plotters places gridlines.
+fn major_step(range: &Range<f64>) -> f64 {
+    let (start, end) = (range.start.min(range.end), range.start.max(range.end));
+    let span = end - start;
+
+    if !(span > 0.0 && span.is_finite()) {
+        return 1.0;
+    }
+
+    let mut magnitude = 10f64.powf((span / MESH_LABELS as f64).log10().floor());
+
+    loop {
+        for factor in [1.0, 2.0, 5.0] {
+            let step = magnitude * factor;
+            let count = (end / step).floor() - (start / step).ceil() + 1.0;
+
+            if count <= MESH_LABELS as f64 {
+                return step;
+            }
+        }
+
+        magnitude *= 10.0;
+    }
+}
+
+/// Map a value from one range onto another.
+fn lerp(value: f64, from: (f64, f64), to: &Range<f64>) -> f64 {
+    let (from_start, from_end) = from;
+
+    to.start + (value - from_start) / (from_end - from_start) * (to.end - to.start)
+}
+
+#[derive(Debug, Clone, Default)]
+/// Shared handle used to record a chart's [`CoordMapping`] from inside its builder callback.
+///
+/// Get one from `Chart::mapping_recorder`, move it into your builder callback and call `record`
+/// once the chart has been built:
+///
+/// ```ignore
+/// let chart = Chart::new(());
+/// let recorder = chart.mapping_recorder();
+///
+/// let chart = chart.builder_cb(Box::new(move |area, _t, _d| {
+///     let chart = ChartBuilder::on(area)
+///         .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+///         .unwrap();
+///
+///     recorder.record(&chart);
+/// }));
+/// ```
+pub struct MappingRecorder(Rc<RefCell<Option<CoordMapping>>>);
+
+impl MappingRecorder {
+    #[inline]
+    /// Create a new, empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Record the mapping of a chart.
+    pub fn record<DB, X, Y>(&self, chart: &ChartContext<DB, Cartesian2d<X, Y>>)
+    where
+        DB: DrawingBackend,
+        X: Ranged,
+        Y: Ranged,
+        X::ValueType: Into<f64>,
+        Y::ValueType: Into<f64>,
+    {
+        self.set(Some(CoordMapping::from_chart(chart)))
+    }
+
+    #[inline]
+    /// Set the recorded mapping directly.
+    pub fn set(&self, mapping: Option<CoordMapping>) {
+        *self.0.borrow_mut() = mapping
+    }
+
+    #[inline]
+    /// Get the last recorded mapping, if any.
+    pub fn get(&self) -> Option<CoordMapping> {
+        self.0.borrow().clone()
+    }
+}
@@ -0,0 +1,60 @@
+//! Immediate mode plotting for one-off plots
+
+use egui::{Response, Sense, Ui, Vec2};
+use plotters::{
+    coord::Shift,
+    prelude::{DrawingArea, IntoDrawingArea},
+    style::RGBColor,
+};
+
+use crate::EguiBackend;
+
+/// Draw a plot of the given size in a single call, without setting up a backend or a Chart.
+///
+/// The area is filled with the ui's background color before the closure is called, and presented
+/// afterwards. Returns the response of the allocated area.
+///
+/// ```no_run
+/// # use egui_plotter::plot;
+/// # use plotters::prelude::*;
+/// # fn show(ui: &mut egui::Ui) {
+/// plot(ui, egui::vec2(300.0, 200.0), |area| {
+///     let mut chart = ChartBuilder::on(area)
+///         .margin(5)
+///         .x_label_area_size(30)
+///         .y_label_area_size(30)
+///         .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)
+///         .unwrap();
+///
+///     chart.configure_mesh().draw().unwrap();
+///
+///     chart
+///         .draw_series(LineSeries::new(
+///             (-50..=50).map(|x| x as f32 / 50.0).map(|x| (x, x * x)),
+///             &RED,
+///         ))
+///         .unwrap();
+/// });
+/// # }
+/// ```
+pub fn plot<F>(ui: &mut Ui, size: Vec2, plot_cb: F) -> Response
+where
+    F: FnOnce(&DrawingArea<EguiBackend, Shift>),
+{
+    let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+
+    let plot_ui = ui.child_ui(rect, *ui.layout());
+
+    let background = plot_ui.visuals().extreme_bg_color;
+    let background = RGBColor(background.r(), background.g(), background.b());
+
+    let area = EguiBackend::new(&plot_ui).into_drawing_area();
+
+    area.fill(&background).unwrap();
+
+    plot_cb(&area);
+
+    area.present().unwrap();
+
+    response
+}
@@ -0,0 +1,202 @@
+//! Minimal software rasterizer for egui meshes, used to render charts offscreen
+
+use egui::{
+    epaint::{tessellate_shapes, ClippedPrimitive, ClippedShape, Primitive, Vertex},
+    Color32, ColorImage, Context, Pos2, Rect, TextureId,
+};
+
+/// Texture sampled by meshes, with the premultiplied colors of each pixel.
+struct Texture<'a> {
+    size: [usize; 2],
+    pixels: &'a [Color32],
+}
+
+impl Texture<'_> {
+    /// Sample the nearest pixel to the given uv coordinate.
+    fn sample(&self, uv: Pos2) -> Color32 {
+        let [width, height] = self.size;
+
+        if width == 0 || height == 0 {
+            return Color32::WHITE;
+        }
+
+        let x = ((uv.x * width as f32) as usize).min(width - 1);
+        let y = ((uv.y * height as f32) as usize).min(height - 1);
+
+        self.pixels[y * width + x]
+    }
+}
+
+/// Tessellate and rasterize shapes, in points, into an image of the given size in pixels using
+/// the context's fonts and tessellation options. Must be called during a frame.
+pub(crate) fn render_shapes(
+    ctx: &Context,
+    shapes: Vec<ClippedShape>,
+    size: [usize; 2],
+) -> ColorImage {
+    let pixels_per_point = ctx.pixels_per_point();
+
+    let (font_tex_size, prepared_discs) = ctx.fonts(|fonts| {
+        let atlas = fonts.texture_atlas();
+        let atlas = atlas.lock();
+
+        (atlas.size(), atlas.prepared_discs())
+    });
+
+    let primitives = tessellate_shapes(
+        pixels_per_point,
+        ctx.tessellation_options(|options| *options),
+        font_tex_size,
+        prepared_discs,
+        shapes,
+    );
+
+    let font_image = ctx.fonts(|fonts| fonts.image());
+    let font_pixels: Vec<Color32> = font_image.srgba_pixels(None).collect();
+
+    let font = Texture {
+        size: font_image.size,
+        pixels: &font_pixels,
+    };
+
+    rasterize(&primitives, &font, size, pixels_per_point)
+}
+
+/// Rasterize tessellated primitives, in points, into an image of the given size in pixels.
+///
+/// Meshes using the font texture sample it, meshes using any other texture are drawn as if it
+/// were white, and paint callbacks are skipped as they need a GPU.
+fn rasterize(
+    primitives: &[ClippedPrimitive],
+    font: &Texture,
+    size: [usize; 2],
+    pixels_per_point: f32,
+) -> ColorImage {
+    let mut image = ColorImage::new(size, Color32::TRANSPARENT);
+
+    let [width, height] = size;
+    let image_rect = Rect::from_min_size(Pos2::ZERO, egui::vec2(width as f32, height as f32));
+
+    for clipped in primitives {
+        let mesh = match &clipped.primitive {
+            Primitive::Mesh(mesh) => mesh,
+            Primitive::Callback(_) => continue,
+        };
+
+        let clip_rect = Rect::from_min_max(
+            clipped.clip_rect.min * pixels_per_point,
+            clipped.clip_rect.max * pixels_per_point,
+        )
+        .intersect(image_rect);
+
+        if !clip_rect.is_positive() {
+            continue;
+        }
+
+        let texture = match mesh.texture_id == TextureId::default() {
+            true => Some(font),
+            false => None,
+        };
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|i| {
+                let vertex = mesh.vertices[triangle[i] as usize];
+
+                Vertex {
+                    pos: (vertex.pos.to_vec2() * pixels_per_point).to_pos2(),
+                    ..vertex
+                }
+            });
+
+            draw_triangle(&mut image, vertices, clip_rect, texture);
+        }
+    }
+
+    image
+}
+
+/// Signed area of the parallelogram spanned by the edge from a to b and the point p.
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Draw a single triangle, in pixels, blending it over the image.
+fn draw_triangle(
+    image: &mut ColorImage,
+    mut vertices: [Vertex; 3],
+    clip_rect: Rect,
+    texture: Option<&Texture>,
+) {
+    let area = edge(vertices[0].pos, vertices[1].pos, vertices[2].pos);
+
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+
+    // Wind every triangle the same way so the fill rule below works
+    if area < 0.0 {
+        vertices.swap(1, 2);
+    }
+
+    let area = area.abs();
+    let [a, b, c] = vertices.map(|vertex| vertex.pos);
+
+    let bounds = Rect::from_points(&[a, b, c]).intersect(clip_rect);
+
+    if !bounds.is_positive() {
+        return;
+    }
+
+    // Pixels exactly on an edge shared by two triangles belong to only one of them, so
+    // translucent shapes don't get seams
+    let owns = |from: Pos2, to: Pos2, weight: f32| {
+        let delta = to - from;
+
+        weight > 0.0 || (weight == 0.0 && (delta.y > 0.0 || (delta.y == 0.0 && delta.x < 0.0)))
+    };
+
+    let width = image.size[0];
+
+    for y in bounds.min.y.floor() as usize..bounds.max.y.ceil() as usize {
+        for x in bounds.min.x.floor() as usize..bounds.max.x.ceil() as usize {
+            let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+
+            if !clip_rect.contains(p) {
+                continue;
+            }
+
+            let w = [edge(b, c, p), edge(c, a, p), edge(a, b, p)];
+
+            if !(owns(b, c, w[0]) && owns(c, a, w[1]) && owns(a, b, w[2])) {
+                continue;
+            }
+
+            let w = w.map(|w| w / area);
+
+            let mut color = [0.0; 4];
+            let mut uv = Pos2::ZERO;
+
+            for (vertex, w) in vertices.iter().zip(w) {
+                for (channel, value) in color.iter_mut().zip(vertex.color.to_array()) {
+                    *channel += value as f32 * w;
+                }
+
+                uv += vertex.uv.to_vec2() * w;
+            }
+
+            let texel = texture.map_or(Color32::WHITE, |texture| texture.sample(uv));
+
+            let source = [0, 1, 2, 3].map(|i| color[i] * texel.to_array()[i] as f32 / 255.0);
+
+            let pixel = &mut image.pixels[y * width + x];
+            let inv_alpha = 1.0 - source[3] / 255.0;
+
+            // Premultiplied alpha blending
+            let blended = [0, 1, 2, 3]
+                .map(|i| (source[i] + pixel.to_array()[i] as f32 * inv_alpha).round() as u8);
+
+            *pixel =
+                Color32::from_rgba_premultiplied(blended[0], blended[1], blended[2], blended[3]);
+        }
+    }
+}
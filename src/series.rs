@@ -0,0 +1,317 @@
+//! Registry of a chart's series, giving each a color and z-order and detecting the one under the
+//! mouse, so multi-series charts stay consistent without configuration
+
+use std::{cell::RefCell, rc::Rc};
+
+use egui::Pos2;
+use plotters::style::{Color, Palette, Palette99, RGBAColor};
+
+use crate::LegendEntry;
+
+#[derive(Debug, Clone, Default)]
+/// Shared handle that registers the series of a chart by name. Each series is given an id, in the
+/// order they're registered, and its own color, picked in turn from plotters' 99 color palette.
+/// The registered series are listed for the chart's legend, and can be checked against the mouse
+/// to find the one being hovered.
+///
+/// Get one from `Chart::series_registry`, move it into your builder callback and ask it for each
+/// series' color by name. A series keeps its id and color for as long as the handle lives, however
+/// the series are ordered from frame to frame. Enable `Chart::auto_legend` to have the registered
+/// series shown in the legend.
+///
+/// Each series also has a z-order, 0 unless set with `set_z`. Whatever is drawn after taking a
+/// series' color with `color`, or tracking it with `track`, belongs to that series until the next
+/// one is taken, and the chart paints it above series with a lower z and below those with a
+/// higher one. Anything drawn before the first series, such as the background and mesh, is
+/// painted below every series, and anything drawn after calling `end_series` above them.
+///
+/// To find the hovered series, pass each series' points to `track` as it's drawn. The chart also
+/// needs a [`MappingRecorder`] to map the points onto the screen. As the points are tracked while
+/// drawing, the hovered series is found using those tracked the frame before.
+///
+/// ```ignore
+/// let chart = Chart::new(series).auto_legend(true);
+/// let recorder = chart.mapping_recorder();
+/// let registry = chart.series_registry();
+///
+/// let chart = chart.builder_cb(Box::new(move |area, _t, series| {
+///     let mut chart = ChartBuilder::on(area)
+///         .build_cartesian_2d(0f32..10f32, 0f32..10f32)
+///         .unwrap();
+///
+///     recorder.record(&chart);
+///
+///     for (name, points) in series {
+///         let width = match registry.track(name, points.iter().copied()) {
+///             true => 4,
+///             false => 2,
+///         };
+///
+///         chart
+///             .draw_series(LineSeries::new(
+///                 points.iter().copied(),
+///                 registry.color(name).stroke_width(width),
+///             ))
+///             .unwrap();
+///     }
+/// }));
+/// ```
+///
+/// [`MappingRecorder`]: crate::MappingRecorder
+pub struct SeriesRegistry(Rc<RefCell<Registry>>);
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// Series in the order they were registered, so each one's index is its id.
+    series: Vec<Series>,
+    /// Id of the series found under the mouse at the last update.
+    hovered: Option<usize>,
+    /// Id of the series being drawn, which shapes drawn now belong to.
+    current: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+/// A registered series.
+struct Series {
+    name: String,
+    id: usize,
+    color: RGBAColor,
+    z: i32,
+    /// Points tracked since the last update, in data coordinates.
+    points: Option<Vec<(f64, f64)>>,
+}
+
+impl SeriesRegistry {
+    #[inline]
+    /// Create a new handle with no series.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the id of a series, registering it if it's new. Ids count up from 0 in the order
+    /// series are registered.
+    pub fn id(&self, name: &str) -> usize {
+        self.with_series(name, |series| series.id)
+    }
+
+    /// Return the name of the series with the given id, if one is registered.
+    pub fn name(&self, id: usize) -> Option<String> {
+        self.0
+            .borrow()
+            .series
+            .get(id)
+            .map(|series| series.name.clone())
+    }
+
+    /// Return the color of a series, registering it with the next color of the palette if it's
+    /// new.
+    ///
+    /// ```
+    /// # use egui_plotter::SeriesRegistry;
+    /// let registry = SeriesRegistry::new();
+    ///
+    /// let pressure = registry.color("Pressure");
+    ///
+    /// assert_ne!(registry.color("Temperature"), pressure);
+    /// assert_eq!(registry.color("Pressure"), pressure);
+    /// assert_eq!(registry.id("Temperature"), 1);
+    /// ```
+    pub fn color(&self, name: &str) -> RGBAColor {
+        let (id, color) = self.with_series(name, |series| (series.id, series.color));
+
+        self.0.borrow_mut().current = Some(id);
+
+        color
+    }
+
+    /// Override the color of a series, registering it if it's new.
+    pub fn set_color<C: Color>(&self, name: &str, color: &C) {
+        let color = color.to_rgba();
+
+        self.with_series(name, |series| series.color = color)
+    }
+
+    /// Return the z-order of a series, registering it if it's new. Defaults to 0.
+    pub fn z(&self, name: &str) -> i32 {
+        self.with_series(name, |series| series.z)
+    }
+
+    /// Set the z-order of a series, registering it if it's new. Charts paint series with a higher
+    /// z on top of those with a lower one, whatever order they're drawn in.
+    pub fn set_z(&self, name: &str, z: i32) {
+        self.with_series(name, |series| series.z = z)
+    }
+
+    /// Sort series into the order they should be drawn in, from the lowest z to the highest, given
+    /// a function returning the name of each. Series with the same z keep their order. Charts
+    /// order series by z themselves, so this is only needed when drawing elsewhere.
+    ///
+    /// ```
+    /// # use egui_plotter::SeriesRegistry;
+    /// let registry = SeriesRegistry::new();
+    ///
+    /// registry.set_z("Line", 1);
+    /// registry.set_z("Fill", -1);
+    ///
+    /// let mut series = ["Line", "Points", "Fill"];
+    /// registry.sort_by_z(&mut series, |name| name);
+    ///
+    /// assert_eq!(series, ["Fill", "Points", "Line"]);
+    /// ```
+    pub fn sort_by_z<T, F>(&self, series: &mut [T], name: F)
+    where
+        F: Fn(&T) -> &str,
+    {
+        series.sort_by_key(|series| self.z(name(series)))
+    }
+
+    /// Track a series' points for finding the hovered series, registering it if it's new.
+    /// Returns true if it's the series being hovered.
+    pub fn track<X, Y, I>(&self, name: &str, points: I) -> bool
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+        I: IntoIterator<Item = (X, Y)>,
+    {
+        let points = points
+            .into_iter()
+            .map(|(x, y)| (x.into(), y.into()))
+            .collect();
+
+        let id = self.with_series(name, |series| {
+            series.points = Some(points);
+
+            series.id
+        });
+
+        let mut registry = self.0.borrow_mut();
+
+        registry.current = Some(id);
+
+        registry.hovered == Some(id)
+    }
+
+    #[inline]
+    /// Stop assigning what's drawn to the last series taken, so anything drawn next, such as
+    /// plotters' own legend, is painted above every series.
+    pub fn end_series(&self) {
+        self.0.borrow_mut().current = None
+    }
+
+    /// Return the z-order of the series being drawn, if there is one.
+    pub(crate) fn current_z(&self) -> Option<i32> {
+        let registry = self.0.borrow();
+
+        registry
+            .current
+            .and_then(|id| registry.series.get(id))
+            .map(|series| series.z)
+    }
+
+    #[inline]
+    /// Get the id of the series being hovered, if any.
+    pub fn hovered(&self) -> Option<usize> {
+        self.0.borrow().hovered
+    }
+
+    /// Call a function on a series, registering it with the next color of the palette if it's
+    /// new.
+    fn with_series<T, F>(&self, name: &str, f: F) -> T
+    where
+        F: FnOnce(&mut Series) -> T,
+    {
+        let series = &mut self.0.borrow_mut().series;
+
+        let index = match series.iter().position(|series| series.name == name) {
+            Some(index) => index,
+            None => {
+                let id = series.len();
+
+                series.push(Series {
+                    name: name.to_string(),
+                    id,
+                    color: Palette99::pick(id).to_rgba(),
+                    z: 0,
+                    points: None,
+                });
+
+                id
+            }
+        };
+
+        f(&mut series[index])
+    }
+
+    #[inline]
+    /// Forget every series, so ids and colors are assigned from the start again.
+    pub fn clear(&self) {
+        *self.0.borrow_mut() = Registry::default()
+    }
+
+    /// Return a legend entry for each series, in the order they were registered.
+    pub fn legend_entries(&self) -> Vec<LegendEntry> {
+        self.0
+            .borrow()
+            .series
+            .iter()
+            .map(|series| LegendEntry::new(series.name.as_str(), &series.color))
+            .collect()
+    }
+
+    /// Find the hovered series among those tracked, ready for them to be tracked again.
+    /// `to_screen` maps data coordinates onto the screen, and the series nearest `pointer` within
+    /// `radius` is hovered.
+    pub(crate) fn update(
+        &self,
+        pointer: Option<Pos2>,
+        radius: f32,
+        to_screen: Option<impl Fn((f64, f64)) -> Pos2>,
+    ) {
+        let mut registry = self.0.borrow_mut();
+
+        let tracked: Vec<(usize, Vec<(f64, f64)>)> = registry
+            .series
+            .iter_mut()
+            .filter_map(|series| Some((series.id, series.points.take()?)))
+            .collect();
+
+        registry.hovered = match (pointer, to_screen) {
+            (Some(pointer), Some(to_screen)) => tracked
+                .iter()
+                .map(|(id, points)| {
+                    let points: Vec<Pos2> = points.iter().copied().map(&to_screen).collect();
+
+                    (*id, distance_to_line(pointer, &points))
+                })
+                .filter(|(_, distance)| *distance <= radius)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(id, _)| id),
+            _ => None,
+        };
+    }
+}
+
+/// Distance from a point to the nearest point of a line through the given points.
+fn distance_to_line(point: Pos2, line: &[Pos2]) -> f32 {
+    match line {
+        [] => f32::INFINITY,
+        [single] => point.distance(*single),
+        _ => line
+            .windows(2)
+            .map(|segment| {
+                let (start, end) = (segment[0], segment[1]);
+                let direction = end - start;
+
+                let t = match direction.length_sq() > 0.0 {
+                    true => {
+                        ((point - start).dot(direction) / direction.length_sq()).clamp(0.0, 1.0)
+                    }
+                    false => 0.0,
+                };
+
+                point.distance(start + direction * t)
+            })
+            .filter(|distance| distance.is_finite())
+            .fold(f32::INFINITY, f32::min),
+    }
+}
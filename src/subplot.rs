@@ -0,0 +1,140 @@
+//! Layout of several charts stacked in rows, panning and zooming together
+
+use egui::{Rect, Ui, Vec2};
+
+use crate::{Axis, Chart, SyncGroup};
+
+/// Default space between rows, in points.
+const DEFAULT_ROW_SPACING: f32 = 4.0;
+
+/// Charts stacked in rows of equal height that share their X offset and scale, for dashboards
+/// plotting several quantities over the same X axis.
+///
+/// Each row is an ordinary [`Chart`] with its own data and builder callback, so each builds its
+/// own Y axis. Adding a row puts it in the grid's [`SyncGroup`], which pans and zooms every row
+/// together along X, and like any group only the hovered row responds to the mouse. The Y axis
+/// of every row is locked, so panning and zooming never move the rows' Y axes.
+///
+/// ```ignore
+/// let mut grid = SubplotGrid::new()
+///     .row(Chart::new(pressure).builder_cb(Box::new(draw_pressure)))
+///     .row(Chart::new(temperature).builder_cb(Box::new(draw_temperature)));
+///
+/// grid.draw(ui);
+/// ```
+pub struct SubplotGrid<Data = ()> {
+    rows: Vec<Chart<Data>>,
+    sync: SyncGroup,
+    row_spacing: f32,
+}
+
+impl<Data> Default for SubplotGrid<Data> {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            sync: SyncGroup::new(),
+            row_spacing: DEFAULT_ROW_SPACING,
+        }
+    }
+}
+
+impl<Data> SubplotGrid<Data> {
+    #[inline]
+    /// Create a new grid with no rows.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a chart as the bottom row, linking its X offset and scale with the other rows and
+    /// locking its Y axis.
+    pub fn push_row(&mut self, mut chart: Chart<Data>) {
+        chart.set_sync_group(Some(self.sync.clone()));
+        chart.set_mouse_lock_axis(Axis::Y, true);
+
+        self.rows.push(chart);
+    }
+
+    #[inline]
+    /// Add a chart as the bottom row. Consumes self.
+    pub fn row(mut self, chart: Chart<Data>) -> Self {
+        self.push_row(chart);
+
+        self
+    }
+
+    #[inline]
+    /// Set the space between rows, in points. Defaults to 4.
+    pub fn set_row_spacing(&mut self, row_spacing: f32) {
+        self.row_spacing = row_spacing
+    }
+
+    #[inline]
+    /// Set the space between rows, in points. Consumes self.
+    pub fn row_spacing(mut self, row_spacing: f32) -> Self {
+        self.set_row_spacing(row_spacing);
+
+        self
+    }
+
+    #[inline]
+    /// Get the charts of the rows, from top to bottom.
+    pub fn rows(&self) -> &[Chart<Data>] {
+        &self.rows
+    }
+
+    #[inline]
+    /// Get the chart of a row, counting from the top, to change its data or settings.
+    pub fn row_mut(&mut self, index: usize) -> Option<&mut Chart<Data>> {
+        self.rows.get_mut(index)
+    }
+
+    /// Return the rect each row is drawn in when the grid is given `rect`, from top to bottom.
+    ///
+    /// ```
+    /// # use egui::{Pos2, Rect};
+    /// # use egui_plotter::{Chart, SubplotGrid};
+    /// let grid = SubplotGrid::new()
+    ///     .row(Chart::new(()))
+    ///     .row(Chart::new(()))
+    ///     .row_spacing(10.0);
+    ///
+    /// let rects = grid.row_rects(Rect::from_min_max(Pos2::ZERO, Pos2::new(100.0, 210.0)));
+    ///
+    /// assert_eq!(rects[1], Rect::from_min_max(Pos2::new(0.0, 110.0), Pos2::new(100.0, 210.0)));
+    /// ```
+    pub fn row_rects(&self, rect: Rect) -> Vec<Rect> {
+        let count = self.rows.len();
+        let spacing = self.row_spacing.max(0.0);
+        let height = ((rect.height() - spacing * count.saturating_sub(1) as f32)
+            / count.max(1) as f32)
+            .max(0.0);
+
+        (0..count)
+            .map(|index| {
+                let min = rect.min + Vec2::new(0.0, index as f32 * (height + spacing));
+
+                Rect::from_min_size(min, Vec2::new(rect.width(), height))
+            })
+            .collect()
+    }
+
+    /// Draw every row, dividing the Ui's available space between them.
+    pub fn draw(&mut self, ui: &Ui) {
+        let rects = self.row_rects(ui.max_rect());
+
+        for (index, (chart, rect)) in self.rows.iter_mut().zip(rects).enumerate() {
+            // Keep Y locked even if the row's mouse controls were replaced since it was added
+            chart.set_mouse_lock_axis(Axis::Y, true);
+
+            let row_ui = Ui::new(
+                ui.ctx().clone(),
+                ui.layer_id(),
+                ui.id().with(("subplot", index)),
+                rect,
+                ui.clip_rect(),
+            );
+
+            chart.draw(&row_ui);
+        }
+    }
+}
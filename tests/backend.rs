@@ -0,0 +1,159 @@
+//! Checks of what the backend paints, made by drawing onto it in a headless egui context and
+//! inspecting the shapes it outputs.
+
+use egui::{
+    epaint::{ClippedShape, Primitive},
+    Color32, Context, FontDefinitions, FontFamily, Id, LayerId, Shape, Ui,
+};
+use egui_plotter::{EguiBackend, ShapeClass};
+use plotters::prelude::*;
+
+/// Draw onto an `EguiBackend` for one frame of the context, returning the shapes it painted. The
+/// backend is given a bare ui, so its shapes are the only ones.
+fn draw_shapes<F>(ctx: &Context, draw: F) -> Vec<ClippedShape>
+where
+    F: FnOnce(EguiBackend),
+{
+    let output = ctx.run(Default::default(), |ctx| {
+        let rect = ctx.screen_rect();
+        let ui = Ui::new(
+            ctx.clone(),
+            LayerId::background(),
+            Id::new("test"),
+            rect,
+            rect,
+        );
+
+        draw(EguiBackend::new(&ui));
+    });
+
+    output.shapes
+}
+
+#[test]
+fn translucent_fills_blend_like_plotters() {
+    let shapes = draw_shapes(&Context::default(), |backend| {
+        let root = backend.into_drawing_area();
+        root.draw(&Rectangle::new([(0, 0), (20, 20)], RED.mix(0.5).filled()))
+            .unwrap();
+        root.draw(&Rectangle::new([(10, 10), (30, 30)], RED.mix(0.5).filled()))
+            .unwrap();
+    });
+
+    let fills: Vec<_> = shapes
+        .iter()
+        .filter_map(|clipped| match &clipped.shape {
+            Shape::Rect(rect) => Some(rect.fill),
+            _ => None,
+        })
+        .collect();
+
+    // Each rectangle is a single shape at 50% alpha
+    assert_eq!(fills.len(), 2);
+    assert!(fills.iter().all(|fill| fill.a() == 128));
+
+    // Blend both premultiplied fills over white to get the color of the overlap
+    let overlap = fills.iter().fold([255u32; 3], |dst, fill| {
+        let src = [fill.r(), fill.g(), fill.b()];
+        let inv_alpha = 255 - fill.a() as u32;
+
+        [0, 1, 2].map(|i| src[i] as u32 + dst[i] * inv_alpha / 255)
+    });
+
+    assert_eq!(overlap, [255, 63, 63]);
+}
+
+#[test]
+fn translucent_text_is_drawn_at_its_opacity() {
+    let ctx = Context::default();
+
+    let shapes = draw_shapes(&ctx, |backend| {
+        let root = backend.into_drawing_area();
+        let color = RED.mix(0.5);
+        let style = TextStyle::from(("sans-serif", 12)).color(&color);
+
+        root.draw(&Text::new("Half", (10, 10), style)).unwrap();
+    });
+
+    // The colors the glyphs are rendered in, once egui has resolved the text's colors
+    let colors: Vec<Color32> = ctx
+        .tessellate(shapes, ctx.pixels_per_point())
+        .into_iter()
+        .flat_map(|clipped| match clipped.primitive {
+            Primitive::Mesh(mesh) => mesh.vertices,
+            Primitive::Callback(_) => Vec::new(),
+        })
+        .map(|vertex| vertex.color)
+        .collect();
+
+    assert!(!colors.is_empty());
+    assert!(colors
+        .iter()
+        .all(|color| *color == Color32::from_rgba_premultiplied(128, 0, 0, 128)));
+}
+
+#[test]
+fn text_falls_back_to_strokes_without_fonts() {
+    let ctx = Context::default();
+
+    let mut fonts = FontDefinitions::empty();
+    fonts.families.insert(FontFamily::Proportional, Vec::new());
+    fonts.families.insert(FontFamily::Monospace, Vec::new());
+    ctx.set_fonts(fonts);
+
+    let shapes = draw_shapes(&ctx, |backend| {
+        let root = backend.into_drawing_area();
+        root.draw(&Text::new("1.5", (10, 10), ("sans-serif", 12)))
+            .unwrap();
+    });
+
+    let strokes = shapes
+        .iter()
+        .filter(|clipped| matches!(clipped.shape, Shape::LineSegment { .. }))
+        .count();
+
+    // Two segments for the 1, one for the dot and five for the 5
+    assert_eq!(strokes, 8);
+}
+
+#[test]
+fn grid_feathering_is_set_apart_from_lines() {
+    let ctx = Context::default();
+
+    let draw = |backend: EguiBackend| {
+        let root = backend.into_drawing_area();
+
+        root.draw(&PathElement::new([(0, 10), (100, 10)], BLACK))
+            .unwrap();
+        root.draw(&PathElement::new([(0, 0), (50, 30), (100, 0)], BLUE))
+            .unwrap();
+    };
+    let meshes = |shapes: Vec<ClippedShape>| -> Vec<bool> {
+        shapes
+            .into_iter()
+            .map(|clipped| matches!(clipped.shape, Shape::Mesh(_)))
+            .collect()
+    };
+
+    // Only the gridline is tessellated by the backend, to turn its feathering off
+    let shapes = draw_shapes(&ctx, |backend| {
+        draw(backend.feathering_for(ShapeClass::Grid, false))
+    });
+
+    assert_eq!(meshes(shapes), [true, false]);
+
+    // Feathering is forced on for the series with feathering off in egui
+    ctx.tessellation_options_mut(|options| options.feathering = false);
+
+    let shapes = draw_shapes(&ctx, |backend| {
+        draw(backend.feathering_for(ShapeClass::Lines, true))
+    });
+
+    let feathered = shapes.iter().any(|clipped| match &clipped.shape {
+        Shape::Mesh(mesh) => mesh.vertices.iter().any(|vertex| vertex.color.a() == 0),
+        _ => false,
+    });
+
+    assert!(feathered);
+    assert_eq!(meshes(shapes), [false, true]);
+}